@@ -1,48 +1,296 @@
-use std::time::{Duration, Instant};
+mod focus;
+mod hints;
+mod history;
+mod macros;
+mod panels;
+mod plain;
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime};
 
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    widgets::Widget,
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
+    text::Line,
+    widgets::{Clear, Paragraph, Widget, Wrap},
 };
+use serde::{Deserialize, Serialize};
 
-use crate::daemon::{DaemonHandle, HardwareState, HardwareUpdate, PowerProfile};
+use crate::automation::{self, Environment, ProcessWatch};
+use crate::clipboard;
+use crate::config::Config;
+use crate::console::{Console, RawCall};
+use crate::daemon::{
+    AnimeBinding, AsusdVersion, DaemonHandle, DaemonSignal, Fan, FanCurve, GpuMode, HardwareState,
+    HardwareUpdate, KbdBrightness, PowerProfile, Rgb, Rollback, SlashChange, CHARGE_LIMIT_RANGE,
+    FAN_HYSTERESIS_RANGE, SLASH_MODES,
+};
+use crate::diagnose::{Bundle, LogEntry};
+use crate::export;
+use crate::health::{self, CheckStatus, HealthView};
+use crate::preset::{self, Preset};
+use crate::recorder::{Recorder, TelemetryRecord};
+use crate::session::{PerProfile, ProfileLimits, UiSession};
+use crate::stats::SessionStats;
+use crate::sysfs::{self, Backlight, Peripheral};
+use crate::telemetry::{self, Telemetry, LOW_POWER_SAMPLE_INTERVAL, SAMPLE_INTERVAL};
+use crate::tune::{TuneRun, Tuning};
+use crate::ui::picker::PickerRow;
+use crate::ui::theme::{profile_color, styles};
 use crate::ui::{
-    colors, BatteryKatana, EffectManager, FanCurveGraph, Header, HelpPopup,
-    PowerProfileSelector, SakuraShader, StatusBar,
+    colors, compat, format_timeout, BatteryKatana, Clock, ColorPicker, ConsolePopup, Dashboard,
+    EffectManager,
+    FanCurveGraph, Header, HealthPopup, HelpPopup, MessagesPopup, PanelHelp, PerfOverlay,
+    PerfStats, PickerState, SakuraShader, StatusBar, TabBar, Toast, TunePopup, WhichKey,
 };
 
+use macros::{MacroAction, Macros};
+pub use history::{History, Message, MessagesView};
+pub use focus::FocusRing;
+pub use panels::{LayoutPreset, PanelId, REGISTRY};
+
 /// Which panel is currently focused
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum FocusedPanel {
     PowerProfile,
     Battery,
     FanCurve,
+    Lighting,
+    Advanced,
+    /// AniMe Matrix or Slash lid lighting, whichever the laptop has
+    Lid,
+    /// TDP quick presets (handheld layout only)
+    Tdp,
+    /// Saved setting bundles
+    Presets,
 }
 
-impl FocusedPanel {
-    pub fn next(self) -> Self {
-        match self {
-            Self::PowerProfile => Self::Battery,
-            Self::Battery => Self::FanCurve,
-            Self::FanCurve => Self::PowerProfile,
-        }
-    }
+/// Top-level views
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Tab {
+    /// Summary of the most important state
+    Dashboard,
+    /// The detail panels
+    Panels,
+}
 
-    pub fn prev(self) -> Self {
-        match self {
-            Self::PowerProfile => Self::FanCurve,
-            Self::Battery => Self::PowerProfile,
-            Self::FanCurve => Self::Battery,
-        }
-    }
+/// Animation running on the battery katana's blade
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BladeEffect {
+    /// Energy flowing toward the tip
+    Charging,
+    /// Red pulse, discharging below `LOW_BATTERY_PERCENT`
+    Low,
 }
 
+/// Tab strip entries as (hotkey, title)
+const TABS: [(&str, &str); 2] = [("0", "Dashboard"), ("1-8", "Panels")];
+
 /// Edit mode for interactive widgets
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EditMode {
     None,
     Battery,
     FanCurve { point_index: usize },
+    /// Choosing which fans follow their custom curve in which profile
+    FanEnablement { profile: PowerProfile, fan: Fan },
+    /// Choosing where to copy the fan curve and how far to move its speeds
+    CurveCopy { target: CurveTarget, offset: i8 },
+    KbdIdleTimeout,
+    /// Picking a static keyboard color
+    KbdColor,
+    /// Typing a preset name, for a new preset or to rename the selected one
+    PresetName { rename: bool },
+    /// Waiting for Enter to delete the selected preset
+    PresetDelete,
+    /// Waiting for Enter to move the GPU MUX, offered by a profile rule
+    GpuSwitch(GpuMode),
+}
+
+/// Where `C` on the fan panel copies the curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveTarget {
+    /// The CPU fan's points onto the GPU fan
+    Gpu,
+    /// The whole curve to another profile's curves in asusd
+    Profile(PowerProfile),
+}
+
+impl CurveTarget {
+    /// Targets in the order h/l step through, leaving out the active profile
+    fn all(active: PowerProfile) -> Vec<Self> {
+        let profiles = PowerProfile::ALL.into_iter().filter(|&profile| profile != active);
+        std::iter::once(Self::Gpu).chain(profiles.map(Self::Profile)).collect()
+    }
+
+    /// `CPU → GPU` or `Balanced → Quiet, speeds -10%`
+    pub fn describe(self, active: PowerProfile, offset: i8) -> String {
+        let copy = match self {
+            Self::Gpu => "CPU → GPU".to_string(),
+            Self::Profile(profile) => format!("{} → {}", active, profile),
+        };
+        match offset {
+            0 => copy,
+            _ => format!("{}, speeds {:+}%", copy, offset),
+        }
+    }
+}
+
+/// Speed offset step and bound for curve copies (percentage points)
+const CURVE_COPY_STEP: i8 = 5;
+const CURVE_COPY_OFFSET_MAX: i8 = 50;
+
+/// Longest preset name accepted by the name editor
+const PRESET_NAME_MAX: usize = 32;
+
+/// Step and upper bound for the keyboard idle timeout editor (seconds)
+const KBD_TIMEOUT_STEP: u32 = 15;
+const KBD_TIMEOUT_MAX: u32 = 3600;
+
+/// Display brightness step for `+` / `-` (percent)
+const DISPLAY_BRIGHTNESS_STEP: u8 = 5;
+
+/// Charge limit and fan speed step per scroll-wheel notch (percent)
+const SCROLL_STEP: u8 = 5;
+
+/// Rows of the Slash panel: enabled, pattern, brightness, interval
+const SLASH_ROWS: usize = 4;
+const SLASH_BRIGHTNESS_STEP: u8 = 32;
+const SLASH_INTERVAL_MAX: u8 = 5;
+
+/// Frame pacing: full rate while particles or effects move, a slower rate
+/// for static content, and slowest while the terminal is in the background
+const ANIMATED_FRAME: Duration = Duration::from_millis(1000 / 60);
+const STATIC_FRAME: Duration = Duration::from_millis(1000 / 10);
+const UNFOCUSED_FRAME: Duration = Duration::from_millis(1000 / 2);
+
+/// Terminal height below which the header collapses to a single row
+const COMPACT_HEADER_BELOW: u16 = 30;
+
+/// How long full blast pins the fans at 100% before reverting
+const FULL_BLAST_TIME: Duration = Duration::from_secs(5 * 60);
+
+/// Time the fans get to reach a new profile's speeds before they count
+/// towards its loudest speed
+const FAN_SETTLE: Duration = Duration::from_secs(60);
+
+/// Charge below which a discharging battery pulses the katana red
+const LOW_BATTERY_PERCENT: u8 = 20;
+
+/// How long `r` waits for asusd to answer before giving up
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a health check that passed everything stays on screen
+const HEALTH_DISMISS: Duration = Duration::from_secs(2);
+
+/// Frames whose draw time the performance overlay keeps
+const FRAME_SAMPLES: usize = 60;
+
+/// How long quitting waits for pending hardware writes
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Longest gap between presses still treated as one held key; covers the
+/// usual 250-660 ms delay before auto-repeat kicks in
+const REPEAT_GAP: Duration = Duration::from_millis(700);
+
+/// How long a digit typed on a list panel waits for `j`/`k` before it
+/// jumps to its panel as usual
+const COUNT_JUMP_DELAY: Duration = Duration::from_millis(600);
+
+/// Tracks how long a key has been held, from the timing of its auto-repeats
+struct KeyRepeat {
+    code: Option<crossterm::event::KeyCode>,
+    started: Instant,
+    last: Instant,
+}
+
+impl KeyRepeat {
+    fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            code: None,
+            started: now,
+            last: now,
+        }
+    }
+
+    /// Record a press, returning how long the key has been held
+    fn press(&mut self, code: crossterm::event::KeyCode) -> Duration {
+        let now = Instant::now();
+        if self.code != Some(code) || now - self.last > REPEAT_GAP {
+            self.code = Some(code);
+            self.started = now;
+        }
+        self.last = now;
+        now - self.started
+    }
+}
+
+/// Vim-style numeric prefix for the next key, e.g. `20l` to add 20% or
+/// `3j` to move three rows down a list
+#[derive(Debug, Default)]
+pub struct CountPrefix(Option<u32>);
+
+impl CountPrefix {
+    /// Large enough for any value hachi edits (idle timeout in seconds)
+    const MAX: u32 = 9999;
+
+    /// Add a digit to the count, returning false for any other key
+    pub fn push(&mut self, code: crossterm::event::KeyCode) -> bool {
+        let crossterm::event::KeyCode::Char(c) = code else {
+            return false;
+        };
+        let Some(digit) = c.to_digit(10) else {
+            return false;
+        };
+        self.0 = Some((self.0.unwrap_or(0) * 10 + digit).min(Self::MAX));
+        true
+    }
+
+    pub fn take(&mut self) -> Option<u32> {
+        self.0.take()
+    }
+}
+
+/// Step for charge limit and fan speed edits: 5, then 10 after a second of
+/// holding the key, then 25
+fn accelerated_step(held: Duration) -> u8 {
+    match held.as_millis() {
+        0..=999 => 5,
+        1000..=1999 => 10,
+        _ => 25,
+    }
+}
+
+/// TDP quick presets for handhelds (watts)
+pub const TDP_PRESETS: [u8; 3] = [9, 15, 25];
+
+/// Severity of a status message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Severity {
+    /// Routine progress (refreshes, typed counts), hidden in the messages
+    /// popup unless asked for
+    Debug,
+    #[default]
+    Info,
+    Warning,
+    Error,
+    /// Shown as a toast in addition to the status bar
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warning => "warn",
+            Self::Error => "error",
+            Self::Critical => "crit",
+        }
+    }
 }
 
 /// Main application state
@@ -50,23 +298,182 @@ pub struct App {
     /// Hardware actor handle
     daemon: DaemonHandle,
 
+    /// User configuration
+    pub config: Config,
+
     /// Shadow state (local copy for immediate UI feedback)
     pub state: HardwareState,
 
+    /// System telemetry (CPU load/frequency, memory, temperatures)
+    pub telemetry: Telemetry,
+
+    /// Session statistics for the exit summary
+    pub stats: SessionStats,
+
+    /// Active telemetry recording, if any
+    pub recorder: Option<Recorder>,
+
     /// Currently focused panel
     pub focused: FocusedPanel,
 
+    /// Gamepad-friendly layout for handhelds like the ROG Ally
+    pub handheld: bool,
+
+    /// Selected TDP preset (index into `TDP_PRESETS`)
+    pub selected_tdp: usize,
+
     /// Currently selected power profile index
     pub selected_profile: usize,
 
+    /// Selected row in the Advanced panel (index into `Peripheral::ALL`)
+    pub selected_peripheral: usize,
+
+    /// Selected row in the lid panel (AniMe bindings or Slash settings)
+    pub selected_lid_row: usize,
+
+    /// Keyboard color picker, while `EditMode::KbdColor`
+    color_picker: PickerState,
+
+    /// Saved presets, sorted by name
+    pub presets: Vec<Preset>,
+
+    /// Selected row in the presets panel
+    pub selected_preset: usize,
+
+    /// Name being typed in `EditMode::PresetName`
+    pub preset_input: String,
+
     /// Current edit mode
     pub edit_mode: EditMode,
 
     /// Whether help popup is visible
     pub show_help: bool,
 
+    /// Panel whose keys the help popup lists, `None` for every key
+    pub help_panel: Option<PanelId>,
+
+    /// Every status message shown, for the messages popup
+    pub history: History,
+
+    /// Messages popup (`:`), when open
+    messages: Option<MessagesView>,
+
+    /// Stress runs for fan curve tuning, and whether their popup is open
+    tuning: Tuning,
+    pub show_tune: bool,
+
+    /// Health checklist, shown on launch and with `D`
+    pub health: Option<HealthView>,
+
+    /// Results of the last health check, for bug reports
+    health_checks: Vec<health::Check>,
+
+    /// Performance overlay (`F10`), and the draw times of the last frames
+    pub show_perf: bool,
+    frame_times: VecDeque<Duration>,
+
     /// Status message to display
-    pub status_message: Option<(String, Instant)>,
+    pub status_message: Option<(String, Severity, Instant)>,
+
+    /// Popup in the top-right corner for critical alerts and external changes
+    pub toast: Option<(String, Severity, Instant)>,
+
+    /// Whether a fan currently reads 0 RPM under load
+    pub fan_stall: bool,
+
+    /// Failed hardware writes the actor is still retrying
+    pub retrying: usize,
+
+    /// Monitor only (`--read-only`): controls are greyed out and inert
+    pub read_only: bool,
+
+    /// 256 colors, ASCII glyphs and at most 10 FPS, for SSH sessions
+    pub safe_render: bool,
+
+    /// `--debug-console`: `` ` `` opens the raw D-Bus console
+    pub debug_console: bool,
+    pub console: Option<Console>,
+    /// `--plain`: text lines for screen readers instead of the panels
+    pub plain: bool,
+
+    /// Held-key tracking for accelerated edits
+    key_repeat: KeyRepeat,
+
+    /// Count typed before the next edit or list key
+    count: CountPrefix,
+
+    /// When a count typed on a list panel started; without a `j`/`k` in
+    /// time it's a panel number after all
+    count_jump: Option<Instant>,
+
+    /// Keyboard macro registers (`Q` / `@`)
+    macros: Macros,
+
+    /// When `r` asked for a refresh that hasn't arrived yet
+    refresh_pending: Option<Instant>,
+
+    /// asusd release last checked for compatibility, so each refresh
+    /// doesn't repeat the warning
+    asusd_version: Option<AsusdVersion>,
+
+    /// When full blast started, the profile it pinned and the fan curve
+    /// to give that profile back
+    full_blast: Option<(Instant, PowerProfile, FanCurve)>,
+
+    /// CPU fan speed the curve asks for, with hysteresis applied
+    fan_target: Option<u8>,
+
+    /// Power limits seen under each profile, for the profile selector
+    profile_limits: ProfileLimits,
+    /// Fastest fan speed seen under each profile, for the profile selector
+    profile_fans: PerProfile<u32>,
+    /// Profile the fans are settling into, and since when
+    fan_profile: Option<(PowerProfile, Instant)>,
+    /// Firmware names of the profiles (`platform_profile` choices)
+    firmware_profiles: [Option<&'static str>; 3],
+
+    /// Process-triggered profile rules in effect
+    process_watch: ProcessWatch,
+
+    /// Lid, dock and power source state for the event rules
+    environment: Environment,
+
+    /// Event rule in effect and the profile and keyboard brightness to
+    /// restore once none match
+    event_rule: Option<usize>,
+    event_restore: Option<PowerProfile>,
+    event_keyboard_restore: Option<KbdBrightness>,
+
+    /// Profile whose `[lighting]` entry was last applied
+    lit_profile: Option<PowerProfile>,
+    /// Profile seen by the last `[automation.profiles]` check
+    acted_profile: Option<PowerProfile>,
+
+    /// Keyboard color following the CPU temperature, the color to put back
+    /// when turned off, and the last color written and when
+    pub reactive_lighting: bool,
+    reactive_restore: Option<Rgb>,
+    reactive_written: Option<(Instant, Rgb)>,
+
+    /// Quiet hours window in effect, the profile from before it started
+    /// and the window profile's curve from before it was capped; no curve
+    /// while the fans aren't capped
+    quiet_hours: Option<usize>,
+    quiet_restore: Option<(PowerProfile, Option<(PowerProfile, FanCurve)>)>,
+
+    /// When logind reported the user went idle, `None` while active
+    idle_since: Option<Instant>,
+
+    /// Profile and keyboard brightness to restore when the user returns
+    idle_restore: Option<(PowerProfile, Option<KbdBrightness>)>,
+
+    /// Whether the last telemetry sample showed the battery discharging
+    on_battery: Option<bool>,
+    /// When the power source last changed, unknown until hachi sees it change
+    power_since: Option<Instant>,
+
+    /// Display brightness to restore on AC after dimming on battery
+    undim_brightness: Option<u32>,
 
     /// Effect manager for TachyonFX
     pub effects: EffectManager,
@@ -77,29 +484,155 @@ pub struct App {
     /// Whether sakura particles are visible
     pub sakura_enabled: bool,
 
+    /// Low-power rendering, while in Quiet or on battery: half the frame
+    /// rate, no particles and slower sensor polling
+    pub low_power: bool,
+
+    /// Whether the terminal window has focus (from focus change events)
+    terminal_focused: bool,
+
+    /// Something on screen changed since the last frame was drawn
+    redraw: bool,
+
     /// Whether app should quit
     pub should_quit: bool,
 
     /// Last frame time for delta calculations
     last_frame: Instant,
+
+    /// Time between the last two frames, used to advance effects
+    frame_delta: Duration,
+
+    /// Header area from the last frame, for the profile-change sweep
+    header_area: Rect,
+
+    /// Panel areas from the last frame, for mouse hit-testing
+    panel_areas: Vec<(PanelId, Rect)>,
+
+    /// Battery animation on the katana blade, if any
+    pub blade_effect: Option<BladeEffect>,
+
+    /// Where the blade animation runs
+    blade_area: Rect,
+
+    /// Mouse cursor position, while it's over the terminal
+    hover: Option<(u16, u16)>,
+
+    /// Laptop model name for the compact header
+    model: Option<String>,
+
+    /// Active top-level view
+    pub tab: Tab,
+
+    /// Terminal height from the last frame, to toggle the compact header
+    /// from whichever mode is currently shown
+    last_height: u16,
 }
 
 impl App {
-    pub fn new(daemon: DaemonHandle) -> Self {
-        Self {
+    pub fn new(daemon: DaemonHandle, config: Config) -> Self {
+        let handheld = config.ui.handheld.unwrap_or_else(sysfs::is_rog_ally);
+        let session = UiSession::load();
+        let read_only = daemon.is_read_only();
+        let reactive_lighting = config.lighting.reactive.enabled;
+        let mut app = Self {
             daemon,
+            config,
             state: HardwareState::default(),
-            focused: FocusedPanel::PowerProfile,
+            telemetry: Telemetry::new(),
+            stats: SessionStats::new(),
+            recorder: None,
+            focused: session.focused,
+            handheld,
+            selected_tdp: 1,
             selected_profile: 1, // Balanced by default
+            selected_peripheral: 0,
+            selected_lid_row: 0,
+            color_picker: PickerState::new(Rgb::new(255, 255, 255)),
+            presets: Preset::load_all(),
+            selected_preset: 0,
+            preset_input: String::new(),
             edit_mode: EditMode::None,
             show_help: false,
+            help_panel: None,
+            history: History::default(),
+            messages: None,
+            tuning: Tuning::default(),
+            show_tune: false,
+            health: Some(HealthView::running()),
+            health_checks: Vec::new(),
+            show_perf: false,
+            frame_times: VecDeque::with_capacity(FRAME_SAMPLES),
             status_message: None,
+            toast: None,
+            fan_stall: false,
+            retrying: 0,
+            read_only,
+            safe_render: false,
+            debug_console: false,
+            console: None,
+            plain: false,
+            key_repeat: KeyRepeat::new(),
+            count: CountPrefix::default(),
+            count_jump: None,
+            macros: Macros::default(),
+            process_watch: ProcessWatch::default(),
+            environment: Environment::default(),
+            event_rule: None,
+            event_restore: None,
+            event_keyboard_restore: None,
+            lit_profile: None,
+            acted_profile: None,
+            reactive_lighting,
+            reactive_restore: None,
+            reactive_written: None,
+            quiet_hours: None,
+            quiet_restore: None,
+            idle_since: None,
+            idle_restore: None,
+            on_battery: None,
+            power_since: None,
+            undim_brightness: None,
             effects: EffectManager::new(),
             sakura: None,
-            sakura_enabled: true,
+            sakura_enabled: session.sakura_enabled,
+            low_power: false,
+            profile_limits: session.profile_limits,
+            profile_fans: session.profile_fans,
+            fan_profile: None,
+            firmware_profiles: {
+                let choices = sysfs::platform_profile_choices();
+                PowerProfile::ALL.map(|profile| profile.firmware_name(&choices))
+            },
+            fan_target: None,
+            refresh_pending: None,
+            asusd_version: None,
+            full_blast: None,
+            terminal_focused: true,
+            redraw: true,
             should_quit: false,
             last_frame: Instant::now(),
+            frame_delta: ANIMATED_FRAME,
+            header_area: Rect::default(),
+            panel_areas: Vec::new(),
+            blade_effect: None,
+            blade_area: Rect::default(),
+            hover: None,
+            model: sysfs::product_name(),
+            tab: session.tab,
+            last_height: 0,
+        };
+
+        app.daemon.check_health();
+
+        // The remembered panel may not be part of the current layout
+        app.keep_focus_visible();
+
+        if app.config.recording.enabled {
+            app.toggle_recording();
         }
+
+        app
     }
 
     /// Initialize sakura shader with terminal dimensions
@@ -111,237 +644,2413 @@ impl App {
     /// Process any pending hardware updates
     pub fn process_updates(&mut self) {
         while let Some(update) = self.daemon.try_recv() {
+            self.redraw = true;
+            self.state.apply(&update);
             match update {
-                HardwareUpdate::StateRefresh(new_state) => {
-                    self.state = new_state;
-                    // Map PowerProfile to UI index: Quiet=0, Balanced=1, Performance=2
-                    self.selected_profile = match self.state.power_profile {
-                        PowerProfile::Quiet => 0,
-                        PowerProfile::Balanced => 1,
-                        PowerProfile::Performance => 2,
-                    };
+                HardwareUpdate::StateRefresh(_) => {
+                    self.selected_profile = self.state.power_profile.ui_index();
+                    if let Some(limits) = self.state.power_limits() {
+                        self.profile_limits.set(self.state.power_profile, limits);
+                    }
+                    if let Some(sent) = self.refresh_pending.take() {
+                        let millis = sent.elapsed().as_millis();
+                        self.notify(Severity::Debug, format!("State refreshed ({} ms)", millis));
+                    }
+                    self.check_asusd_version();
+                }
+                HardwareUpdate::PowerLimitsChanged(profile, limits) => {
+                    self.profile_limits.set(profile, limits);
                 }
                 HardwareUpdate::PowerProfileChanged(profile) => {
-                    self.state.power_profile = profile;
                     // Sync UI selection with new profile
-                    self.selected_profile = match profile {
-                        PowerProfile::Quiet => 0,
-                        PowerProfile::Balanced => 1,
-                        PowerProfile::Performance => 2,
-                    };
+                    self.selected_profile = profile.ui_index();
+                    self.effects.trigger_header_sweep(self.header_area, profile_color(&profile));
                     self.set_status(format!("Profile changed to {}", profile));
                 }
                 HardwareUpdate::ChargeLimitChanged(limit) => {
-                    self.state.charge_limit = limit;
                     self.set_status(format!("Charge limit set to {}%", limit));
                 }
-                HardwareUpdate::FanCurveChanged(curve) => {
-                    self.state.fan_curve = curve;
-                    self.set_status("Fan curve updated".to_string());
+                HardwareUpdate::ChargeBypassChanged(enabled) => {
+                    let status = if enabled {
+                        "Bypass charging on - running from AC, battery idle"
+                    } else {
+                        "Bypass charging off - battery charging normally"
+                    };
+                    self.set_status(status.to_string());
+                }
+                HardwareUpdate::KbdBrightnessChanged(brightness) => {
+                    self.set_status(format!("Keyboard brightness set to {}", brightness));
+                }
+                HardwareUpdate::KbdColorChanged(color) => {
+                    // Temperature colors change quietly
+                    if !self.reactive_written.is_some_and(|(_, written)| written == color) {
+                        self.set_status(format!("Keyboard color set to {}", color));
+                    }
+                }
+                HardwareUpdate::KbdIdleTimeoutChanged(seconds) => {
+                    self.set_status(format!("Keyboard idle timeout set to {}", format_timeout(seconds)));
+                }
+                HardwareUpdate::PeripheralChanged(peripheral, enabled) => {
+                    let state = if enabled { "enabled" } else { "disabled" };
+                    self.set_status(format!("{} {}", peripheral.as_str(), state));
+                }
+                HardwareUpdate::DisplayBrightnessChanged(_) => {
+                    if let Some(percent) = self.state.backlight.as_ref().map(Backlight::percent) {
+                        self.set_status(format!("Display brightness {}%", percent));
+                    }
+                }
+                HardwareUpdate::AnimeBindingChanged(binding, shown) => {
+                    let state = if shown { "shown" } else { "off" };
+                    self.set_status(format!("AniMe display {} on {}", state, binding.as_str().to_lowercase()));
+                }
+                HardwareUpdate::SlashChanged(change) => {
+                    let status = match change {
+                        SlashChange::Enabled(true) => "Slash lighting on".to_string(),
+                        SlashChange::Enabled(false) => "Slash lighting off".to_string(),
+                        SlashChange::Mode(mode) => format!("Slash pattern {}", mode),
+                        SlashChange::Brightness(brightness) => format!("Slash brightness {}", brightness),
+                        SlashChange::Interval(interval) => format!("Slash interval {}", interval),
+                    };
+                    self.set_status(status);
                 }
-                HardwareUpdate::ConnectionStatus(connected) => {
-                    self.state.connected = connected;
-                    if !connected {
-                        self.set_status("Disconnected from daemon".to_string());
+                HardwareUpdate::TdpChanged(watts) => {
+                    self.set_status(format!("TDP set to {}W", watts));
+                }
+                HardwareUpdate::GpuModeChanged(mode) => {
+                    self.set_status(format!("GPU MUX set to {}, reboot to apply", mode.as_str()));
+                }
+                HardwareUpdate::FanCurveChanged(profile, _) => {
+                    if profile == self.state.power_profile {
+                        self.set_status("Fan curve updated".to_string());
+                    } else {
+                        let status = format!("{} fan curve written, used once active", profile);
+                        self.set_status(status);
                     }
                 }
-                HardwareUpdate::Error(msg) => {
-                    self.set_status(format!("Error: {}", msg));
+                HardwareUpdate::FanCurveLoaded(profile, _) => {
+                    self.cap_quiet_fans();
+                    self.apply_process_preset(profile);
+                }
+                HardwareUpdate::FanCurveEnabledChanged(enabled) => {
+                    let status = if enabled { "Custom fan curve on" } else { "Firmware fan curve" };
+                    self.set_status(status.to_string());
+                }
+                HardwareUpdate::FanEnabledChanged(profile, fan, enabled) => {
+                    let curve = if enabled { "custom curve" } else { "firmware curve" };
+                    self.set_status(format!(
+                        "{} fan in {}: {}",
+                        fan.as_str(),
+                        profile.as_str(),
+                        curve
+                    ));
+                }
+                HardwareUpdate::Retrying(count) => self.retrying = count,
+                HardwareUpdate::Health(checks) => self.show_health(checks),
+                HardwareUpdate::Connected => {
+                    self.set_status("Connected".to_string());
+                }
+                HardwareUpdate::Disconnected(reason) => {
+                    self.refresh_pending = None;
+                    self.notify(Severity::Warning, format!("Disconnected: {}", reason));
+                }
+                HardwareUpdate::External(signal) => self.apply_external(signal),
+                HardwareUpdate::Error(error) => match error.auth_denial() {
+                    Some(denial) => self.notify(Severity::Warning, denial.hint().to_string()),
+                    None => self.notify(Severity::Error, format!("Error: {}", error)),
+                },
+                HardwareUpdate::RolledBack(rollback) => self.flash_rollback(&rollback),
+                HardwareUpdate::RawReply(reply) => {
+                    if let Some(console) = &mut self.console {
+                        match reply {
+                            Ok(text) => console.print(&text),
+                            Err(e) => console.print(&format!("error: {}", e)),
+                        }
+                    }
                 }
             }
         }
 
-        // Clear old status messages (after 5 seconds)
-        if let Some((_, time)) = &self.status_message {
-            if time.elapsed() > Duration::from_secs(5) {
-                self.status_message = None;
-            }
+        let dropped = self.daemon.take_dropped();
+        if dropped > 0 {
+            let msg = match dropped {
+                1 => "Command dropped, daemon busy".to_string(),
+                n => format!("{} commands dropped, daemon busy", n),
+            };
+            self.toast = Some((msg.clone(), Severity::Warning, Instant::now()));
+            self.notify(Severity::Warning, msg);
         }
-    }
 
-    /// Set a status message
-    fn set_status(&mut self, msg: String) {
-        self.status_message = Some((msg, Instant::now()));
+        // Clear old status messages and toasts (critical ones linger longer)
+        for slot in [&mut self.status_message, &mut self.toast] {
+            if let Some((_, severity, time)) = slot {
+                let ttl = if *severity == Severity::Critical { 10 } else { 5 };
+                if time.elapsed() > Duration::from_secs(ttl) {
+                    *slot = None;
+                    self.redraw = true;
+                }
+            }
+        }
     }
 
-    /// Handle keyboard input
-    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
-        use crossterm::event::KeyCode;
-
-        // Global keys
-        match key.code {
-            KeyCode::Char('q') if self.edit_mode == EditMode::None => {
-                self.should_quit = true;
-                return;
+    /// Explain a change made outside hachi with a toast
+    fn apply_external(&mut self, signal: DaemonSignal) {
+        let msg = match signal {
+            DaemonSignal::PowerProfile(profile) => {
+                self.selected_profile = profile.ui_index();
+                self.effects.trigger_header_sweep(self.header_area, profile_color(&profile));
+                format!("Power profile changed to {} outside hachi", profile)
             }
-            KeyCode::Char('?') if self.edit_mode == EditMode::None => {
-                self.show_help = !self.show_help;
+            DaemonSignal::ChargeLimit(limit) => {
+                format!("Charge limit changed to {}% outside hachi", limit)
+            }
+            DaemonSignal::KbdBrightness(brightness) => {
+                format!("Keyboard brightness changed to {}", brightness)
+            }
+            DaemonSignal::Idle(since) => {
+                self.set_idle(since);
                 return;
             }
-            KeyCode::Esc => {
-                if self.show_help {
-                    self.show_help = false;
-                } else if self.edit_mode != EditMode::None {
-                    self.edit_mode = EditMode::None;
-                }
+            DaemonSignal::LidClosed(closed) => {
+                self.environment.lid_closed = closed;
+                self.check_events();
                 return;
             }
-            KeyCode::Char('r') if self.edit_mode == EditMode::None => {
-                self.daemon.refresh();
-                self.set_status("Refreshing state...".to_string());
+            DaemonSignal::Docked(docked) => {
+                self.environment.docked = docked;
+                self.check_events();
                 return;
             }
-            KeyCode::Char('s') if self.edit_mode == EditMode::None => {
-                self.sakura_enabled = !self.sakura_enabled;
-                let status = if self.sakura_enabled { "Sakura enabled" } else { "Sakura disabled" };
-                self.set_status(status.to_string());
+            DaemonSignal::Resumed => {
+                self.set_status("Resumed from sleep: hardware state refreshed".to_string());
                 return;
             }
-            _ => {}
+        };
+        self.toast = Some((msg.clone(), Severity::Info, Instant::now()));
+        self.set_status(msg);
+    }
+
+    /// Set an informational status message
+    fn set_status(&mut self, msg: String) {
+        self.notify(Severity::Info, msg);
+    }
+
+    /// Set a status message with an explicit severity; critical ones also toast
+    fn notify(&mut self, severity: Severity, msg: String) {
+        self.redraw = true;
+        if severity == Severity::Critical {
+            self.toast = Some((msg.clone(), severity, Instant::now()));
         }
+        self.history.push(severity, &msg);
+        self.status_message = Some((msg, severity, Instant::now()));
+    }
 
-        // Don't process other keys if help is showing
-        if self.show_help {
+    /// Step the display brightness by a signed percentage
+    fn adjust_display_brightness(&mut self, delta: i16) {
+        let Some(backlight) = &mut self.state.backlight else {
+            self.set_status("No display backlight found".to_string());
             return;
-        }
+        };
+        let brightness = backlight.stepped(delta);
+        // Optimistic update for immediate feedback
+        backlight.brightness = brightness;
+        self.daemon.set_display_brightness(brightness);
+    }
 
-        // Handle edit mode input
-        match self.edit_mode {
-            EditMode::Battery => self.handle_battery_edit(key),
-            EditMode::FanCurve { point_index } => self.handle_fan_curve_edit(key, point_index),
-            EditMode::None => self.handle_navigation(key),
-        }
+    /// Panel columns for the current layout, left to right, leaving out
+    /// panels whose hardware is missing
+    fn layout_columns(&self) -> Vec<Vec<PanelId>> {
+        let layout = &self.config.layout;
+        let columns: Vec<&[PanelId]> = if let Some(preset) = layout.preset {
+            preset.columns(self.handheld)
+        } else if self.handheld {
+            vec![layout.handheld.as_deref().unwrap_or(&panels::DEFAULT_HANDHELD)]
+        } else {
+            vec![
+                layout.left.as_deref().unwrap_or(&panels::DEFAULT_LEFT),
+                layout.right.as_deref().unwrap_or(&panels::DEFAULT_RIGHT),
+            ]
+        };
+
+        columns
+            .into_iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .copied()
+                    .filter(|&id| panels::spec(id).requires.available(self))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|column| !column.is_empty())
+            .collect()
     }
 
-    /// Handle navigation when not in edit mode
-    fn handle_navigation(&mut self, key: crossterm::event::KeyEvent) {
+    /// Panels Tab moves through, in the configured order
+    fn focus_ring(&self) -> FocusRing {
+        let layout = &self.config.layout;
+        FocusRing::new(&self.layout_columns(), &layout.focus_order)
+            .wrap(self.config.ui.focus_wrap.unwrap_or(true))
+    }
+
+    /// Whether a panel is part of the current layout
+    fn panel_visible(&self, panel: FocusedPanel) -> bool {
+        self.focus_ring().contains(panel)
+    }
+
+    /// Whether a key on the focused panel applies a change (or opens an
+    /// editor for one) rather than just moving the selection
+    fn key_writes(&self, code: crossterm::event::KeyCode) -> bool {
         use crossterm::event::KeyCode;
 
-        match key.code {
-            KeyCode::Tab | KeyCode::Char('L') => {
-                self.focused = self.focused.next();
-            }
-            KeyCode::BackTab | KeyCode::Char('H') => {
-                self.focused = self.focused.prev();
-            }
-            KeyCode::Char('1') => {
-                self.focused = FocusedPanel::PowerProfile;
+        match code {
+            KeyCode::Enter | KeyCode::Char(' ') => true,
+            KeyCode::Char('+' | '=' | '-') => self.focused == FocusedPanel::Lighting,
+            KeyCode::Left | KeyCode::Right | KeyCode::Char('h' | 'l') => {
+                self.focused == FocusedPanel::Lid
             }
-            KeyCode::Char('2') => {
-                self.focused = FocusedPanel::Battery;
-            }
-            KeyCode::Char('3') => {
-                self.focused = FocusedPanel::FanCurve;
-            }
-            KeyCode::Up | KeyCode::Char('k') => match self.focused {
-                FocusedPanel::PowerProfile => {
-                    self.selected_profile = self.selected_profile.saturating_sub(1);
-                }
-                _ => {}
-            },
-            KeyCode::Down | KeyCode::Char('j') => match self.focused {
-                FocusedPanel::PowerProfile => {
-                    self.selected_profile = (self.selected_profile + 1).min(2);
-                }
-                _ => {}
-            },
-            KeyCode::Enter | KeyCode::Char(' ') => match self.focused {
-                FocusedPanel::PowerProfile => {
-                    // UI index: 0=Quiet, 1=Balanced, 2=Performance
-                    let new_profile = match self.selected_profile {
-                        0 => PowerProfile::Quiet,
-                        1 => PowerProfile::Balanced,
-                        2 => PowerProfile::Performance,
-                        _ => PowerProfile::Balanced,
-                    };
-                    if new_profile != self.state.power_profile {
-                        self.daemon.set_power_profile(new_profile);
-                        // Optimistic update for immediate feedback
-                        self.state.power_profile = new_profile;
-                    }
-                }
-                FocusedPanel::Battery => {
-                    self.edit_mode = EditMode::Battery;
-                }
-                FocusedPanel::FanCurve => {
-                    self.edit_mode = EditMode::FanCurve { point_index: 0 };
-                }
-            },
-            _ => {}
+            KeyCode::Char('b') => self.focused == FocusedPanel::Battery,
+            KeyCode::Char('p' | 't') => self.focused == FocusedPanel::Lighting,
+            KeyCode::Char('m' | 'C') => self.focused == FocusedPanel::FanCurve,
+            _ => false,
         }
     }
 
-    /// Handle battery edit mode input
-    fn handle_battery_edit(&mut self, key: crossterm::event::KeyEvent) {
-        use crossterm::event::KeyCode;
+    /// Move focus off a panel the layout no longer shows
+    fn keep_focus_visible(&mut self) {
+        if !self.panel_visible(self.focused) {
+            self.cycle_focus(true);
+        }
+    }
 
-        match key.code {
-            KeyCode::Left | KeyCode::Char('h') => {
-                self.state.charge_limit = self.state.charge_limit.saturating_sub(5).max(20);
-            }
-            KeyCode::Right | KeyCode::Char('l') => {
-                self.state.charge_limit = (self.state.charge_limit + 5).min(100);
-            }
-            KeyCode::Enter => {
-                self.daemon.set_charge_limit(self.state.charge_limit);
-                self.edit_mode = EditMode::None;
+    /// Switch to a layout preset, or back to the configured columns when
+    /// it's already active
+    fn toggle_layout_preset(&mut self, preset: LayoutPreset) {
+        let preset = (self.config.layout.preset != Some(preset)).then_some(preset);
+        self.config.layout.preset = preset;
+        self.tab = Tab::Panels;
+        self.edit_mode = EditMode::None;
+        self.keep_focus_visible();
+
+        match self.config.save() {
+            Ok(()) => {
+                let name = preset.map_or("custom", LayoutPreset::as_str);
+                self.set_status(format!("Layout: {}", name));
             }
-            _ => {}
+            Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
         }
     }
 
-    /// Handle fan curve edit mode input
-    fn handle_fan_curve_edit(&mut self, key: crossterm::event::KeyEvent, point_index: usize) {
+    /// Move focus to the next (or previous) visible panel
+    fn cycle_focus(&mut self, forward: bool) {
+        if let Some(panel) = self.focus_ring().step(self.focused, forward) {
+            self.focused = panel;
+        }
+    }
+
+    /// Focus the first or last visible panel
+    fn focus_edge(&mut self, last: bool) {
+        let ring = self.focus_ring();
+        if let Some(panel) = if last { ring.last() } else { ring.first() } {
+            self.focused = panel;
+        }
+    }
+
+    /// Focus a panel if it's visible in the current layout
+    fn focus(&mut self, panel: FocusedPanel) {
+        if self.panel_visible(panel) {
+            self.focused = panel;
+        }
+    }
+
+    /// Write the active profile's fan curve snapped to the EC's steps, so
+    /// the points shown once it's confirmed are the ones the firmware stores
+    fn send_fan_curve(&self, curve: FanCurve) {
+        self.send_fan_curve_to(self.state.power_profile, curve);
+    }
+
+    fn send_fan_curve_to(&self, profile: PowerProfile, curve: FanCurve) {
+        self.daemon.set_fan_curve(profile, curve.snapped(self.config.fan));
+    }
+
+    /// Pin the fans at 100% for a cooling burst, or end one early
+    fn toggle_full_blast(&mut self) {
+        if let Some((_, profile, curve)) = self.full_blast.take() {
+            self.send_fan_curve_to(profile, curve);
+            self.set_status("Full blast off: fan curve restored".to_string());
+            return;
+        }
+
+        if !self.state.fan_curve_control {
+            let status = "Full blast unsupported: asusd has no fan curve control here";
+            self.set_status(status.to_string());
+            return;
+        }
+        let profile = self.state.power_profile;
+        let curve = self.state.fan_curve.clone();
+        self.send_fan_curve_to(profile, curve.full_blast(profile));
+        self.full_blast = Some((Instant::now(), profile, curve));
+        let minutes = FULL_BLAST_TIME.as_secs() / 60;
+        let status = format!("Full blast: fans at 100% for {} min (F to stop)", minutes);
+        self.notify(Severity::Warning, status);
+    }
+
+    /// Revert full blast once its timer runs out, keeping the countdown live
+    fn check_full_blast(&mut self) {
+        let Some((started, ..)) = &self.full_blast else {
+            return;
+        };
+        self.redraw = true;
+        if started.elapsed() < FULL_BLAST_TIME {
+            return;
+        }
+        if let Some((_, profile, curve)) = self.full_blast.take() {
+            self.send_fan_curve_to(profile, curve);
+            self.set_status("Full blast ended: fan curve restored".to_string());
+        }
+    }
+
+    /// Switch the sensor the fan graph follows, saving the choice
+    fn cycle_fan_temp_source(&mut self) {
+        let source = self.config.ui.fan_temp_source.cycle_next();
+        self.config.ui.fan_temp_source = source;
+        // The hysteresis state belongs to the old sensor's readings
+        self.fan_target = None;
+        match self.config.save() {
+            Ok(()) => self.set_status(format!("Fan graph follows {} temperature", source.as_str())),
+            Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+        }
+    }
+
+    /// Step the fan hysteresis by whole degrees within the accepted range,
+    /// saving it
+    fn adjust_fan_hysteresis(&mut self, step: i8) {
+        let range = FAN_HYSTERESIS_RANGE;
+        let current = self.config.ui.fan_hysteresis;
+        let hysteresis = (current as i8 + step).clamp(range.min as i8, range.max as i8) as u8;
+        if hysteresis == current {
+            return;
+        }
+
+        self.config.ui.fan_hysteresis = hysteresis;
+        let delta = self.config.ui.temperature_unit.format_delta(hysteresis as f32);
+        match self.config.save() {
+            Ok(()) => self.set_status(format!("Fan hysteresis {}", delta)),
+            Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+        }
+    }
+
+    /// Step the selected Slash pattern, brightness or interval row
+    fn adjust_slash(&mut self, step: i8) {
+        let Some(slash) = &self.state.slash else {
+            return;
+        };
+
+        let change = match self.selected_lid_row {
+            1 => slash.mode.as_deref().map(|mode| {
+                let index = SLASH_MODES.iter().position(|m| *m == mode).unwrap_or(0) as i32;
+                let next = (index + step as i32).rem_euclid(SLASH_MODES.len() as i32);
+                SlashChange::Mode(SLASH_MODES[next as usize])
+            }),
+            2 => slash.brightness.map(|brightness| {
+                SlashChange::Brightness(if step > 0 {
+                    brightness.saturating_add(SLASH_BRIGHTNESS_STEP)
+                } else {
+                    brightness.saturating_sub(SLASH_BRIGHTNESS_STEP)
+                })
+            }),
+            3 => slash.interval.map(|interval| {
+                SlashChange::Interval((interval as i8 + step).clamp(0, SLASH_INTERVAL_MAX as i8) as u8)
+            }),
+            _ => None,
+        };
+        if let Some(change) = change {
+            self.daemon.set_slash(change);
+        }
+    }
+
+    /// Start or stop appending telemetry samples to the recording file
+    pub fn toggle_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            self.set_status(format!("Recording saved to {}", recorder.path.display()));
+            return;
+        }
+
+        match Recorder::start(&self.config.recording) {
+            Ok(recorder) => {
+                self.set_status(format!("Recording to {}", recorder.path.display()));
+                self.recorder = Some(recorder);
+            }
+            Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+        }
+    }
+
+    /// `?` on a focused panel lists its keys first, a second `?` every key,
+    /// and a third closes the popup
+    fn toggle_help(&mut self) {
+        if !self.show_help {
+            self.show_help = true;
+            self.help_panel = REGISTRY
+                .iter()
+                .find(|spec| self.tab == Tab::Panels && spec.focus == Some(self.focused))
+                .filter(|spec| !spec.help.is_empty())
+                .map(|spec| spec.id);
+        } else if self.help_panel.take().is_none() {
+            self.show_help = false;
+        }
+    }
+
+    /// Flash the panel showing a setting that went back to its confirmed
+    /// value after a failed write
+    fn flash_rollback(&mut self, rollback: &Rollback) {
+        let panel = match rollback {
+            Rollback::PowerProfile(profile) => {
+                self.selected_profile = profile.ui_index();
+                PanelId::Power
+            }
+            Rollback::ChargeLimit(_) => PanelId::Battery,
+            Rollback::FanCurve(_) => {
+                // The burst never reached the fans, so there's nothing to count down
+                if self.full_blast.take().is_some() {
+                    let status = "Full blast failed: fan curve unchanged";
+                    self.notify(Severity::Warning, status.to_string());
+                }
+                // ...and a quiet hours cap that didn't apply isn't shown
+                if let Some((_, curve)) = &mut self.quiet_restore {
+                    *curve = None;
+                }
+                PanelId::FanCurve
+            }
+            Rollback::DisplayBrightness(_) => PanelId::Lighting,
+        };
+        let area = self.panel_areas.iter().find(|(id, _)| *id == panel).map(|(_, area)| *area);
+        if let (Tab::Panels, Some(area)) = (self.tab, area) {
+            self.effects.trigger_border_glow("rollback", area, colors::RONIN_RED);
+        }
+    }
+
+    /// Warn once per asusd release outside the range hachi was written
+    /// against, naming the panels that may misbehave
+    fn check_asusd_version(&mut self) {
+        let version = self.state.asusd_version;
+        if version.is_none() || version == self.asusd_version {
+            return;
+        }
+        self.asusd_version = version;
+        if let Some(warning) = version.and_then(AsusdVersion::compatibility_warning) {
+            self.notify(Severity::Warning, warning);
+        }
+    }
+
+    /// Add the local checks to the actor's and show the results. If the
+    /// checklist was already dismissed, failures still get a warning.
+    fn show_health(&mut self, mut checks: Vec<health::Check>) {
+        let sensors = telemetry::read_hwmon().0.len();
+        let colorterm = std::env::var("COLORTERM").ok();
+        checks.extend(health::local_checks(sensors, colorterm.as_deref(), self.safe_render));
+        self.health_checks = checks.clone();
+
+        let Some(view) = &mut self.health else {
+            let failed = checks.iter().filter(|c| c.status == CheckStatus::Fail).count();
+            if failed > 0 {
+                let warning = format!("Health check: {} failed, D for details", failed);
+                self.notify(Severity::Warning, warning);
+            }
+            return;
+        };
+        view.checks = checks;
+        view.finished = Some(Instant::now());
+        self.redraw = true;
+    }
+
+    /// Run the health check again and open the checklist
+    fn rerun_health(&mut self) {
+        self.show_help = false;
+        self.health = Some(HealthView::running());
+        self.daemon.check_health();
+    }
+
+    /// Keep the checklist spinner turning, closing it shortly after
+    /// everything passed
+    fn check_health(&mut self) {
+        let Some(view) = &self.health else {
+            return;
+        };
+        match view.finished {
+            None => self.redraw = true,
+            Some(at) if view.all_pass() && at.elapsed() >= HEALTH_DISMISS => {
+                self.health = None;
+                self.redraw = true;
+            }
+            Some(_) => {}
+        }
+    }
+
+    /// Keys while the tune popup is open: Enter starts a run, ends the
+    /// load early or stops the cooldown
+    fn handle_tune_key(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('T' | 'q') => self.show_tune = false,
+            KeyCode::Enter => match &mut self.tuning.run {
+                Some(run) if run.loading() => {
+                    run.end_load();
+                    self.set_status("Load stopped, recording the cooldown".to_string());
+                }
+                Some(_) => self.finish_tune_run(),
+                None => self.start_tune_run(),
+            },
+            _ => {}
+        }
+    }
+
+    fn start_tune_run(&mut self) {
+        match TuneRun::start(&self.config.tune.command) {
+            Ok(run) => {
+                self.tuning.run = Some(run);
+                // Suspending would cut the trace short
+                self.daemon.inhibit_sleep(Some("Tune run in progress"));
+                self.set_status(format!("Tune run started: {}", self.config.tune.command));
+            }
+            Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+        }
+    }
+
+    /// Record a sample for the run in progress, finishing it once the
+    /// cooldown is over
+    fn check_tune_run(&mut self) {
+        let Some(run) = &mut self.tuning.run else {
+            return;
+        };
+        if let Some(celsius) = self.telemetry.fan_temp(self.config.ui.fan_temp_source) {
+            let rpm = self.telemetry.fans.iter().map(|fan| fan.rpm).max();
+            run.record(celsius, rpm);
+        }
+        if run.poll(Duration::from_secs(self.config.tune.cooldown_secs)) {
+            self.finish_tune_run();
+        }
+    }
+
+    fn finish_tune_run(&mut self) {
+        let unit = self.config.ui.temperature_unit;
+        let Some(trace) = self.tuning.finish() else {
+            return;
+        };
+        self.daemon.inhibit_sleep(None);
+        let peak = trace.peak().map_or_else(|| "-".to_string(), |peak| unit.format(peak));
+        let status = match trace.recovery_secs() {
+            Some(secs) => format!("Tune run done: peak {}, cooled in {:.0}s", peak, secs),
+            None => format!("Tune run done: peak {}", peak),
+        };
+        self.set_status(status);
+    }
+
+    /// Keys while the messages popup is open: `/` searches, `d`/`i`/`w`/`e`
+    /// set the least severity shown, `y` copies the listed messages, `j`/`k`
+    /// scroll
+    fn handle_messages_key(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+
+        let Some(view) = &mut self.messages else {
+            return;
+        };
+        if view.searching {
+            match key.code {
+                KeyCode::Enter => view.searching = false,
+                KeyCode::Esc => {
+                    view.searching = false;
+                    view.query.clear();
+                }
+                KeyCode::Backspace => {
+                    view.query.pop();
+                }
+                KeyCode::Char(c) => view.query.push(c),
+                _ => return,
+            }
+            view.scroll = 0;
+            return;
+        }
+
+        let last = view.found(&self.history).len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char(':' | 'q') => self.messages = None,
+            KeyCode::Char('/') => view.searching = true,
+            KeyCode::Char(c @ ('d' | 'i' | 'w' | 'e')) => {
+                view.least = match c {
+                    'd' => Severity::Debug,
+                    'i' => Severity::Info,
+                    'w' => Severity::Warning,
+                    _ => Severity::Error,
+                };
+                view.scroll = 0;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.scroll = (view.scroll + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::Char('g') | KeyCode::Home => view.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => view.scroll = last,
+            KeyCode::Char('y') => {
+                // Oldest first, like a log
+                let found = view.found(&self.history);
+                let lines: Vec<String> = found.iter().rev().map(|message| message.line()).collect();
+                match clipboard::copy(&lines.join("\n")) {
+                    Ok(()) => {
+                        self.set_status(format!("Copied {} messages to clipboard", lines.len()))
+                    }
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Keys while the D-Bus console is open: typing, Enter runs the line,
+    /// Up/Down recall earlier commands
+    fn handle_console_key(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+
+        let Some(console) = &mut self.console else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.console = None,
+            KeyCode::Enter => {
+                let Some(line) = console.submit() else {
+                    return;
+                };
+                match RawCall::parse(&line) {
+                    Ok(call) if call.writes() && self.read_only => {
+                        console.print("error: read-only mode, only get and introspect run");
+                    }
+                    Ok(call) => self.daemon.raw_call(call),
+                    Err(e) => console.print(&format!("error: {}", e)),
+                }
+            }
+            KeyCode::Backspace => {
+                console.input.pop();
+            }
+            KeyCode::Up | KeyCode::Down => console.recall(key.code == KeyCode::Up),
+            KeyCode::Char(c) => console.input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Handle keyboard input
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
+        self.redraw = true;
+
+        // Typed names, searches and console commands are text, not macro keys
+        let typing = self.messages.is_some() || self.console.is_some();
+        if matches!(self.edit_mode, EditMode::PresetName { .. }) || typing {
+            self.dispatch_key(key);
+            return;
+        }
+
+        match self.macros.handle(key) {
+            MacroAction::Pass => self.dispatch_key(key),
+            MacroAction::Status(status) => self.set_status(status),
+            MacroAction::Replay(register, keys) => {
+                for key in keys {
+                    // Replayed keys join a macro being recorded, like typed ones
+                    self.macros.record(key);
+                    self.dispatch_key(key);
+                    if self.should_quit {
+                        return;
+                    }
+                }
+                self.set_status(format!("Replayed macro @{}", register));
+            }
+        }
+    }
+
+    fn dispatch_key(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if self.messages.is_some() {
+            self.handle_messages_key(key);
+            return;
+        }
+        if self.console.is_some() {
+            self.handle_console_key(key);
+            return;
+        }
+        if self.show_tune {
+            self.handle_tune_key(key);
+            return;
+        }
+        if self.health.is_some() {
+            // D runs the checks again, anything else continues to the dashboard
+            match key.code {
+                KeyCode::Char('D') => self.rerun_health(),
+                _ => self.health = None,
+            }
+            return;
+        }
+
+        // Global keys
+        match key.code {
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.toggle_recording();
+                return;
+            }
+            KeyCode::Char('q') if self.edit_mode == EditMode::None => {
+                self.should_quit = true;
+                return;
+            }
+            KeyCode::Char('?') if self.edit_mode == EditMode::None => {
+                self.toggle_help();
+                return;
+            }
+            KeyCode::Char(':') if self.edit_mode == EditMode::None => {
+                self.show_help = false;
+                self.messages = Some(MessagesView::default());
+                return;
+            }
+            KeyCode::Char('T') if self.edit_mode == EditMode::None => {
+                self.show_help = false;
+                self.show_tune = true;
+                return;
+            }
+            KeyCode::Char('`') if self.debug_console && self.edit_mode == EditMode::None => {
+                self.show_help = false;
+                self.console = Some(Console::new());
+                return;
+            }
+            KeyCode::Char('D') if self.edit_mode == EditMode::None => {
+                self.rerun_health();
+                return;
+            }
+            KeyCode::Char('B') if self.edit_mode == EditMode::None => {
+                let log = self.history.messages().map(LogEntry::from).collect();
+                match Bundle::new(&self.state, &self.health_checks, log).save() {
+                    Ok(path) => self.set_status(format!("Bug report saved to {}", path.display())),
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                self.take_count();
+                if self.show_help {
+                    self.show_help = false;
+                } else if self.edit_mode != EditMode::None {
+                    self.edit_mode = EditMode::None;
+                }
+                return;
+            }
+            KeyCode::Char('r') if self.edit_mode == EditMode::None => {
+                self.daemon.refresh();
+                self.refresh_pending.get_or_insert_with(Instant::now);
+                self.notify(Severity::Debug, "Refreshing state...".to_string());
+                return;
+            }
+            KeyCode::Char('u') if self.edit_mode == EditMode::None => {
+                let unit = self.config.ui.temperature_unit.toggle();
+                self.config.ui.temperature_unit = unit;
+                match self.config.save() {
+                    Ok(()) => self.set_status(format!("Temperatures in {}", unit.symbol())),
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+                return;
+            }
+            KeyCode::Char('a') if self.edit_mode == EditMode::None => {
+                let art = self.config.ui.header_art.cycle_next();
+                self.config.ui.header_art = art;
+                match self.config.save() {
+                    Ok(()) => self.set_status(format!("Header art: {}", art.as_str())),
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+                return;
+            }
+            KeyCode::Char('c') if self.edit_mode == EditMode::None => {
+                let compact = self.config.ui.compact_header.unwrap_or(self.last_height < COMPACT_HEADER_BELOW);
+                self.config.ui.compact_header = Some(!compact);
+                match self.config.save() {
+                    Ok(()) => {
+                        let status = if compact { "Full header" } else { "Compact header" };
+                        self.set_status(status.to_string());
+                    }
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+                return;
+            }
+            KeyCode::F(n @ 1..=3) => {
+                self.toggle_layout_preset(LayoutPreset::ALL[n as usize - 1]);
+                return;
+            }
+            KeyCode::F(n @ 5..=9) if self.edit_mode == EditMode::None => {
+                self.apply_scenario(n as usize - 5);
+                return;
+            }
+            KeyCode::F(10) => {
+                self.show_perf = !self.show_perf;
+                return;
+            }
+            KeyCode::Char('y') if self.edit_mode == EditMode::None => {
+                let summary = clipboard::summary(
+                    self.model.as_deref(),
+                    &self.state,
+                    &self.telemetry,
+                    self.config.ui.temperature_unit,
+                );
+                match clipboard::copy(&summary) {
+                    Ok(()) => self.set_status("Copied state summary to clipboard".to_string()),
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+                return;
+            }
+            KeyCode::Char('e') if self.edit_mode == EditMode::None => {
+                let exported = export::export_fan_curve(
+                    &self.state.fan_curve,
+                    self.state.power_profile,
+                    self.config.ui.temperature_unit,
+                );
+                match exported {
+                    Ok(path) => self.set_status(format!("Fan curve saved to {}", path.display())),
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+                return;
+            }
+            KeyCode::Char('F') if self.edit_mode == EditMode::None => {
+                if self.read_only {
+                    self.set_status("Read-only mode: changes are disabled".to_string());
+                } else {
+                    self.toggle_full_blast();
+                }
+                return;
+            }
+            KeyCode::Char('s') if self.edit_mode == EditMode::None => {
+                self.sakura_enabled = !self.sakura_enabled;
+                let status = if self.sakura_enabled { "Sakura enabled" } else { "Sakura disabled" };
+                self.set_status(status.to_string());
+                return;
+            }
+            _ => {}
+        }
+
+        // Don't process other keys if help is showing
+        if self.show_help {
+            return;
+        }
+
+        // In value edits and lists digits build a count for the next key
+        // (`20l`, `3j`). On a list a count can't start with 0, the
+        // dashboard key.
+        let counts = match self.edit_mode {
+            EditMode::Battery | EditMode::FanCurve { .. } | EditMode::KbdIdleTimeout => true,
+            EditMode::KbdColor => self.color_picker.row != PickerRow::Hex,
+            EditMode::None => {
+                self.list_focused()
+                    && (self.count.0.is_some() || matches!(key.code, KeyCode::Char('1'..='9')))
+            }
+            _ => false,
+        };
+        if counts && self.count.push(key.code) {
+            if self.edit_mode == EditMode::None {
+                self.count_jump.get_or_insert_with(Instant::now);
+            }
+            self.notify(Severity::Debug, format!("Count: {}", self.count.0.unwrap_or(0)));
+            return;
+        }
+        let count = self.take_count();
+
+        // Handle edit mode input
+        match self.edit_mode {
+            EditMode::Battery => self.handle_battery_edit(key, count),
+            EditMode::FanCurve { point_index } => {
+                self.handle_fan_curve_edit(key, point_index, count)
+            }
+            EditMode::FanEnablement { profile, fan } => {
+                self.handle_fan_enablement_edit(key, profile, fan)
+            }
+            EditMode::CurveCopy { target, offset } => self.handle_curve_copy(key, target, offset),
+            EditMode::KbdIdleTimeout => self.handle_kbd_timeout_edit(key, count),
+            EditMode::KbdColor => self.handle_kbd_color_edit(key, count),
+            EditMode::GpuSwitch(mode) => self.handle_gpu_switch(key, mode),
+            EditMode::PresetName { rename } => self.handle_preset_name_edit(key, rename),
+            EditMode::PresetDelete => self.handle_preset_delete(key),
+            EditMode::None => self.handle_navigation(key, count),
+        }
+    }
+
+    fn take_count(&mut self) -> Option<u32> {
+        self.count_jump = None;
+        self.count.take()
+    }
+
+    /// Whether the focused panel is a list `j`/`k` moves through
+    fn list_focused(&self) -> bool {
+        self.tab == Tab::Panels
+            && matches!(
+                self.focused,
+                FocusedPanel::PowerProfile
+                    | FocusedPanel::Advanced
+                    | FocusedPanel::Lid
+                    | FocusedPanel::Presets
+            )
+    }
+
+    /// A count on a list panel that no `j`/`k` followed: a lone panel
+    /// number jumps to its panel, anything longer is dropped
+    fn jump_to_count(&mut self, count: u32) {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        if let Some(digit) = char::from_digit(count, 10).filter(|_| count <= 8) {
+            self.handle_navigation(KeyEvent::new(KeyCode::Char(digit), KeyModifiers::NONE), None);
+        }
+    }
+
+    /// Percent step for a value edit: the typed count if any, otherwise
+    /// accelerating while the key is held
+    fn edit_step(&mut self, code: crossterm::event::KeyCode, count: Option<u32>) -> u8 {
+        match count {
+            Some(count) => count.min(100) as u8,
+            None => accelerated_step(self.key_repeat.press(code)),
+        }
+    }
+
+    /// Handle navigation when not in edit mode; `count` rows for `j`/`k`
+    fn handle_navigation(&mut self, key: crossterm::event::KeyEvent, count: Option<u32>) {
+        use crossterm::event::KeyCode;
+
+        let moves = matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::Char('j' | 'k'));
+        if let Some(count) = count.filter(|_| !moves) {
+            self.jump_to_count(count);
+        }
+        let rows = count.unwrap_or(1) as usize;
+
+        if key.code == KeyCode::Char('0') {
+            self.tab = Tab::Dashboard;
+            return;
+        }
+
+        // The dashboard only leaves for the panels: number keys jump straight
+        // to the matching panel, Tab opens the focused one
+        if self.tab == Tab::Dashboard {
+            match key.code {
+                KeyCode::Char('1'..='8') => self.tab = Tab::Panels,
+                KeyCode::Tab | KeyCode::BackTab => {
+                    self.tab = Tab::Panels;
+                    return;
+                }
+                _ => return,
+            }
+        }
+
+        // Read-only: moving around works, anything that would write doesn't
+        if self.read_only && self.key_writes(key.code) {
+            self.set_status("Read-only mode: changes are disabled".to_string());
+            return;
+        }
+
+        match key.code {
+            KeyCode::Tab | KeyCode::Char('L') => self.cycle_focus(true),
+            KeyCode::BackTab | KeyCode::Char('H') => self.cycle_focus(false),
+            KeyCode::Char('1') => self.focus(FocusedPanel::PowerProfile),
+            KeyCode::Char('2') => self.focus(FocusedPanel::Battery),
+            KeyCode::Char('3') => self.focus(FocusedPanel::FanCurve),
+            KeyCode::Char('4') => self.focus(FocusedPanel::Lighting),
+            KeyCode::Char('5') => self.focus(FocusedPanel::Advanced),
+            KeyCode::Char('6') => self.focus(FocusedPanel::Lid),
+            KeyCode::Char('7') => self.focus(FocusedPanel::Tdp),
+            KeyCode::Char('8') => self.focus(FocusedPanel::Presets),
+            KeyCode::Char('g') | KeyCode::Home => self.focus_edge(false),
+            KeyCode::Char('G') | KeyCode::End => self.focus_edge(true),
+            KeyCode::Up | KeyCode::Char('k') => match self.focused {
+                FocusedPanel::PowerProfile => {
+                    self.selected_profile = self.selected_profile.saturating_sub(rows);
+                }
+                FocusedPanel::Advanced => {
+                    self.selected_peripheral = self.selected_peripheral.saturating_sub(rows);
+                }
+                FocusedPanel::Lid => {
+                    self.selected_lid_row = self.selected_lid_row.saturating_sub(rows);
+                }
+                FocusedPanel::Presets => {
+                    self.selected_preset = self.selected_preset.saturating_sub(rows);
+                }
+                _ => {}
+            },
+            KeyCode::Down | KeyCode::Char('j') => match self.focused {
+                FocusedPanel::PowerProfile => {
+                    self.selected_profile = (self.selected_profile + rows).min(2);
+                }
+                FocusedPanel::Advanced => {
+                    self.selected_peripheral =
+                        (self.selected_peripheral + rows).min(Peripheral::ALL.len() - 1);
+                }
+                FocusedPanel::Lid => {
+                    let lid_rows = if self.state.slash.is_some() {
+                        SLASH_ROWS
+                    } else {
+                        AnimeBinding::ALL.len()
+                    };
+                    self.selected_lid_row = (self.selected_lid_row + rows).min(lid_rows - 1);
+                }
+                FocusedPanel::Presets => {
+                    self.selected_preset =
+                        (self.selected_preset + rows).min(self.presets.len().saturating_sub(1));
+                }
+                _ => {}
+            },
+            KeyCode::Char('+') | KeyCode::Char('=') if self.focused == FocusedPanel::Lighting => {
+                self.adjust_display_brightness(DISPLAY_BRIGHTNESS_STEP as i16);
+            }
+            KeyCode::Char('-') if self.focused == FocusedPanel::Lighting => {
+                self.adjust_display_brightness(-(DISPLAY_BRIGHTNESS_STEP as i16));
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') if self.focused == FocusedPanel::FanCurve => {
+                self.adjust_fan_hysteresis(1);
+            }
+            KeyCode::Char('-') if self.focused == FocusedPanel::FanCurve => {
+                self.adjust_fan_hysteresis(-1);
+            }
+            KeyCode::Char('t') if self.focused == FocusedPanel::FanCurve => {
+                self.cycle_fan_temp_source();
+            }
+            KeyCode::Char('C') if self.focused == FocusedPanel::FanCurve => {
+                if !self.state.fan_curve_control {
+                    let status = "Curve copy unsupported: asusd has no fan curve control here";
+                    self.set_status(status.to_string());
+                    return;
+                }
+                self.edit_mode = EditMode::CurveCopy {
+                    target: CurveTarget::Gpu,
+                    offset: 0,
+                };
+            }
+            KeyCode::Char('m') if self.focused == FocusedPanel::FanCurve => {
+                self.edit_mode = EditMode::FanEnablement {
+                    profile: self.state.power_profile,
+                    fan: Fan::Cpu,
+                };
+            }
+            KeyCode::Char('p') if self.focused == FocusedPanel::Lighting => {
+                match &self.state.aura {
+                    Some(aura) => {
+                        let color = aura.color.unwrap_or(Rgb::new(255, 255, 255));
+                        self.color_picker = PickerState::new(color);
+                        self.edit_mode = EditMode::KbdColor;
+                    }
+                    None => self.set_status("No Aura keyboard to color".to_string()),
+                }
+            }
+            KeyCode::Char('t') if self.focused == FocusedPanel::Lighting => {
+                self.toggle_reactive_lighting();
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.focused == FocusedPanel::Tdp => {
+                self.selected_tdp = self.selected_tdp.saturating_sub(1);
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.focused == FocusedPanel::Tdp => {
+                self.selected_tdp = (self.selected_tdp + 1).min(TDP_PRESETS.len() - 1);
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.focused == FocusedPanel::Lid => {
+                self.adjust_slash(-1);
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.focused == FocusedPanel::Lid => {
+                self.adjust_slash(1);
+            }
+            KeyCode::Char('n') if self.focused == FocusedPanel::Presets => {
+                self.preset_input.clear();
+                self.edit_mode = EditMode::PresetName { rename: false };
+            }
+            KeyCode::Char('m') if self.focused == FocusedPanel::Presets => {
+                if let Some(preset) = self.presets.get(self.selected_preset) {
+                    self.preset_input = preset.name.clone();
+                    self.edit_mode = EditMode::PresetName { rename: true };
+                }
+            }
+            KeyCode::Char('d') if self.focused == FocusedPanel::Presets => {
+                if let Some(preset) = self.presets.get(self.selected_preset) {
+                    let name = &preset.name;
+                    let prompt = format!("Delete preset {}? Enter to confirm, Esc to cancel", name);
+                    self.set_status(prompt);
+                    self.edit_mode = EditMode::PresetDelete;
+                }
+            }
+            KeyCode::Char('b') if self.focused == FocusedPanel::Battery => {
+                // Wait for the actor's confirmation rather than updating optimistically
+                match self.state.charge_bypass {
+                    Some(enabled) => {
+                        self.daemon.set_charge_bypass(!enabled);
+                        self.set_status("Switching charge mode...".to_string());
+                    }
+                    None => self.set_status("Bypass charging not supported".to_string()),
+                }
+            }
+            KeyCode::Char(' ') if self.focused == FocusedPanel::FanCurve => {
+                // The ●/○ indicator waits for the actor's confirmation
+                self.daemon.set_fan_curve_enabled(!self.state.fan_curve.enabled);
+                self.set_status("Switching fan curve...".to_string());
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => match self.focused {
+                FocusedPanel::PowerProfile => self.apply_selected_profile(),
+                FocusedPanel::Battery => {
+                    self.edit_mode = EditMode::Battery;
+                }
+                FocusedPanel::FanCurve => {
+                    self.edit_mode = EditMode::FanCurve { point_index: 0 };
+                }
+                FocusedPanel::Lighting => {
+                    let supported = self
+                        .state
+                        .aura
+                        .as_ref()
+                        .is_some_and(|aura| aura.idle_timeout.is_some());
+                    if supported {
+                        self.edit_mode = EditMode::KbdIdleTimeout;
+                    } else {
+                        self.set_status("Keyboard idle timeout not supported".to_string());
+                    }
+                }
+                FocusedPanel::Advanced => {
+                    let peripheral = Peripheral::ALL[self.selected_peripheral];
+                    match self.state.peripheral(peripheral) {
+                        Some(enabled) => self.daemon.set_peripheral(peripheral, !enabled),
+                        None => self.set_status(format!("{} not supported", peripheral.as_str())),
+                    }
+                }
+                FocusedPanel::Presets => self.apply_selected_preset(),
+                FocusedPanel::Tdp => {
+                    let watts = TDP_PRESETS[self.selected_tdp];
+                    let watts = self.state.tdp_range.map_or(watts, |range| range.clamp(watts));
+                    self.daemon.set_tdp(watts);
+                }
+                FocusedPanel::Lid if self.state.slash.is_some() => {
+                    let enabled = self.state.slash.as_ref().and_then(|slash| slash.enabled);
+                    match (self.selected_lid_row, enabled) {
+                        (0, Some(enabled)) => self.daemon.set_slash(SlashChange::Enabled(!enabled)),
+                        (0, None) => self.set_status("Slash toggle not supported".to_string()),
+                        _ => self.set_status("Use ←/→ to adjust".to_string()),
+                    }
+                }
+                FocusedPanel::Lid => {
+                    let binding = AnimeBinding::ALL[self.selected_lid_row.min(AnimeBinding::ALL.len() - 1)];
+                    match self.state.anime.as_ref().and_then(|anime| anime.shown(binding)) {
+                        Some(shown) => self.daemon.set_anime_binding(binding, !shown),
+                        None => self.set_status("AniMe setting not supported".to_string()),
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn apply_selected_profile(&mut self) {
+        let new_profile = PowerProfile::ALL
+            .get(self.selected_profile)
+            .copied()
+            .unwrap_or_default();
+        if new_profile != self.state.power_profile {
+            self.daemon.set_power_profile(new_profile);
+            // Optimistic update for immediate feedback
+            self.state.power_profile = new_profile;
+        }
+    }
+
+    fn apply_selected_preset(&mut self) {
+        let Some(preset) = self.presets.get(self.selected_preset).cloned() else {
+            self.set_status("No presets yet: n saves the current settings".to_string());
+            return;
+        };
+
+        self.daemon.set_power_profile(preset.power_profile);
+        // Optimistic update for immediate feedback
+        self.state.power_profile = preset.power_profile;
+        self.selected_profile = preset.power_profile.ui_index();
+        self.daemon.set_charge_limit(preset.charge_limit);
+        self.send_fan_curve(preset.fan_curve);
+        let idle_timeout_supported =
+            self.state.aura.as_ref().is_some_and(|aura| aura.idle_timeout.is_some());
+        if let (Some(seconds), true) = (preset.kbd_idle_timeout, idle_timeout_supported) {
+            self.daemon.set_kbd_idle_timeout(seconds);
+        }
+        if let Some(aura) = &self.state.aura {
+            // The preset's lighting wins over the new profile's
+            self.lit_profile = Some(preset.power_profile);
+            if let Some(color) = preset.kbd_color.filter(|_| !self.reactive_lighting) {
+                if aura.color != Some(color) {
+                    self.daemon.set_kbd_color(color);
+                }
+            }
+            if let Some(level) = preset.kbd_brightness {
+                if aura.brightness != Some(level) {
+                    self.daemon.set_kbd_brightness(level);
+                }
+            }
+        }
+        self.set_status(format!("Applied preset {}", preset.name));
+    }
+
+    /// Apply the scenario on `F5` + `index`: profile, charge limit, keyboard
+    /// lighting, command and petals, whichever parts it sets
+    fn apply_scenario(&mut self, index: usize) {
+        let Some(scenario) = self.config.scenarios().into_iter().nth(index) else {
+            self.set_status(format!("No scenario on F{}", index + 5));
+            return;
+        };
+        if self.read_only {
+            self.set_status("Read-only mode: changes are disabled".to_string());
+            return;
+        }
+
+        let mut actions = Vec::new();
+        if let Some(profile) = scenario.profile {
+            if profile != self.state.power_profile {
+                self.daemon.set_power_profile(profile);
+                // Optimistic update for immediate feedback
+                self.state.power_profile = profile;
+                self.selected_profile = profile.ui_index();
+            }
+            actions.push(profile.to_string());
+        }
+        if let Some(limit) = scenario.charge_limit {
+            let limit = CHARGE_LIMIT_RANGE.clamp(limit);
+            self.daemon.set_charge_limit(limit);
+            actions.push(format!("charge limit {}%", limit));
+        }
+        if let (Some(lighting), Some(aura)) = (&scenario.lighting, &self.state.aura) {
+            // The scenario's lighting wins over the new profile's
+            self.lit_profile = Some(self.state.power_profile);
+            if let Some(color) = lighting.color.filter(|_| !self.reactive_lighting) {
+                if aura.color != Some(color) {
+                    self.daemon.set_kbd_color(color);
+                }
+                actions.push(color.to_string());
+            }
+            if let Some(level) = lighting.brightness {
+                if aura.brightness != Some(level) {
+                    self.daemon.set_kbd_brightness(level);
+                }
+                actions.push(format!("keyboard {}", level));
+            }
+        }
+        if let Some(particles) = scenario.particles {
+            self.sakura_enabled = particles;
+            actions.push(if particles { "sakura on" } else { "sakura off" }.to_string());
+        }
+        if let Some(command) = &scenario.command {
+            if let Err(e) = automation::run_command(command) {
+                self.notify(Severity::Error, format!("Error: {}", e));
+                return;
+            }
+            actions.push(format!("ran {}", command));
+        }
+        self.set_status(format!("{}: {}", scenario.name, actions.join(", ")));
+    }
+
+    /// Re-read the preset directory, selecting `name` if given
+    fn reload_presets(&mut self, name: Option<&str>) {
+        self.presets = Preset::load_all();
+        let index = name.and_then(|name| self.presets.iter().position(|p| p.name == name));
+        if let Some(index) = index {
+            self.selected_preset = index;
+        }
+        self.selected_preset = self.selected_preset.min(self.presets.len().saturating_sub(1));
+    }
+
+    /// Track the cursor for hover highlighting, and focus (or apply, for
+    /// power profiles) on left click
+    pub fn handle_mouse(&mut self, mouse: crossterm::event::MouseEvent) {
+        use crossterm::event::{MouseButton, MouseEventKind};
+
+        let position = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::Moved | MouseEventKind::Drag(_) if self.hover != Some(position) => {
+                self.hover = Some(position);
+                self.redraw = true;
+            }
+            MouseEventKind::Down(MouseButton::Left) if self.edit_mode == EditMode::None => {
+                self.hover = Some(position);
+                self.click(position);
+                // Focus and selection move without a status line to trigger a frame
+                self.redraw = true;
+            }
+            MouseEventKind::ScrollUp if self.edit_mode == EditMode::None => {
+                self.scroll(position, true);
+                self.redraw = true;
+            }
+            MouseEventKind::ScrollDown if self.edit_mode == EditMode::None => {
+                self.scroll(position, false);
+                self.redraw = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Quick tweaks without edit mode: the profile selection, the charge
+    /// limit, or the speed of the fan curve point nearest the cursor
+    fn scroll(&mut self, (column, row): (u16, u16), up: bool) {
+        let Some((id, area)) = self.panel_at(column, row) else {
+            return;
+        };
+
+        match id {
+            PanelId::Power => {
+                self.focused = FocusedPanel::PowerProfile;
+                self.selected_profile = if up {
+                    self.selected_profile.saturating_sub(1)
+                } else {
+                    (self.selected_profile + 1).min(PowerProfile::ALL.len() - 1)
+                };
+            }
+            PanelId::Battery | PanelId::FanCurve if self.read_only => {
+                self.set_status("Read-only mode: changes are disabled".to_string());
+            }
+            PanelId::Battery => {
+                let limit = self.state.charge_limit;
+                let limit = CHARGE_LIMIT_RANGE.clamp(if up {
+                    limit.saturating_add(SCROLL_STEP)
+                } else {
+                    limit.saturating_sub(SCROLL_STEP)
+                });
+                if limit != self.state.charge_limit {
+                    self.state.charge_limit = limit;
+                    self.daemon.set_charge_limit(limit);
+                }
+                self.set_status(format!("Charge limit: {}%", limit));
+            }
+            PanelId::FanCurve => {
+                let steps = self.config.fan;
+                let step = SCROLL_STEP.max(steps.speed_step);
+                let curve = &mut self.state.fan_curve;
+                let Some(index) = FanCurveGraph::point_at(curve, area, column) else {
+                    return;
+                };
+                let point = &mut curve.cpu_curve[index];
+                let speed = steps.snap_speed(if up {
+                    point.speed.saturating_add(step).min(100)
+                } else {
+                    point.speed.saturating_sub(step)
+                });
+                let temp = point.temp;
+                if speed != point.speed {
+                    point.speed = speed;
+                    self.send_fan_curve(self.state.fan_curve.clone());
+                }
+                let unit = self.config.ui.temperature_unit;
+                self.set_status(format!("Fan speed at {}: {}%", unit.format(temp as f32), speed));
+            }
+            _ => {}
+        }
+    }
+
+    fn click(&mut self, (column, row): (u16, u16)) {
+        let Some((id, area)) = self.panel_at(column, row) else {
+            return;
+        };
+        let Some(panel) = panels::spec(id).focus else {
+            return;
+        };
+        self.focused = panel;
+
+        if let Some(index) = profile_row(id, area, row) {
+            if self.read_only {
+                self.set_status("Read-only mode: changes are disabled".to_string());
+                return;
+            }
+            self.selected_profile = index;
+            self.apply_selected_profile();
+        }
+    }
+
+    /// Panel under a screen position in the last frame
+    fn panel_at(&self, column: u16, row: u16) -> Option<(PanelId, Rect)> {
+        if self.tab != Tab::Panels || self.show_help {
+            return None;
+        }
+        self.panel_areas
+            .iter()
+            .copied()
+            .find(|(_, area)| area.contains(Position::new(column, row)))
+    }
+
+    /// One-line description of what clicking under the cursor would do
+    fn tooltip(&self) -> Option<String> {
+        let (column, row) = self.hover?;
+        let (id, area) = self.panel_at(column, row)?;
+        let spec = panels::spec(id);
+
+        if let Some(index) = profile_row(id, area, row) {
+            let profile = PowerProfile::ALL[index];
+            return Some(if profile == self.state.power_profile {
+                format!("{} profile is active", profile.as_str())
+            } else {
+                format!("Click to apply {} profile", profile.as_str())
+            });
+        }
+        Some(match spec.focus {
+            Some(panel) if panel == self.focused => spec.title.to_string(),
+            Some(_) => format!("Click to focus {}", spec.title.to_lowercase()),
+            None => format!("{} (display only)", spec.title),
+        })
+    }
+
+    /// Handle preset name input: create from the current settings or rename
+    fn handle_preset_name_edit(&mut self, key: crossterm::event::KeyEvent, rename: bool) {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        match key.code {
+            KeyCode::Char(c)
+                if !key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.preset_input.chars().count() < PRESET_NAME_MAX =>
+            {
+                self.preset_input.push(c);
+            }
+            KeyCode::Backspace => {
+                self.preset_input.pop();
+            }
+            KeyCode::Enter => {
+                let name = self.preset_input.trim().to_string();
+                if !preset::valid_name(&name) {
+                    let hint = "Preset names can't be empty, contain / or start with .";
+                    self.set_status(hint.to_string());
+                    return;
+                }
+
+                let result = if rename {
+                    match self.presets.get_mut(self.selected_preset) {
+                        Some(preset) => preset.rename(&name),
+                        None => Ok(()),
+                    }
+                } else {
+                    Preset::from_state(&name, &self.state).save()
+                };
+                match result {
+                    Ok(()) => {
+                        let verb = if rename { "Renamed" } else { "Saved" };
+                        self.set_status(format!("{} preset {}", verb, name));
+                        self.reload_presets(Some(&name));
+                    }
+                    Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+                }
+                self.edit_mode = EditMode::None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Delete the selected preset on Enter (Esc cancels)
+    fn handle_preset_delete(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+
+        if key.code != KeyCode::Enter {
+            return;
+        }
+        self.edit_mode = EditMode::None;
+        let Some(preset) = self.presets.get(self.selected_preset) else {
+            return;
+        };
+        match preset.delete() {
+            Ok(()) => {
+                self.set_status(format!("Deleted preset {}", preset.name));
+                self.reload_presets(None);
+            }
+            Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+        }
+    }
+
+    /// Handle battery edit mode input
+    fn handle_battery_edit(&mut self, key: crossterm::event::KeyEvent, count: Option<u32>) {
         use crossterm::event::KeyCode;
 
         match key.code {
             KeyCode::Left | KeyCode::Char('h') => {
-                if point_index > 0 {
-                    self.edit_mode = EditMode::FanCurve {
-                        point_index: point_index - 1,
-                    };
+                let step = self.edit_step(key.code, count);
+                self.state.charge_limit = CHARGE_LIMIT_RANGE.clamp(self.state.charge_limit.saturating_sub(step));
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                let step = self.edit_step(key.code, count);
+                self.state.charge_limit = CHARGE_LIMIT_RANGE.clamp(self.state.charge_limit.saturating_add(step));
+            }
+            KeyCode::Enter => {
+                self.daemon.set_charge_limit(self.state.charge_limit);
+                self.edit_mode = EditMode::None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Keys in the keyboard color picker: `j`/`k` pick a row, `h`/`l` move
+    /// its slider or swatch, hex digits type a color on the hex row
+    fn handle_kbd_color_edit(&mut self, key: crossterm::event::KeyEvent, count: Option<u32>) {
+        use crossterm::event::KeyCode;
+
+        let rows = count.unwrap_or(1).min(PickerRow::ALL.len() as u32);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                (0..rows).for_each(|_| self.color_picker.select_row(false));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                (0..rows).for_each(|_| self.color_picker.select_row(true));
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                let step = self.edit_step(key.code, count);
+                self.color_picker.adjust(-(step as i16));
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                let step = self.edit_step(key.code, count);
+                self.color_picker.adjust(step as i16);
+            }
+            KeyCode::Backspace => self.color_picker.backspace(),
+            KeyCode::Enter => {
+                let color = self.color_picker.color;
+                // A picked color ends the temperature colors
+                self.reactive_lighting = false;
+                self.reactive_restore = None;
+                self.daemon.set_kbd_color(color);
+                self.set_status(format!("Setting keyboard color to {}...", color));
+                self.edit_mode = EditMode::None;
+            }
+            KeyCode::Char(c) => {
+                self.color_picker.type_hex(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_gpu_switch(&mut self, key: crossterm::event::KeyEvent, mode: GpuMode) {
+        if key.code != crossterm::event::KeyCode::Enter {
+            return;
+        }
+        self.edit_mode = EditMode::None;
+        self.daemon.set_gpu_mode(mode);
+        self.set_status(format!("Switching the GPU MUX to {}...", mode.as_str()));
+    }
+
+    /// Handle keyboard idle timeout edit mode input
+    fn handle_kbd_timeout_edit(&mut self, key: crossterm::event::KeyEvent, count: Option<u32>) {
+        use crossterm::event::KeyCode;
+
+        let Some(timeout) = self.state.aura.as_mut().and_then(|a| a.idle_timeout.as_mut()) else {
+            self.edit_mode = EditMode::None;
+            return;
+        };
+
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => {
+                *timeout = timeout.saturating_sub(count.unwrap_or(KBD_TIMEOUT_STEP));
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                *timeout = (*timeout + count.unwrap_or(KBD_TIMEOUT_STEP)).min(KBD_TIMEOUT_MAX);
+            }
+            KeyCode::Enter => {
+                let seconds = *timeout;
+                self.daemon.set_kbd_idle_timeout(seconds);
+                self.edit_mode = EditMode::None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle fan curve edit mode input
+    fn handle_fan_curve_edit(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        point_index: usize,
+        count: Option<u32>,
+    ) {
+        use crossterm::event::KeyCode;
+
+        let points = count.unwrap_or(1) as usize;
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.edit_mode = EditMode::FanCurve {
+                    point_index: point_index.saturating_sub(points),
+                };
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                let last = self.state.fan_curve.cpu_curve.len().saturating_sub(1);
+                self.edit_mode = EditMode::FanCurve {
+                    point_index: (point_index + points).min(last),
+                };
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                let steps = self.config.fan;
+                let step = self.edit_step(key.code, count).max(steps.speed_step);
+                if let Some(point) = self.state.fan_curve.cpu_curve.get_mut(point_index) {
+                    point.speed = steps.snap_speed(point.speed.saturating_add(step).min(100));
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let steps = self.config.fan;
+                let step = self.edit_step(key.code, count).max(steps.speed_step);
+                if let Some(point) = self.state.fan_curve.cpu_curve.get_mut(point_index) {
+                    point.speed = steps.snap_speed(point.speed.saturating_sub(step));
+                }
+            }
+            KeyCode::Enter => {
+                self.send_fan_curve(self.state.fan_curve.clone());
+                self.edit_mode = EditMode::None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle the curve copy prompt: h/l pick where to, j/k move the copy's
+    /// speeds up or down, Enter copies
+    fn handle_curve_copy(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        target: CurveTarget,
+        offset: i8,
+    ) {
+        use crossterm::event::KeyCode;
+
+        let active = self.state.power_profile;
+        let targets = CurveTarget::all(active);
+        let index = targets.iter().position(|&t| t == target).unwrap_or(0);
+        let target = match key.code {
+            KeyCode::Left | KeyCode::Char('h') => targets[index.saturating_sub(1)],
+            KeyCode::Right | KeyCode::Char('l') => targets[(index + 1).min(targets.len() - 1)],
+            _ => target,
+        };
+        let offset = match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                (offset + CURVE_COPY_STEP).min(CURVE_COPY_OFFSET_MAX)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                (offset - CURVE_COPY_STEP).max(-CURVE_COPY_OFFSET_MAX)
+            }
+            _ => offset,
+        };
+        if key.code != KeyCode::Enter {
+            self.edit_mode = EditMode::CurveCopy { target, offset };
+            return;
+        }
+
+        self.edit_mode = EditMode::None;
+        let copy = target.describe(active, offset);
+        match target {
+            CurveTarget::Gpu => {
+                self.state.fan_curve = self.state.fan_curve.cpu_to_gpu(offset);
+                self.send_fan_curve(self.state.fan_curve.clone());
+                self.set_status(format!("Copied {}", copy));
+            }
+            CurveTarget::Profile(profile) => {
+                self.send_fan_curve_to(profile, self.state.fan_curve.shifted(offset));
+                self.set_status(format!("Copying {}...", copy));
+            }
+        }
+    }
+
+    /// Handle the fan/profile switch matrix: h/l pick the profile, j/k the
+    /// fan, and Enter or Space flips the switch once asusd confirms it
+    fn handle_fan_enablement_edit(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        profile: PowerProfile,
+        fan: Fan,
+    ) {
+        use crossterm::event::KeyCode;
+
+        let index = profile.ui_index();
+        let profile = match key.code {
+            KeyCode::Left | KeyCode::Char('h') => PowerProfile::ALL[index.saturating_sub(1)],
+            KeyCode::Right | KeyCode::Char('l') => PowerProfile::ALL[(index + 1).min(2)],
+            _ => profile,
+        };
+        let fan = match key.code {
+            KeyCode::Up | KeyCode::Char('k') => Fan::Cpu,
+            KeyCode::Down | KeyCode::Char('j') => Fan::Gpu,
+            _ => fan,
+        };
+        self.edit_mode = EditMode::FanEnablement { profile, fan };
+
+        if matches!(key.code, KeyCode::Enter | KeyCode::Char(' ')) {
+            let enabled = !self.state.fan_curve.enablement.get(profile, fan);
+            self.daemon.set_fan_enabled(profile, fan, enabled);
+            self.set_status(format!(
+                "Switching the {} fan curve in {}...",
+                fan.as_str(),
+                profile.as_str()
+            ));
+        }
+    }
+
+    /// Update frame timing and effects
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        let delta = now.duration_since(self.last_frame);
+        self.last_frame = now;
+        self.frame_delta = delta;
+
+        if self.count_jump.is_some_and(|started| started.elapsed() >= COUNT_JUMP_DELAY) {
+            if let Some(count) = self.take_count() {
+                self.jump_to_count(count);
+                self.redraw = true;
+            }
+        }
+
+        // Update sakura particles; they hold still while the terminal is
+        // in the background or they're hidden. Only a particle changing
+        // cells needs a redraw.
+        let particles = self.terminal_focused && self.particles_visible();
+        if let Some(ref mut sakura) = self.sakura {
+            if particles && sakura.update(delta) {
+                self.redraw = true;
+            }
+        }
+
+        // Resample telemetry (rate-limited internally)
+        if self.telemetry.poll() {
+            self.redraw |= self.terminal_focused;
+            let profile = self.state.connected.then_some(self.state.power_profile);
+            self.stats.record(profile, &self.telemetry);
+
+            if let Some(recorder) = &mut self.recorder {
+                if let Err(e) = recorder.write(&TelemetryRecord::capture(profile, &self.telemetry)) {
+                    self.recorder = None;
+                    self.notify(Severity::Error, format!("Error: recording stopped: {}", e));
+                }
+            }
+
+            self.check_fan_stall();
+            self.check_profile_fans();
+            self.check_power_source();
+            self.check_quiet_hours();
+            self.check_tune_run();
+            self.check_reactive_lighting();
+            if let Some(celsius) = self.telemetry.fan_temp(self.config.ui.fan_temp_source) {
+                let (previous, hysteresis) = (self.fan_target, self.config.ui.fan_hysteresis);
+                let target = self.state.fan_curve.target_speed(celsius, previous, hysteresis);
+                self.fan_target = Some(target);
+            }
+        }
+
+        self.check_low_power();
+        self.check_blade();
+        self.check_processes();
+        self.check_idle();
+        self.check_profile_lighting();
+        self.check_profile_rules();
+        self.check_full_blast();
+        self.check_refresh();
+        self.check_health();
+    }
+
+    /// Keep the refresh spinner turning, giving up once asusd has taken too
+    /// long to answer
+    fn check_refresh(&mut self) {
+        let Some(sent) = self.refresh_pending else {
+            return;
+        };
+        self.redraw = true;
+        if sent.elapsed() >= REFRESH_TIMEOUT {
+            self.refresh_pending = None;
+            let secs = REFRESH_TIMEOUT.as_secs();
+            let error = format!("Error: refresh timed out after {} s, asusd isn't answering", secs);
+            self.notify(Severity::Error, error);
+        }
+    }
+
+    /// Track terminal focus; regaining it re-reads hardware state, which
+    /// may have changed while the user was elsewhere
+    pub fn set_terminal_focus(&mut self, focused: bool) {
+        if focused && !self.terminal_focused {
+            self.daemon.refresh();
+        }
+        self.terminal_focused = focused;
+        self.redraw = true;
+    }
+
+    /// Note how long the last frame took to draw, for the performance
+    /// overlay
+    pub fn record_frame(&mut self, elapsed: Duration) {
+        if self.frame_times.len() == FRAME_SAMPLES {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(elapsed);
+    }
+
+    fn perf_stats(&self) -> PerfStats {
+        let (queued_updates, queued_intents) = self.daemon.queue_depths();
+        PerfStats {
+            frame: self.frame_times.back().copied().unwrap_or_default(),
+            frame_max: self.frame_times.iter().max().copied().unwrap_or_default(),
+            effects: self.effects.count(),
+            particles: match &self.sakura {
+                Some(sakura) if self.particles_visible() => sakura.particle_count(),
+                _ => 0,
+            },
+            dbus_latency: self.daemon.last_call_latency(),
+            queued_updates,
+            queued_intents,
+        }
+    }
+
+    /// Whether to draw this frame: only when something changed, or while
+    /// effects run. While unfocused telemetry and particles don't count,
+    /// so only hardware changes, alerts and input redraw.
+    pub fn should_draw(&mut self) -> bool {
+        let redraw = std::mem::take(&mut self.redraw);
+        redraw || (self.terminal_focused && self.effects.has_active_effects())
+    }
+
+    /// How long to wait for input before drawing the next frame
+    pub fn frame_duration(&self) -> Duration {
+        let animating = (self.particles_visible() && self.sakura.is_some()) || self.effects.has_active_effects();
+        let frame = if !self.terminal_focused {
+            UNFOCUSED_FRAME
+        } else if animating && !self.safe_render {
+            ANIMATED_FRAME
+        } else {
+            STATIC_FRAME
+        };
+        if self.low_power {
+            frame * 2
+        } else {
+            frame
+        }
+    }
+
+    /// Whether sakura particles are drawn: switched on, and not hidden by
+    /// low-power rendering
+    fn particles_visible(&self) -> bool {
+        self.sakura_enabled && !self.low_power && !self.plain
+    }
+
+    /// Apply the first process rule with a running process, or restore the
+    /// profile and fan curve from before once none match
+    fn check_processes(&mut self) {
+        let rules = &self.config.automation.processes;
+        if rules.is_empty() || self.read_only || !self.process_watch.due() {
+            return;
+        }
+        let active = automation::active_rule(rules, &automation::running_processes());
+        if active == self.process_watch.active {
+            return;
+        }
+        self.process_watch.active = active;
+
+        let Some(index) = active else {
+            if let Some((profile, saved)) = self.process_watch.restore.take() {
+                if let Some((replaced, curve)) = saved {
+                    self.send_fan_curve_to(replaced, curve);
                 }
+                self.daemon.set_power_profile(profile);
+                self.state.power_profile = profile;
+                self.set_status(format!("Programs exited, back to {}", profile));
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                if point_index < self.state.fan_curve.cpu_curve.len() - 1 {
-                    self.edit_mode = EditMode::FanCurve {
-                        point_index: point_index + 1,
-                    };
+            return;
+        };
+
+        // A rule with a broken preset changes nothing
+        let rule = self.config.automation.processes[index].clone();
+        if let Some(name) = rule.preset.as_deref() {
+            let problem = match self.presets.iter().find(|p| p.name == name) {
+                None => Some(format!("no preset named {}", name)),
+                Some(preset) => {
+                    let invalid = preset.fan_curve.validate().err();
+                    invalid.map(|e| format!("preset {}: {}", name, e))
                 }
+            };
+            if let Some(problem) = problem {
+                self.notify(Severity::Warning, format!("Process rule: {}", problem));
+                return;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if let Some(point) = self.state.fan_curve.cpu_curve.get_mut(point_index) {
-                    point.speed = (point.speed + 5).min(100);
-                }
+        }
+
+        let current = self.state.power_profile;
+        let (_, saved) = self.process_watch.restore.get_or_insert((current, None));
+        // The previous rule's preset goes back before this one applies
+        if let Some((replaced, curve)) = saved.take() {
+            self.send_fan_curve_to(replaced, curve);
+        }
+        self.daemon.set_power_profile(rule.profile);
+        self.state.power_profile = rule.profile;
+        // Otherwise the preset waits for the profile's own curve to load
+        if current == rule.profile {
+            self.apply_process_preset(current);
+        }
+        let names = rule.processes.join(", ");
+        self.set_status(format!("{} running: switched to {}", names, rule.profile));
+    }
+
+    /// Write the active process rule's preset curve into its profile, saving
+    /// the curve it replaces. `loaded` is the profile whose curve
+    /// `state.fan_curve` holds.
+    fn apply_process_preset(&mut self, loaded: PowerProfile) {
+        let rules = &self.config.automation.processes;
+        let Some(rule) = self.process_watch.active.and_then(|i| rules.get(i)) else {
+            return;
+        };
+        let Some(name) = rule.preset.as_deref() else {
+            return;
+        };
+        let Some(preset) = self.presets.iter().find(|p| p.name == name) else {
+            return;
+        };
+        let pending = matches!(self.process_watch.restore, Some((_, None)));
+        if !pending || loaded != rule.profile || loaded != self.state.power_profile {
+            return;
+        }
+        let curve = preset.fan_curve.clone();
+        let before = self.state.fan_curve.clone();
+        self.send_fan_curve_to(loaded, curve);
+        if let Some((_, saved)) = &mut self.process_watch.restore {
+            *saved = Some((loaded, before));
+        }
+    }
+
+    /// Track logind's idle hint (microseconds since the epoch when idle)
+    fn set_idle(&mut self, since: Option<u64>) {
+        let Some(micros) = since else {
+            self.idle_since = None;
+            self.restore_from_idle();
+            return;
+        };
+        let idle_for = automation::idle_duration(micros, SystemTime::now());
+        self.idle_since = Some(Instant::now().checked_sub(idle_for).unwrap_or_else(Instant::now));
+    }
+
+    /// Drop to Quiet and dim the keyboard once idle long enough
+    fn check_idle(&mut self) {
+        let minutes = self.config.automation.idle_quiet_minutes;
+        let (Some(minutes), Some(since)) = (minutes, self.idle_since) else {
+            return;
+        };
+        if self.idle_restore.is_some()
+            || self.read_only
+            || since.elapsed() < Duration::from_secs(minutes as u64 * 60)
+        {
+            return;
+        }
+
+        let brightness = self.state.aura.as_ref().and_then(|aura| aura.brightness);
+        self.idle_restore = Some((self.state.power_profile, brightness));
+        if self.state.power_profile != PowerProfile::Quiet {
+            self.daemon.set_power_profile(PowerProfile::Quiet);
+            self.state.power_profile = PowerProfile::Quiet;
+        }
+        if brightness.is_some_and(|b| b.to_u32() > KbdBrightness::Low.to_u32()) {
+            self.daemon.set_kbd_brightness(KbdBrightness::Low);
+        }
+        self.set_status(format!("Idle for {} min: switched to Quiet", minutes));
+    }
+
+    /// Undo the idle changes once the user is back
+    fn restore_from_idle(&mut self) {
+        let Some((profile, brightness)) = self.idle_restore.take() else {
+            return;
+        };
+        if profile != self.state.power_profile {
+            self.daemon.set_power_profile(profile);
+            self.state.power_profile = profile;
+        }
+        let current = self.state.aura.as_ref().and_then(|aura| aura.brightness);
+        if let Some(brightness) = brightness.filter(|&b| Some(b) != current) {
+            self.daemon.set_kbd_brightness(brightness);
+        }
+        self.set_status(format!("Welcome back: restored {}", profile));
+    }
+
+    /// Apply the `[lighting]` entry for the current profile once it changes,
+    /// writing only what differs from the keyboard's state. Idle dimming and
+    /// event rules keep the backlight level while they hold.
+    fn check_profile_lighting(&mut self) {
+        let profile = self.state.power_profile;
+        let Some(aura) = self.state.aura.as_ref().filter(|_| self.state.connected) else {
+            return;
+        };
+        if self.read_only || self.lit_profile == Some(profile) {
+            return;
+        }
+        self.lit_profile = Some(profile);
+        let Some(lighting) = self.config.lighting.for_profile(profile) else {
+            return;
+        };
+
+        let mut actions = Vec::new();
+        let reactive = self.reactive_lighting;
+        if let Some(color) = lighting.color.filter(|&c| !reactive && aura.color != Some(c)) {
+            self.daemon.set_kbd_color(color);
+            actions.push(color.to_string());
+        }
+        let held = self.idle_restore.is_some() || self.event_keyboard_restore.is_some();
+        let differs = |level| aura.brightness.is_some_and(|current| current != level);
+        if let Some(level) = lighting.brightness.filter(|&level| !held && differs(level)) {
+            self.daemon.set_kbd_brightness(level);
+            actions.push(format!("brightness {}", level));
+        }
+        if !actions.is_empty() {
+            self.set_status(format!("{} lighting: {}", profile, actions.join(", ")));
+        }
+    }
+
+    fn toggle_reactive_lighting(&mut self) {
+        let Some(aura) = &self.state.aura else {
+            self.set_status("No Aura keyboard to color".to_string());
+            return;
+        };
+        self.reactive_lighting = !self.reactive_lighting;
+        if self.reactive_lighting {
+            self.reactive_restore = aura.color;
+            self.reactive_written = None;
+            let reactive = &self.config.lighting.reactive;
+            let unit = self.config.ui.temperature_unit;
+            self.set_status(format!(
+                "Keyboard color follows CPU temperature, blue at {} to red at {}",
+                unit.format(reactive.cool),
+                unit.format(reactive.hot)
+            ));
+        } else {
+            if let Some(color) = self.reactive_restore.take() {
+                self.daemon.set_kbd_color(color);
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if let Some(point) = self.state.fan_curve.cpu_curve.get_mut(point_index) {
-                    point.speed = point.speed.saturating_sub(5);
+            self.set_status("Keyboard color no longer follows temperature".to_string());
+        }
+    }
+
+    /// Write the temperature color, at most once per `interval_secs` and only
+    /// when it changes
+    fn check_reactive_lighting(&mut self) {
+        let Some(aura) = self.state.aura.as_ref().filter(|_| self.state.connected) else {
+            return;
+        };
+        let Some(celsius) = self.telemetry.cpu_temp() else {
+            return;
+        };
+        if !self.reactive_lighting || self.read_only {
+            return;
+        }
+        let reactive = &self.config.lighting.reactive;
+        if self.reactive_written.is_some_and(|(at, _)| at.elapsed() < reactive.interval()) {
+            return;
+        }
+        let color = reactive.color(celsius);
+        if aura.color == Some(color) {
+            return;
+        }
+
+        // Started from the config: keep the color from before the first write
+        if self.reactive_written.is_none() && self.reactive_restore.is_none() {
+            self.reactive_restore = aura.color;
+        }
+        self.daemon.set_kbd_color(color);
+        self.reactive_written = Some((Instant::now(), color));
+    }
+
+    /// Run the `[automation.profiles]` entry once the profile changes: its
+    /// command, and a prompt to move the GPU MUX. Nothing runs for the
+    /// profile hachi finds on launch.
+    fn check_profile_rules(&mut self) {
+        if !self.state.connected {
+            return;
+        }
+        let profile = self.state.power_profile;
+        let previous = self.acted_profile.replace(profile);
+        if previous.is_none() || previous == Some(profile) || self.read_only {
+            return;
+        }
+        let Some(rule) = self.config.automation.profiles.for_profile(profile).cloned() else {
+            return;
+        };
+
+        if let Some(command) = &rule.command {
+            match automation::run_command(command) {
+                Ok(()) => self.set_status(format!("{}: ran {}", profile, command)),
+                Err(e) => self.notify(Severity::Error, format!("Error: {}", e)),
+            }
+        }
+        let current = self.state.gpu_mode;
+        let offer = rule.gpu.filter(|&mode| current.is_some_and(|m| m != mode));
+        if let (Some(mode), Some(current)) = (offer, current) {
+            // Not over an edit in progress
+            if self.edit_mode == EditMode::None {
+                self.edit_mode = EditMode::GpuSwitch(mode);
+                self.set_status(format!(
+                    "{}: switch the GPU MUX from {} to {} (after a reboot)? \
+                     Enter to switch, Esc to keep",
+                    profile,
+                    current.as_str(),
+                    mode.as_str()
+                ));
+            }
+        }
+    }
+
+    /// Apply the first event rule matching the lid, dock and power state, or
+    /// restore the profile from before once none match
+    fn check_events(&mut self) {
+        let rules = &self.config.automation.events;
+        if rules.is_empty() || self.read_only {
+            return;
+        }
+        let active = automation::active_event(rules, &self.environment);
+        if active == self.event_rule {
+            return;
+        }
+        self.event_rule = active;
+
+        let brightness = self.state.aura.as_ref().and_then(|aura| aura.brightness);
+        let Some(index) = active else {
+            if let Some(level) = self.event_keyboard_restore.take().filter(|&l| Some(l) != brightness) {
+                self.daemon.set_kbd_brightness(level);
+            }
+            if let Some(profile) = self.event_restore.take() {
+                self.daemon.set_power_profile(profile);
+                self.state.power_profile = profile;
+                self.set_status(format!("Event rule ended, back to {}", profile));
+            }
+            return;
+        };
+
+        let rule = self.config.automation.events[index].clone();
+        let mut actions = Vec::new();
+        if let Some(profile) = rule.profile {
+            self.event_restore.get_or_insert(self.state.power_profile);
+            self.daemon.set_power_profile(profile);
+            self.state.power_profile = profile;
+            actions.push(profile.to_string());
+        }
+        let current = self.state.gpu_mode;
+        if let Some(mode) = rule.gpu.filter(|&mode| current.is_some_and(|m| m != mode)) {
+            self.daemon.set_gpu_mode(mode);
+            actions.push(format!("GPU {}", mode.as_str()));
+        }
+        // Only on keyboards with a backlight asusd controls
+        if let (Some(level), Some(current)) = (rule.keyboard, brightness) {
+            self.event_keyboard_restore.get_or_insert(current);
+            if level != current {
+                self.daemon.set_kbd_brightness(level);
+                actions.push(format!("keyboard {}", level));
+            }
+        }
+        if !actions.is_empty() {
+            let conditions = automation::describe(&rule);
+            self.set_status(format!("{}: switched to {}", conditions, actions.join(", ")));
+        }
+    }
+
+    /// Cap the fans and force the window's profile during quiet hours,
+    /// restoring both once the window ends
+    fn check_quiet_hours(&mut self) {
+        let rules = &self.config.automation.quiet_hours;
+        // A full blast burst wins until it ends
+        if rules.is_empty() || self.read_only || self.full_blast.is_some() {
+            return;
+        }
+        let active = automation::local_clock(SystemTime::now())
+            .and_then(|now| automation::active_quiet_hours(rules, now));
+        if active == self.quiet_hours {
+            return;
+        }
+        self.quiet_hours = active;
+
+        let Some(index) = active else {
+            if let Some((profile, saved)) = self.quiet_restore.take() {
+                // Uncap the window profile's curve before leaving it
+                if let Some((capped, curve)) = saved {
+                    self.send_fan_curve_to(capped, curve);
                 }
+                self.daemon.set_power_profile(profile);
+                self.state.power_profile = profile;
+                self.set_status(format!("Quiet hours over: back to {}", profile));
             }
-            KeyCode::Enter => {
-                self.daemon.set_fan_curve(self.state.fan_curve.clone());
-                self.edit_mode = EditMode::None;
+            return;
+        };
+
+        let rule = self.config.automation.quiet_hours[index].clone();
+        let profile = self.state.power_profile;
+        let (_, saved) = self.quiet_restore.get_or_insert((profile, None));
+        // A window following another on a different profile uncaps it first
+        if let Some((capped, curve)) = saved.take_if(|(capped, _)| *capped != rule.profile) {
+            self.send_fan_curve_to(capped, curve);
+        }
+        if profile == rule.profile {
+            self.cap_quiet_fans();
+        } else {
+            // The cap follows once the window profile's curve has loaded
+            self.daemon.set_power_profile(rule.profile);
+            self.state.power_profile = rule.profile;
+        }
+        if self.quiet_cap().is_none() {
+            let reason = if self.state.fan_curve_control {
+                "fans capped once its curve loads"
+            } else {
+                "no fan curve control, fans not capped"
+            };
+            self.set_status(format!(
+                "Quiet hours {}-{}: {} ({})",
+                rule.start, rule.end, rule.profile, reason
+            ));
+        }
+    }
+
+    /// Cap the window profile's curve during quiet hours, once it's the
+    /// active curve; without asusd's fan curves only the profile part applies
+    fn cap_quiet_fans(&mut self) {
+        let Some(rule) = self.quiet_hours.and_then(|i| self.config.automation.quiet_hours.get(i))
+        else {
+            return;
+        };
+        let rule = rule.clone();
+        let profile = self.state.power_profile;
+        let uncapped = matches!(self.quiet_restore, Some((_, None)));
+        if !uncapped || profile != rule.profile || !self.state.fan_curve_control {
+            return;
+        }
+        let curve = self.state.fan_curve.clone();
+        self.send_fan_curve_to(profile, curve.capped(rule.fan_cap, profile));
+        if let Some((_, saved)) = &mut self.quiet_restore {
+            *saved = Some((profile, curve));
+        }
+        self.set_status(format!(
+            "Quiet hours {}-{}: fans capped at {}%, {}",
+            rule.start, rule.end, rule.fan_cap, rule.profile
+        ));
+    }
+
+    /// Fan cap of the quiet hours window in effect, once it has been applied
+    fn quiet_cap(&self) -> Option<u8> {
+        let (_, Some(_)) = self.quiet_restore.as_ref()? else {
+            return None;
+        };
+        let rule = self.config.automation.quiet_hours.get(self.quiet_hours?)?;
+        Some(rule.fan_cap)
+    }
+
+    /// Cut hachi's own overhead while the laptop is saving power, unless
+    /// `follow_power_profile` is off
+    fn check_low_power(&mut self) {
+        let saving = self.state.power_profile == PowerProfile::Quiet || self.on_battery == Some(true);
+        let low_power = self.config.ui.follow_power_profile.unwrap_or(true) && saving;
+        if low_power == self.low_power {
+            return;
+        }
+        self.low_power = low_power;
+        self.telemetry.interval = if low_power {
+            LOW_POWER_SAMPLE_INTERVAL
+        } else {
+            SAMPLE_INTERVAL
+        };
+        let status = if low_power {
+            "Low-power rendering: half frame rate, no particles"
+        } else {
+            "Full rendering restored"
+        };
+        self.set_status(status.to_string());
+    }
+
+    /// Animate the katana blade from the battery status: energy flows while
+    /// charging and it pulses red when discharging low. Only runs while the
+    /// battery panel is on screen.
+    fn check_blade(&mut self) {
+        let effect = self.telemetry.battery.as_ref().and_then(|battery| {
+            if battery.is_charging() {
+                Some(BladeEffect::Charging)
+            } else if battery.is_discharging() && battery.capacity < LOW_BATTERY_PERCENT {
+                Some(BladeEffect::Low)
+            } else {
+                None
             }
-            _ => {}
+        });
+        let area = self
+            .panel_areas
+            .iter()
+            .find(|(id, _)| *id == PanelId::Battery)
+            .and_then(|(_, area)| BatteryKatana::blade_area(*area))
+            .filter(|_| self.tab == Tab::Panels);
+        let (effect, area) = match (effect, area) {
+            (Some(effect), Some(area)) => (Some(effect), area),
+            _ => (None, Rect::default()),
+        };
+        if effect == self.blade_effect && area == self.blade_area {
+            return;
         }
+        self.blade_effect = effect;
+        self.blade_area = area;
+        match effect {
+            Some(BladeEffect::Charging) => self.effects.trigger_blade_charging(area),
+            Some(BladeEffect::Low) => self.effects.trigger_blade_low(area),
+            None => self.effects.stop_blade(),
+        }
+        self.redraw = true;
     }
 
-    /// Update frame timing and effects
-    pub fn tick(&mut self) {
-        let now = Instant::now();
-        let delta = now.duration_since(self.last_frame);
-        self.last_frame = now;
+    /// Dim the display when switching to battery and restore it on AC
+    fn check_power_source(&mut self) {
+        let Some(on_battery) = self.telemetry.battery.as_ref().map(|b| b.is_discharging()) else {
+            return;
+        };
+        if self.environment.external_power == on_battery {
+            self.environment.external_power = !on_battery;
+            self.check_events();
+        }
 
-        // Update sakura particles
-        if let Some(ref mut sakura) = self.sakura {
-            sakura.update(delta);
+        let changed = self.on_battery.is_some_and(|prev| prev != on_battery);
+        self.on_battery = Some(on_battery);
+        if !changed {
+            return;
+        }
+        self.power_since = Some(Instant::now());
+
+        let (Some(dim_percent), Some(backlight)) =
+            (self.config.display.dim_on_battery, &self.state.backlight)
+        else {
+            return;
+        };
+
+        if on_battery {
+            let dimmed = backlight.raw_for_percent(dim_percent).max(1);
+            if dimmed < backlight.brightness {
+                self.undim_brightness = Some(backlight.brightness);
+                self.daemon.set_display_brightness(dimmed);
+            }
+        } else if let Some(brightness) = self.undim_brightness.take() {
+            self.daemon.set_display_brightness(brightness);
+        }
+    }
+
+    /// Learn how loud each profile gets: the fastest fan speed seen under
+    /// it, once the fans have had time to leave the previous profile's speed
+    fn check_profile_fans(&mut self) {
+        // Full blast pins the fans whatever the profile
+        if !self.state.connected || self.full_blast.is_some() {
+            return;
+        }
+        let profile = self.state.power_profile;
+        let since = match self.fan_profile {
+            Some((current, since)) if current == profile => since,
+            _ => {
+                self.fan_profile = Some((profile, Instant::now()));
+                return;
+            }
+        };
+        let Some(rpm) = self.telemetry.fans.iter().map(|fan| fan.rpm).max() else {
+            return;
+        };
+        let peak = self.profile_fans.get(profile).unwrap_or(0);
+        if since.elapsed() >= FAN_SETTLE && rpm > peak {
+            self.profile_fans.set(profile, rpm);
+        }
+    }
+
+    /// Raise a critical alert when a fan stops spinning under load
+    fn check_fan_stall(&mut self) {
+        let threshold = self.config.alerts.fan_stall_temp;
+        let stall = self
+            .telemetry
+            .stalled_fan(threshold)
+            .map(|(fan, temp)| (fan.label.clone(), temp));
+
+        // Only alert on the transition into the stalled state
+        if let (Some((fan, temp)), false) = (&stall, self.fan_stall) {
+            let temp = self.config.ui.temperature_unit.format(*temp);
+            self.notify(
+                Severity::Critical,
+                format!("Fan stall: {} at 0 RPM while {} - check fan curve", fan, temp),
+            );
         }
+        self.fan_stall = stall.is_some();
     }
 
     /// Render the application
     pub fn render(&mut self, frame: &mut ratatui::Frame) {
         let area = frame.area();
+        self.last_height = area.height;
+        if self.plain {
+            self.render_plain(frame.buffer_mut(), area);
+            return;
+        }
 
         // Clear with void black background
         let buf = frame.buffer_mut();
@@ -355,97 +3064,321 @@ impl App {
             }
         }
 
-        // Render sakura particles in background (if enabled)
-        if self.sakura_enabled {
-            if let Some(ref sakura) = self.sakura {
-                sakura.render(buf, area);
-            }
-        }
-
-        // Main layout - compact header to maximize content space
+        // Main layout - header sized to its art, content takes the rest.
+        // Short terminals get the one-row header to leave room for the panels.
+        let compact = self.config.ui.compact_header.unwrap_or(area.height < COMPACT_HEADER_BELOW);
+        let header = Header::new()
+            .art(self.config.ui.header_art)
+            .compact(compact)
+            .model(self.model.as_deref())
+            .profile(self.state.connected.then_some(self.state.power_profile));
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(7),   // Header (minimal)
+                Constraint::Length(header.height()),
                 Constraint::Min(10),     // Main content (expanded)
                 Constraint::Length(1),   // Status bar
             ])
             .split(area);
 
         // Render header
-        Header::new().render(chunks[0], buf);
+        header.render(chunks[0], buf);
+        self.header_area = chunks[0];
 
-        // Main content area
-        let content_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(35), // Left panel
-                Constraint::Percentage(65), // Right panel
-            ])
-            .margin(1)
+        let content = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
             .split(chunks[1]);
 
-        // Left panel: Power Profile + Battery
-        let left_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(10), // Power profile
-                Constraint::Min(6),     // Battery
-            ])
-            .split(content_chunks[0]);
-
-        // Render power profile selector
-        PowerProfileSelector::new(self.state.power_profile)
-            .selected(self.selected_profile)
-            .focused(self.focused == FocusedPanel::PowerProfile)
-            .render(left_chunks[0], buf);
-
-        // Render battery katana
-        BatteryKatana::new(self.state.charge_limit)
-            .focused(self.focused == FocusedPanel::Battery)
-            .editing(self.edit_mode == EditMode::Battery)
-            .render(left_chunks[1], buf);
-
-        // Right panel: Fan curve
-        let fan_selected_point = match self.edit_mode {
-            EditMode::FanCurve { point_index } => Some(point_index),
-            _ => None,
-        };
+        TabBar::new(&TABS)
+            .selected(if self.tab == Tab::Dashboard { 0 } else { 1 })
+            .render(content[0], buf);
+        Clock::new(&self.clock())
+            .uptime(self.telemetry.uptime)
+            .power(self.on_battery, self.power_since.map(|since| since.elapsed()))
+            .render(content[0], buf);
+
+        match self.tab {
+            Tab::Dashboard => {
+                Dashboard::new(&self.state, &self.telemetry)
+                    .unit(self.config.ui.temperature_unit)
+                    .render(content[1].inner(Margin::new(1, 1)), buf);
+            }
+            Tab::Panels => self.panel_areas = self.render_panels(content[1], buf),
+        }
 
-        FanCurveGraph::new(&self.state.fan_curve)
-            .selected_point(fan_selected_point)
-            .focused(self.focused == FocusedPanel::FanCurve)
-            .editing(matches!(self.edit_mode, EditMode::FanCurve { .. }))
-            .render(content_chunks[1], buf);
+        // Sakura particles fill the background cells left over by the panels
+        if self.particles_visible() {
+            if let Some(ref sakura) = self.sakura {
+                sakura.render(buf, area);
+            }
+        }
 
         // Render status bar
-        let mut status_bar = StatusBar::new(self.state.connected);
-        if let Some((ref msg, _)) = self.status_message {
-            status_bar = status_bar.message(msg);
+        let tooltip = self.tooltip();
+        let hints = hints::hints(self);
+        let mut status_bar = StatusBar::new(self.state.connected)
+            .backend(self.state.backend)
+            .disconnect_reason(self.state.disconnect_reason)
+            .retrying(self.retrying > 0)
+            .read_only(self.read_only)
+            .recording(self.recorder.is_some())
+            .macro_register(self.macros.recording())
+            .full_blast(self.full_blast.as_ref().map(|(started, ..)| {
+                FULL_BLAST_TIME.saturating_sub(started.elapsed())
+            }))
+            .tuning(self.tuning.run.as_ref().map(TuneRun::elapsed))
+            .refreshing(self.refresh_pending.map(|sent| sent.elapsed()))
+            .fan_cap(self.quiet_cap())
+            .tooltip(tooltip.as_deref())
+            .hints(&hints);
+        if let Some((ref msg, severity, _)) = self.status_message {
+            status_bar = status_bar.message(msg, severity);
         }
         status_bar.render(chunks[2], buf);
 
+        // After a prefix key, what can follow it, just above the status bar
+        if let Some((title, keys)) = hints::continuations(self) {
+            let which_key = WhichKey::new(&title, &keys);
+            let height = which_key.height(area.width).min(chunks[2].y - area.y);
+            let overlay = Rect {
+                y: chunks[2].y - height,
+                height,
+                ..area
+            };
+            which_key.render(overlay, buf);
+        }
+
+        // Critical alerts and external changes pop up in the top-right corner
+        if let Some((ref msg, severity, _)) = self.toast {
+            let width = (msg.chars().count() as u16 + 4).min(area.width / 2).max(20);
+            let toast_area = Rect {
+                x: area.right().saturating_sub(width + 1),
+                y: area.y + 1,
+                width: width.min(area.width),
+                height: 3.min(area.height),
+            };
+            Toast::new(msg).severity(severity).render(toast_area, buf);
+        }
+
         // Render help popup if visible
         if self.show_help {
-            let popup_area = centered_rect(50, 60, area);
-            HelpPopup.render(popup_area, buf);
+            match self.help_panel.map(panels::spec) {
+                Some(spec) => {
+                    let height = spec.help.len() as u16 + 4;
+                    let popup_area = centered_rect(50, 100, area);
+                    let popup_area = Rect {
+                        y: popup_area.y + popup_area.height.saturating_sub(height) / 2,
+                        height: height.min(popup_area.height),
+                        ..popup_area
+                    };
+                    PanelHelp::new(spec.title, spec.help).render(popup_area, buf);
+                }
+                None => HelpPopup.render(centered_rect(50, 60, area), buf),
+            }
+        }
+
+        if self.show_tune {
+            let (before, after) = self.tuning.traces();
+            let running = self.tuning.run.as_ref().map(|run| (run.elapsed(), run.loading()));
+            TunePopup::new(before, after, &self.config.tune.command)
+                .running(running)
+                .unit(self.config.ui.temperature_unit)
+                .render(centered_rect(70, 70, area), buf);
+        }
+
+        if let Some(view) = &self.health {
+            let popup = HealthPopup::new(&view.checks)
+                .running(view.finished.is_none().then(|| view.started.elapsed()));
+            let popup_area = centered_rect(60, 100, area);
+            let height = popup.height().min(popup_area.height);
+            let popup_area = Rect {
+                y: popup_area.y + (popup_area.height - height) / 2,
+                height,
+                ..popup_area
+            };
+            popup.render(popup_area, buf);
+        }
+
+        if self.edit_mode == EditMode::KbdColor {
+            let popup_area = centered_rect(50, 100, area);
+            let height = ColorPicker::HEIGHT.min(popup_area.height);
+            let popup_area = Rect {
+                y: popup_area.y + (popup_area.height - height) / 2,
+                height,
+                ..popup_area
+            };
+            ColorPicker::new(&self.color_picker)
+                .title("keyboard color")
+                .render(popup_area, buf);
+        }
+
+        if let Some(view) = &self.messages {
+            let found = view.found(&self.history);
+            MessagesPopup::new(&found)
+                .query(&view.query, view.searching)
+                .least(view.least)
+                .scroll(view.scroll)
+                .render(centered_rect(70, 70, area), buf);
+        }
+
+        if let Some(console) = &self.console {
+            ConsolePopup::new(console).render(centered_rect(80, 70, area), buf);
         }
 
         // Process effects
-        let delta = Duration::from_millis(16); // ~60fps
-        self.effects.process(delta, buf, area);
+        self.effects.process(self.frame_delta, buf, area);
+
+        if self.show_perf {
+            let width = PerfOverlay::WIDTH.min(area.width);
+            let overlay_area = Rect {
+                x: area.right() - width,
+                y: area.y,
+                width,
+                height: PerfOverlay::HEIGHT.min(area.height),
+            };
+            PerfOverlay::new(&self.perf_stats()).render(overlay_area, buf);
+        }
+
+        if self.safe_render {
+            compat::downgrade(buf, area);
+        }
+    }
+
+    /// Local time as `HH:MM`
+    fn clock(&self) -> String {
+        automation::local_clock(SystemTime::now()).map_or("--:--".to_string(), |t| t.to_string())
+    }
+
+    /// `--plain`: unstyled text, wrapped to the width. Effects never run, so
+    /// the ones other code starts are dropped.
+    fn render_plain(&mut self, buf: &mut Buffer, area: Rect) {
+        self.effects.clear();
+        let lines: Vec<Line> = plain::lines(self).into_iter().map(Line::from).collect();
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    /// Lay out the configured panel columns, the first one narrower when
+    /// there are two (desktop: controls left, graphs right). Returns where
+    /// each panel went.
+    fn render_panels(&self, area: Rect, buf: &mut Buffer) -> Vec<(PanelId, Rect)> {
+        let columns = self.layout_columns();
+        let widths: Vec<Constraint> = match columns.len() {
+            2 => vec![Constraint::Percentage(35), Constraint::Percentage(65)],
+            n => vec![Constraint::Ratio(1, n.max(1) as u32); n],
+        };
+        let column_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(widths)
+            .margin(1)
+            .split(area);
+
+        let mut placed = Vec::new();
+        for (column, column_area) in columns.iter().zip(column_areas.iter()) {
+            let specs: Vec<_> = column.iter().map(|&id| panels::spec(id)).collect();
+            let panel_areas = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(specs.iter().map(|spec| spec.constraint))
+                .split(*column_area);
+
+            for (spec, panel_area) in specs.iter().zip(panel_areas.iter()) {
+                (spec.render)(self, *panel_area, buf);
+
+                // Grey out controls that can't be used
+                if self.read_only && spec.focus.is_some() {
+                    buf.set_style(*panel_area, styles::text_dim());
+                }
+
+                let hovered = self
+                    .hover
+                    .is_some_and(|(x, y)| panel_area.contains(Position::new(x, y)));
+                if hovered && !self.show_help && spec.focus != Some(self.focused) {
+                    highlight_border(*panel_area, buf);
+                }
+                placed.push((spec.id, *panel_area));
+            }
+        }
+        placed
+    }
+
+    /// Switch to `--plain` output, starting on the panels since the dashboard
+    /// has nothing to add in text
+    pub fn set_plain(&mut self) {
+        self.plain = true;
+        self.tab = Tab::Panels;
     }
 
     /// Handle terminal resize
     pub fn resize(&mut self, width: u16, height: u16) {
+        self.redraw = true;
         if let Some(ref mut sakura) = self.sakura {
             sakura.resize(width, height);
         }
     }
 
-    /// Shutdown the daemon actor
-    pub fn shutdown(&self) {
-        self.daemon.shutdown();
+    /// Snapshot of the UI state worth restoring next run
+    pub fn session(&self) -> UiSession {
+        UiSession {
+            tab: self.tab,
+            focused: self.focused,
+            sakura_enabled: self.sakura_enabled,
+            profile_limits: self.profile_limits.clone(),
+            profile_fans: self.profile_fans.clone(),
+        }
+    }
+
+    /// Shutdown the daemon actor, waiting for pending writes. Returns
+    /// false if some had to be abandoned.
+    pub async fn shutdown(&mut self) -> bool {
+        // Stop a stress run rather than leave it loading the machine
+        self.tuning.run = None;
+        // Don't leave the fans pinned at 100% after quitting
+        if let Some((_, profile, curve)) = self.full_blast.take() {
+            self.send_fan_curve_to(profile, curve);
+        }
+        // ...or capped by quiet hours
+        if let Some((profile, saved)) = self.quiet_restore.take() {
+            if let Some((capped, curve)) = saved {
+                self.send_fan_curve_to(capped, curve);
+            }
+            self.daemon.set_power_profile(profile);
+        }
+        self.daemon.shutdown_and_wait(SHUTDOWN_TIMEOUT).await
+    }
+}
+
+/// Power profile under a row of the power panel: one profile every two
+/// rows inside the border
+fn profile_row(id: PanelId, area: Rect, row: u16) -> Option<usize> {
+    if id != PanelId::Power || row + 1 >= area.bottom() {
+        return None;
+    }
+    let index = (row.checked_sub(area.y + 1)? / 2) as usize;
+    (index < PowerProfile::ALL.len()).then_some(index)
+}
+
+/// Restyle a panel's border ring for the hovered panel
+fn highlight_border(area: Rect, buf: &mut Buffer) {
+    if area.is_empty() {
+        return;
+    }
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    for x in area.left()..area.right() {
+        for y in [area.top(), bottom] {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_style(styles::border_hover());
+            }
+        }
+    }
+    for y in area.top()..area.bottom() {
+        for x in [area.left(), right] {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_style(styles::border_hover());
+            }
+        }
     }
 }
 
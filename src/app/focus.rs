@@ -0,0 +1,70 @@
+//! Tab order: the focusable panels the layout shows, optionally reordered
+//! by `[layout] focus_order`. Panels left out of the layout, or hidden for
+//! hardware the laptop lacks, never take focus.
+
+use super::panels::{spec, PanelId};
+use super::FocusedPanel;
+
+/// Focus targets in the order Tab visits them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusRing {
+    panels: Vec<FocusedPanel>,
+    wrap: bool,
+}
+
+impl FocusRing {
+    /// Focus targets of the shown panels: those named in `order` first, in
+    /// that order, then the rest column by column, top to bottom
+    pub fn new(columns: &[Vec<PanelId>], order: &[PanelId]) -> Self {
+        let shown: Vec<PanelId> = columns.iter().flatten().copied().collect();
+        let ordered = order.iter().filter(|id| shown.contains(id));
+        let rest = shown.iter().filter(|id| !order.contains(id));
+
+        let mut panels = Vec::new();
+        for panel in ordered.chain(rest).filter_map(|&id| spec(id).focus) {
+            if !panels.contains(&panel) {
+                panels.push(panel);
+            }
+        }
+        Self { panels, wrap: true }
+    }
+
+    /// Whether stepping past either end wraps around (default) or stays put
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn panels(&self) -> &[FocusedPanel] {
+        &self.panels
+    }
+
+    pub fn contains(&self, panel: FocusedPanel) -> bool {
+        self.panels.contains(&panel)
+    }
+
+    pub fn first(&self) -> Option<FocusedPanel> {
+        self.panels.first().copied()
+    }
+
+    pub fn last(&self) -> Option<FocusedPanel> {
+        self.panels.last().copied()
+    }
+
+    /// The panel after (or before) `current`. A panel that isn't in the
+    /// ring hands focus to the first one; `None` when nothing can take it.
+    pub fn step(&self, current: FocusedPanel, forward: bool) -> Option<FocusedPanel> {
+        let ring = &self.panels;
+        let Some(index) = ring.iter().position(|&panel| panel == current) else {
+            return self.first();
+        };
+        let next = match (forward, self.wrap) {
+            (true, _) if index + 1 < ring.len() => index + 1,
+            (false, _) if index > 0 => index - 1,
+            (true, true) => 0,
+            (false, true) => ring.len() - 1,
+            (_, false) => index,
+        };
+        Some(ring[next])
+    }
+}
@@ -0,0 +1,131 @@
+//! Status bar key hints for the current context: the open popup, edit mode
+//! or focused panel, followed by the global keys. After a prefix key the
+//! which-key overlay lists what can follow it.
+
+use super::panels::REGISTRY;
+use super::{App, EditMode, Tab};
+
+/// A key and what it does, e.g. `("enter", "apply")`
+pub type Hint = (&'static str, &'static str);
+
+const GLOBAL: [Hint; 3] = [("tab", "cycle"), ("?", "help"), ("q", "quit")];
+
+/// Hints for whatever currently receives keys, most specific first
+pub fn hints(app: &App) -> Vec<Hint> {
+    if let Some(view) = &app.messages {
+        return if view.searching {
+            vec![("enter", "done"), ("esc", "clear")]
+        } else {
+            vec![
+                ("/", "search"),
+                ("d/i/w/e", "level"),
+                ("y", "copy"),
+                ("j/k", "scroll"),
+                ("esc", "close"),
+            ]
+        };
+    }
+    if app.console.is_some() {
+        return vec![("enter", "run"), ("↑/↓", "recall"), ("esc", "close")];
+    }
+    if app.health.is_some() {
+        return vec![("D", "re-run"), ("enter", "continue")];
+    }
+    if app.show_tune {
+        return vec![("enter", "run / stop"), ("esc", "close")];
+    }
+    if app.show_help {
+        if app.help_panel.is_some() {
+            return vec![("?", "all keys"), ("esc", "close")];
+        }
+        return vec![("esc", "close")];
+    }
+
+    match app.edit_mode {
+        EditMode::Battery | EditMode::KbdIdleTimeout => {
+            return vec![("h/l", "adjust"), ("enter", "apply"), ("esc", "cancel")];
+        }
+        EditMode::FanCurve { .. } => {
+            return vec![
+                ("h/l", "point"),
+                ("j/k", "speed"),
+                ("enter", "apply"),
+                ("esc", "cancel"),
+            ];
+        }
+        EditMode::FanEnablement { .. } => {
+            return vec![
+                ("h/l", "profile"),
+                ("j/k", "fan"),
+                ("space", "on/off"),
+                ("esc", "done"),
+            ];
+        }
+        EditMode::CurveCopy { .. } => {
+            return vec![
+                ("h/l", "copy to"),
+                ("j/k", "speeds"),
+                ("enter", "copy"),
+                ("esc", "cancel"),
+            ];
+        }
+        EditMode::KbdColor => {
+            return vec![
+                ("j/k", "row"),
+                ("h/l", "adjust"),
+                ("enter", "apply"),
+                ("esc", "cancel"),
+            ];
+        }
+        EditMode::PresetName { .. } => return vec![("enter", "save"), ("esc", "cancel")],
+        EditMode::PresetDelete => return vec![("enter", "delete"), ("esc", "cancel")],
+        EditMode::GpuSwitch(_) => return vec![("enter", "switch GPU"), ("esc", "keep")],
+        EditMode::None => {}
+    }
+
+    let mut hints = match app.tab {
+        Tab::Dashboard => vec![("1-8", "panel")],
+        Tab::Panels => REGISTRY
+            .iter()
+            .find(|spec| spec.focus == Some(app.focused))
+            .map_or_else(Vec::new, |spec| spec.hints.to_vec()),
+    };
+    hints.extend(GLOBAL);
+    hints
+}
+
+/// Title and the keys that can follow a pending prefix: `Q` or `@` for
+/// macros, or a count typed while editing a value or on a list
+pub fn continuations(app: &App) -> Option<(String, Vec<(String, String)>)> {
+    if let Some(pending) = app.macros.continuations() {
+        return Some(pending);
+    }
+
+    let count = app.count.0?;
+    let keys = match app.edit_mode {
+        EditMode::Battery => vec![("h / l", format!("charge limit -/+ {}%", count))],
+        EditMode::KbdIdleTimeout => vec![("h / l", format!("idle timeout -/+ {} s", count))],
+        EditMode::FanCurve { .. } => vec![
+            ("h / l", format!("move {} points", count)),
+            ("j / k", format!("speed -/+ {}%", count)),
+        ],
+        EditMode::KbdColor => vec![
+            ("j / k", format!("move {} rows", count)),
+            ("h / l", format!("adjust -/+ {}", count)),
+        ],
+        EditMode::None => {
+            let mut keys = vec![("j / k", format!("move {} rows", count))];
+            if count <= 8 {
+                keys.push(("other", format!("jump to panel {}", count)));
+            }
+            keys
+        }
+        _ => return None,
+    };
+    let keys = keys
+        .into_iter()
+        .map(|(key, action)| (key.to_string(), action))
+        .chain([("0-9".to_string(), "extend the count".to_string())])
+        .collect();
+    Some((format!("{}  count", count), keys))
+}
@@ -0,0 +1,97 @@
+//! Status message history for the `:` messages popup, like vim's
+//! `:messages`: the last 100 status lines and errors with timestamps and
+//! severities, filterable by the least severity shown
+
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use super::Severity;
+use crate::automation::local_tm;
+
+/// Messages kept before the oldest are dropped
+pub const HISTORY_LEN: usize = 100;
+
+/// One status line as it was shown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub time: SystemTime,
+    pub severity: Severity,
+    pub text: String,
+}
+
+impl Message {
+    /// Local wall-clock time as `HH:MM:SS`
+    pub fn timestamp(&self) -> String {
+        clock(self.time)
+    }
+
+    /// `HH:MM:SS level text`, as copied to the clipboard
+    pub fn line(&self) -> String {
+        format!(
+            "{} {:5} {}",
+            self.timestamp(),
+            self.severity.as_str(),
+            self.text
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    messages: VecDeque<Message>,
+}
+
+impl History {
+    pub fn push(&mut self, severity: Severity, text: &str) {
+        if self.messages.len() == HISTORY_LEN {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(Message {
+            time: SystemTime::now(),
+            severity,
+            text: text.to_string(),
+        });
+    }
+
+    /// Every message kept, oldest first
+    pub fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.messages.iter()
+    }
+
+    /// Messages containing `query` (ignoring case), newest first
+    pub fn search(&self, query: &str) -> Vec<&Message> {
+        let query = query.to_lowercase();
+        self.messages
+            .iter()
+            .rev()
+            .filter(|message| message.text.to_lowercase().contains(&query))
+            .collect()
+    }
+}
+
+/// Popup state: search query, severity filter and scroll offset
+#[derive(Debug, Default)]
+pub struct MessagesView {
+    pub query: String,
+    /// Whether keys are being typed into the query
+    pub searching: bool,
+    /// Least severity listed; debug lines are hidden at first
+    pub least: Severity,
+    pub scroll: usize,
+}
+
+impl MessagesView {
+    /// Messages matching the query and filter, newest first
+    pub fn found<'a>(&self, history: &'a History) -> Vec<&'a Message> {
+        let mut found = history.search(&self.query);
+        found.retain(|message| message.severity >= self.least);
+        found
+    }
+}
+
+fn clock(time: SystemTime) -> String {
+    match local_tm(time) {
+        Some(tm) => format!("{:02}:{:02}:{:02}", tm.tm_hour, tm.tm_min, tm.tm_sec),
+        None => "--:--:--".to_string(),
+    }
+}
@@ -0,0 +1,128 @@
+//! Vim-style keyboard macros: `Q` and a register (a-z) starts recording,
+//! `Q` stops, `@` and a register replays (`@@` repeats the last replay).
+//! Registers live for the session only.
+
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// What a key means to the macro layer
+pub enum MacroAction {
+    /// Not a macro key, handle it as usual
+    Pass,
+    /// Consumed, with a status line to show
+    Status(String),
+    /// Replay a register's keys
+    Replay(char, Vec<KeyEvent>),
+}
+
+/// Waiting for the register after `Q` or `@`
+enum Pending {
+    Record,
+    Replay,
+}
+
+#[derive(Default)]
+pub struct Macros {
+    registers: HashMap<char, Vec<KeyEvent>>,
+    recording: Option<(char, Vec<KeyEvent>)>,
+    pending: Option<Pending>,
+    last_replayed: Option<char>,
+}
+
+impl Macros {
+    /// Register being recorded into, if any
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|(register, _)| *register)
+    }
+
+    /// Title and the keys that can follow a pending `Q` or `@`, for the
+    /// which-key overlay
+    pub fn continuations(&self) -> Option<(String, Vec<(String, String)>)> {
+        match self.pending.as_ref()? {
+            Pending::Record => Some((
+                "Q  record macro".to_string(),
+                vec![
+                    ("a-z".to_string(), "record into register".to_string()),
+                    ("esc".to_string(), "cancel".to_string()),
+                ],
+            )),
+            Pending::Replay => {
+                let mut keys: Vec<(String, String)> = self
+                    .registers
+                    .iter()
+                    .map(|(register, keys)| (register.to_string(), format!("{} keys", keys.len())))
+                    .collect();
+                keys.sort();
+                if keys.is_empty() {
+                    keys.push(("a-z".to_string(), "no macros recorded yet".to_string()));
+                }
+                if let Some(last) = self.last_replayed {
+                    keys.push(("@".to_string(), format!("last (@{})", last)));
+                }
+                keys.push(("esc".to_string(), "cancel".to_string()));
+                Some(("@  replay macro".to_string(), keys))
+            }
+        }
+    }
+
+    /// Add a key to the macro being recorded
+    pub fn record(&mut self, key: KeyEvent) {
+        if let Some((_, keys)) = &mut self.recording {
+            keys.push(key);
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> MacroAction {
+        if let Some(pending) = self.pending.take() {
+            return self.with_register(pending, key.code);
+        }
+
+        match key.code {
+            KeyCode::Char('Q') => match self.recording.take() {
+                Some((register, keys)) => {
+                    let status = format!("Recorded macro @{} ({} keys)", register, keys.len());
+                    self.registers.insert(register, keys);
+                    MacroAction::Status(status)
+                }
+                None => {
+                    self.pending = Some(Pending::Record);
+                    MacroAction::Status("Record macro into register (a-z)".to_string())
+                }
+            },
+            KeyCode::Char('@') => {
+                self.pending = Some(Pending::Replay);
+                MacroAction::Status("Replay macro from register (a-z, @ for last)".to_string())
+            }
+            _ => {
+                self.record(key);
+                MacroAction::Pass
+            }
+        }
+    }
+
+    fn with_register(&mut self, pending: Pending, code: KeyCode) -> MacroAction {
+        let register = match (code, &pending) {
+            (KeyCode::Char(c), _) if c.is_ascii_lowercase() => c,
+            (KeyCode::Char('@'), Pending::Replay) => match self.last_replayed {
+                Some(register) => register,
+                None => return MacroAction::Status("No macro replayed yet".to_string()),
+            },
+            _ => return MacroAction::Status("Macro cancelled".to_string()),
+        };
+
+        match pending {
+            Pending::Record => {
+                self.recording = Some((register, Vec::new()));
+                MacroAction::Status(format!("Recording macro @{}", register))
+            }
+            Pending::Replay => match self.registers.get(&register) {
+                Some(keys) => {
+                    self.last_replayed = Some(register);
+                    MacroAction::Replay(register, keys.clone())
+                }
+                None => MacroAction::Status(format!("Macro @{} is empty", register)),
+            },
+        }
+    }
+}
@@ -0,0 +1,679 @@
+//! Panel registry: every panel the main view can show, with its size,
+//! focus target, key hints and the hardware it needs
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    widgets::Widget,
+};
+use serde::{Deserialize, Serialize};
+
+use super::hints::Hint;
+use super::{App, EditMode, FocusedPanel, TDP_PRESETS};
+use crate::daemon::{AnimeBinding, Fan, FanPoint, PowerProfile};
+use crate::sysfs::Peripheral;
+use crate::telemetry::LimitPreview;
+use crate::ui::{
+    format_timeout, profile_details, AdvancedPanel, AnimePanel, BatteryKatana, FanCurveGraph,
+    LightingPanel, PowerProfileSelector, PresetsPanel, SlashPanel, TdpPresets, TelemetryPanel,
+};
+
+/// Panel identifiers, as used in the `[layout]` config section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PanelId {
+    Power,
+    Battery,
+    Lighting,
+    Advanced,
+    FanCurve,
+    /// AniMe Matrix or Slash, whichever the laptop has
+    Lid,
+    Telemetry,
+    Tdp,
+    Presets,
+}
+
+/// Hardware a panel needs before it's worth showing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// Always shown
+    None,
+    /// AniMe Matrix or Slash lid lighting
+    LidLighting,
+}
+
+impl Capability {
+    pub fn available(self, app: &App) -> bool {
+        match self {
+            Self::None => true,
+            Self::LidLighting => app.state.anime.is_some() || app.state.slash.is_some(),
+        }
+    }
+}
+
+pub struct PanelSpec {
+    pub id: PanelId,
+    pub title: &'static str,
+    /// Height within its column
+    pub constraint: Constraint,
+    pub requires: Capability,
+    /// Focus target for keyboard input, `None` for display-only panels
+    pub focus: Option<FocusedPanel>,
+    /// Status bar key hints while focused
+    pub hints: &'static [Hint],
+    /// Keys listed by `?` while focused, with what they do
+    pub help: &'static [Hint],
+    pub render: fn(&App, Rect, &mut Buffer),
+    /// The same state as plain text lines, for `--plain`
+    pub describe: fn(&App) -> Vec<String>,
+}
+
+pub const REGISTRY: [PanelSpec; 9] = [
+    PanelSpec {
+        id: PanelId::Power,
+        title: "Power profile",
+        constraint: Constraint::Length(10),
+        requires: Capability::None,
+        focus: Some(FocusedPanel::PowerProfile),
+        hints: &[("j/k", "select"), ("enter", "apply")],
+        help: &[
+            ("j / k", "Select a profile"),
+            ("3j, 2k", "Move by a count (a lone digit still jumps to its panel)"),
+            ("Enter / Space", "Apply the selected profile"),
+            ("Click", "Apply the clicked profile"),
+            ("Scroll", "Move the selection"),
+        ],
+        render: render_power,
+        describe: describe_power,
+    },
+    PanelSpec {
+        id: PanelId::Battery,
+        title: "Battery",
+        constraint: Constraint::Length(7),
+        requires: Capability::None,
+        focus: Some(FocusedPanel::Battery),
+        hints: &[("enter", "edit limit"), ("b", "bypass")],
+        help: &[
+            ("Enter", "Edit the charge limit"),
+            ("← / →", "Adjust while editing (hold: 5 → 10 → 25)"),
+            ("20l, 3h", "Step by a count while editing"),
+            ("Enter / Esc", "Apply / cancel the edit"),
+            ("b", "Toggle bypass charging"),
+            ("Scroll", "Adjust the charge limit"),
+        ],
+        render: render_battery,
+        describe: describe_battery,
+    },
+    PanelSpec {
+        id: PanelId::Lighting,
+        title: "Lighting",
+        constraint: Constraint::Length(5),
+        requires: Capability::None,
+        focus: Some(FocusedPanel::Lighting),
+        hints: &[
+            ("enter", "idle timeout"),
+            ("p", "color"),
+            ("t", "temp color"),
+            ("+/-", "display"),
+        ],
+        help: &[
+            ("Enter", "Edit the keyboard idle timeout"),
+            ("← / →", "Adjust while editing"),
+            ("Enter / Esc", "Apply / cancel the edit"),
+            ("p", "Pick a static keyboard color"),
+            ("j / k", "Picker row: hue, saturation, value, hex, presets"),
+            ("3j, 20l", "Move or adjust by a count in the picker (not on the hex row)"),
+            ("t", "Color follows the CPU temperature, on / off"),
+            ("+ / -", "Display brightness"),
+        ],
+        render: render_lighting,
+        describe: describe_lighting,
+    },
+    PanelSpec {
+        id: PanelId::Advanced,
+        title: "Advanced",
+        constraint: Constraint::Min(5),
+        requires: Capability::None,
+        focus: Some(FocusedPanel::Advanced),
+        hints: &[("j/k", "select"), ("enter", "toggle")],
+        help: &[
+            ("j / k", "Select a switch"),
+            ("3j, 2k", "Move by a count (a lone digit still jumps to its panel)"),
+            ("Enter / Space", "Toggle Fn-lock or the touchpad"),
+        ],
+        render: render_advanced,
+        describe: describe_advanced,
+    },
+    PanelSpec {
+        id: PanelId::FanCurve,
+        title: "Fan curve",
+        constraint: Constraint::Min(10),
+        requires: Capability::None,
+        focus: Some(FocusedPanel::FanCurve),
+        hints: &[
+            ("enter", "edit curve"),
+            ("space", "on/off"),
+            ("m", "per fan"),
+            ("C", "copy"),
+            ("+/-", "hysteresis"),
+            ("t", "temp source"),
+            ("e", "export"),
+        ],
+        help: &[
+            ("Enter", "Edit the curve points"),
+            ("Space", "Custom curve on / off for the active profile"),
+            ("m", "Per fan and profile switches: h/l profile, j/k fan, Space flips"),
+            ("C", "Copy the curve to the GPU fan or another profile: h/l where, j/k speeds"),
+            ("h / l", "Previous / next point while editing"),
+            ("j / k", "Point speed while editing"),
+            ("20k, 3j", "Step by a count while editing"),
+            ("Enter / Esc", "Apply / cancel the edit"),
+            ("+ / -", "Hysteresis"),
+            ("t", "Temperature source (CPU, GPU, skin)"),
+            ("e", "Export as SVG"),
+            ("F", "Full blast for 5 minutes"),
+            ("T", "Stress run to compare curves"),
+        ],
+        render: render_fan_curve,
+        describe: describe_fan_curve,
+    },
+    PanelSpec {
+        id: PanelId::Lid,
+        title: "Lid lighting",
+        constraint: Constraint::Length(6),
+        requires: Capability::LidLighting,
+        focus: Some(FocusedPanel::Lid),
+        hints: &[("j/k", "select"), ("enter", "toggle"), ("h/l", "adjust")],
+        help: &[
+            ("j / k", "Select a row"),
+            ("3j, 2k", "Move by a count (a lone digit still jumps to its panel)"),
+            ("Enter / Space", "Toggle the selected row"),
+            ("h / l", "Slash pattern, brightness or interval"),
+        ],
+        render: render_lid,
+        describe: describe_lid,
+    },
+    PanelSpec {
+        id: PanelId::Telemetry,
+        title: "Telemetry",
+        constraint: Constraint::Length(8),
+        requires: Capability::None,
+        focus: None,
+        hints: &[],
+        help: &[],
+        render: render_telemetry,
+        describe: describe_telemetry,
+    },
+    PanelSpec {
+        id: PanelId::Tdp,
+        title: "TDP presets",
+        constraint: Constraint::Length(5),
+        requires: Capability::None,
+        focus: Some(FocusedPanel::Tdp),
+        hints: &[("h/l", "select"), ("enter", "apply")],
+        help: &[
+            ("h / l", "Select a power limit"),
+            ("Enter / Space", "Apply it"),
+        ],
+        render: render_tdp,
+        describe: describe_tdp,
+    },
+    PanelSpec {
+        id: PanelId::Presets,
+        title: "Presets",
+        constraint: Constraint::Length(6),
+        requires: Capability::None,
+        focus: Some(FocusedPanel::Presets),
+        hints: &[("enter", "apply"), ("n", "new"), ("m", "rename"), ("d", "delete")],
+        help: &[
+            ("j / k", "Select a preset"),
+            ("3j, 2k", "Move by a count (a lone digit still jumps to its panel)"),
+            ("Enter / Space", "Apply profile, charge limit, fan curve and lighting"),
+            ("n", "Save the current settings as a new preset"),
+            ("m", "Rename the selected preset"),
+            ("d", "Delete the selected preset"),
+        ],
+        render: render_presets,
+        describe: describe_presets,
+    },
+];
+
+/// Default two-column desktop layout
+pub const DEFAULT_LEFT: [PanelId; 4] = [
+    PanelId::Power,
+    PanelId::Battery,
+    PanelId::Lighting,
+    PanelId::Advanced,
+];
+pub const DEFAULT_RIGHT: [PanelId; 4] = [
+    PanelId::FanCurve,
+    PanelId::Presets,
+    PanelId::Lid,
+    PanelId::Telemetry,
+];
+
+/// Default single-column handheld layout
+pub const DEFAULT_HANDHELD: [PanelId; 4] = [
+    PanelId::Power,
+    PanelId::Tdp,
+    PanelId::Battery,
+    PanelId::Telemetry,
+];
+
+/// Built-in layouts switchable with F1-F3, each offering a desktop and a
+/// handheld arrangement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LayoutPreset {
+    /// Telemetry and fan curve up front, few controls
+    Monitoring,
+    /// Every control, no telemetry
+    Control,
+    /// Power profile and battery only
+    Minimal,
+}
+
+impl LayoutPreset {
+    pub const ALL: [LayoutPreset; 3] = [Self::Monitoring, Self::Control, Self::Minimal];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Monitoring => "monitoring",
+            Self::Control => "control",
+            Self::Minimal => "minimal",
+        }
+    }
+
+    /// Panel columns, left to right
+    pub fn columns(self, handheld: bool) -> Vec<&'static [PanelId]> {
+        use PanelId::*;
+
+        match (self, handheld) {
+            (Self::Monitoring, false) => vec![&[Power, Battery], &[Telemetry, FanCurve]],
+            (Self::Monitoring, true) => vec![&[Power, Battery, Telemetry]],
+            (Self::Control, false) => {
+                vec![&[Power, Battery, Lighting, Advanced], &[FanCurve, Presets, Lid]]
+            }
+            (Self::Control, true) => vec![&[Power, Tdp, Battery]],
+            (Self::Minimal, false) => vec![&[Power, Battery]],
+            (Self::Minimal, true) => vec![&[Power, Tdp]],
+        }
+    }
+}
+
+pub fn spec(id: PanelId) -> &'static PanelSpec {
+    REGISTRY
+        .iter()
+        .find(|spec| spec.id == id)
+        .expect("every PanelId is registered")
+}
+
+fn render_power(app: &App, area: Rect, buf: &mut Buffer) {
+    PowerProfileSelector::new(app.state.power_profile)
+        .limits(PowerProfile::ALL.map(|profile| app.profile_limits.get(profile)))
+        .fans(PowerProfile::ALL.map(|profile| app.profile_fans.get(profile)))
+        .firmware(app.firmware_profiles)
+        .selected(app.selected_profile)
+        .focused(app.focused == FocusedPanel::PowerProfile)
+        .render(area, buf);
+}
+
+fn render_battery(app: &App, area: Rect, buf: &mut Buffer) {
+    BatteryKatana::new(app.state.charge_limit)
+        .bypass(app.state.charge_bypass)
+        .charging(app.telemetry.battery.as_ref().is_some_and(|b| b.is_charging()))
+        .estimate(app.telemetry.battery.as_ref().and_then(|battery| {
+            app.telemetry.battery_rate.estimate(battery, app.state.charge_limit)
+        }))
+        .preview((app.edit_mode == EditMode::Battery).then(|| {
+            LimitPreview::new(
+                app.state.charge_limit,
+                app.telemetry.battery.as_ref(),
+                &app.telemetry.battery_rate,
+            )
+        }))
+        .focused(app.focused == FocusedPanel::Battery)
+        .editing(app.edit_mode == EditMode::Battery)
+        .render(area, buf);
+}
+
+fn render_lighting(app: &App, area: Rect, buf: &mut Buffer) {
+    LightingPanel::new(app.state.aura.as_ref())
+        .backlight(app.state.backlight.as_ref())
+        .focused(app.focused == FocusedPanel::Lighting)
+        .editing(app.edit_mode == EditMode::KbdIdleTimeout)
+        .reactive(app.reactive_lighting)
+        .render(area, buf);
+}
+
+fn render_advanced(app: &App, area: Rect, buf: &mut Buffer) {
+    let peripherals = [
+        (Peripheral::FnLock.as_str(), app.state.fn_lock),
+        (Peripheral::Touchpad.as_str(), app.state.touchpad),
+    ];
+    AdvancedPanel::new(&peripherals)
+        .selected(app.selected_peripheral)
+        .focused(app.focused == FocusedPanel::Advanced)
+        .render(area, buf);
+}
+
+fn render_fan_curve(app: &App, area: Rect, buf: &mut Buffer) {
+    let selected_point = match app.edit_mode {
+        EditMode::FanCurve { point_index } => Some(point_index),
+        _ => None,
+    };
+
+    let switch = match app.edit_mode {
+        EditMode::FanEnablement { profile, fan } => Some((profile, fan)),
+        _ => None,
+    };
+
+    let copying = match app.edit_mode {
+        EditMode::CurveCopy { target, offset } => {
+            Some(target.describe(app.state.power_profile, offset))
+        }
+        _ => None,
+    };
+
+    FanCurveGraph::new(&app.state.fan_curve)
+        .selected_point(selected_point)
+        .selected_switch(switch)
+        .copying(copying)
+        .focused(app.focused == FocusedPanel::FanCurve)
+        .editing(matches!(app.edit_mode, EditMode::FanCurve { .. }))
+        .unit(app.config.ui.temperature_unit)
+        .source(app.config.ui.fan_temp_source)
+        .hysteresis(app.config.ui.fan_hysteresis)
+        .target(app.telemetry.fan_temp(app.config.ui.fan_temp_source).zip(app.fan_target))
+        .alert(app.fan_stall || app.full_blast.is_some())
+        .render(area, buf);
+}
+
+/// Lid lighting: Slash on 2024+ models, AniMe Matrix otherwise
+fn render_lid(app: &App, area: Rect, buf: &mut Buffer) {
+    if let Some(slash) = &app.state.slash {
+        SlashPanel::new(slash)
+            .selected(app.selected_lid_row)
+            .focused(app.focused == FocusedPanel::Lid)
+            .render(area, buf);
+    } else {
+        let anime_bindings =
+            app.state.anime.as_ref().map(|anime| {
+                AnimeBinding::ALL.map(|binding| (binding.as_str(), anime.shown(binding)))
+            });
+        AnimePanel::new(anime_bindings.as_ref().map(|rows| rows.as_slice()))
+            .selected(app.selected_lid_row)
+            .focused(app.focused == FocusedPanel::Lid)
+            .render(area, buf);
+    }
+}
+
+fn render_telemetry(app: &App, area: Rect, buf: &mut Buffer) {
+    TelemetryPanel::new(&app.telemetry)
+        .hidden_sensors(&app.config.telemetry.hidden_sensors)
+        .unit(app.config.ui.temperature_unit)
+        .render(area, buf);
+}
+
+fn render_tdp(app: &App, area: Rect, buf: &mut Buffer) {
+    TdpPresets::new(&TDP_PRESETS, app.state.tdp)
+        .range(app.state.tdp_range)
+        .selected(app.selected_tdp)
+        .focused(app.focused == FocusedPanel::Tdp)
+        .render(area, buf);
+}
+
+fn render_presets(app: &App, area: Rect, buf: &mut Buffer) {
+    let names: Vec<&str> = app.presets.iter().map(|preset| preset.name.as_str()).collect();
+    let naming = matches!(app.edit_mode, EditMode::PresetName { .. });
+    let input = naming.then_some(app.preset_input.as_str());
+    PresetsPanel::new(&names)
+        .selected(app.selected_preset)
+        .focused(app.focused == FocusedPanel::Presets)
+        .input(input)
+        .render(area, buf);
+}
+
+fn on_off(value: Option<bool>) -> &'static str {
+    match value {
+        Some(true) => "on",
+        Some(false) => "off",
+        None => "unknown",
+    }
+}
+
+fn describe_power(app: &App) -> Vec<String> {
+    let selected = PowerProfile::ALL[app.selected_profile.min(PowerProfile::ALL.len() - 1)];
+    let mut line = format!("{} active", app.state.power_profile);
+    if selected != app.state.power_profile {
+        line.push_str(&format!(", {} selected", selected));
+    }
+    let mut lines = vec![line];
+    lines.extend(PowerProfile::ALL.iter().enumerate().filter_map(|(i, &profile)| {
+        let details = profile_details(
+            profile,
+            app.firmware_profiles[i],
+            app.profile_limits.get(profile),
+            app.profile_fans.get(profile),
+        )?;
+        Some(format!("{}: {}", profile, details))
+    }));
+    lines
+}
+
+fn describe_battery(app: &App) -> Vec<String> {
+    let mut line = match &app.telemetry.battery {
+        Some(battery) => format!("{}% {}", battery.capacity, battery.status.to_lowercase()),
+        None => "no battery found".to_string(),
+    };
+    line.push_str(&format!(", charge limit {}%", app.state.charge_limit));
+    if let Some(bypass) = app.state.charge_bypass {
+        line.push_str(&format!(", bypass {}", on_off(Some(bypass))));
+    }
+    let estimate = app.telemetry.battery.as_ref().and_then(|battery| {
+        app.telemetry.battery_rate.estimate(battery, app.state.charge_limit)
+    });
+    if let Some(estimate) = estimate {
+        let until = if estimate.to_full { "to full" } else { "to empty" };
+        line.push_str(&format!(", {} {}", estimate.label(), until));
+    }
+
+    let mut lines = vec![line];
+    if app.edit_mode == EditMode::Battery {
+        let preview = LimitPreview::new(
+            app.state.charge_limit,
+            app.telemetry.battery.as_ref(),
+            &app.telemetry.battery_rate,
+        );
+        lines.push(format!("Editing the limit: {}", preview.label()));
+    }
+    lines
+}
+
+fn describe_lighting(app: &App) -> Vec<String> {
+    let mut lines = Vec::new();
+    match &app.state.aura {
+        Some(aura) => {
+            let mut line = format!("keyboard brightness {}", aura.brightness.unwrap_or_default());
+            if let Some(color) = aura.color {
+                line.push_str(&format!(", color {}", color));
+            }
+            if app.reactive_lighting {
+                line.push_str(" following CPU temperature");
+            }
+            if let Some(seconds) = aura.idle_timeout {
+                line.push_str(&format!(", idle off {}", format_timeout(seconds)));
+            }
+            lines.push(line);
+        }
+        None => lines.push("no Aura keyboard".to_string()),
+    }
+    if let Some(backlight) = &app.state.backlight {
+        lines.push(format!("display brightness {}%", backlight.percent()));
+    }
+    lines
+}
+
+fn describe_advanced(app: &App) -> Vec<String> {
+    let line = [Peripheral::FnLock, Peripheral::Touchpad]
+        .iter()
+        .enumerate()
+        .map(|(i, &peripheral)| {
+            let state = on_off(app.state.peripheral(peripheral));
+            let selected = if i == app.selected_peripheral { " (selected)" } else { "" };
+            format!("{} {}{}", peripheral.as_str(), state, selected)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    vec![line]
+}
+
+fn describe_fan_curve(app: &App) -> Vec<String> {
+    let unit = app.config.ui.temperature_unit;
+    let curve = &app.state.fan_curve;
+    let points = |points: &[FanPoint]| {
+        if points.is_empty() {
+            return "no points".to_string();
+        }
+        points
+            .iter()
+            .map(|point| format!("{} {}%", unit.format(point.temp as f32), point.speed))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut state = if curve.enabled { "custom curve" } else { "firmware default" }.to_string();
+    if let Some(target) = app.fan_target {
+        state.push_str(&format!(", target {}%", target));
+    }
+    let steps = app.config.fan;
+    if !steps.takes_any() {
+        let temp = unit.format_delta(steps.temp_step as f32);
+        state.push_str(&format!(", in steps of {}% and {}", steps.speed_step, temp));
+    }
+    // `CPU curve in Balanced, Performance` for each fan
+    let profiles = |fan: Fan| {
+        let on: Vec<_> = PowerProfile::ALL
+            .into_iter()
+            .filter(|&profile| curve.enablement.get(profile, fan))
+            .map(|profile| profile.as_str())
+            .collect();
+        if on.is_empty() {
+            "off".to_string()
+        } else {
+            on.join(", ")
+        }
+    };
+    let mut lines = vec![
+        state,
+        format!("CPU: {}", points(&curve.cpu_curve)),
+        format!("GPU: {}", points(&curve.gpu_curve)),
+        format!("Custom curve in: CPU {}; GPU {}", profiles(Fan::Cpu), profiles(Fan::Gpu)),
+    ];
+    if let EditMode::CurveCopy { target, offset } = app.edit_mode {
+        lines.push(format!("Copying {}", target.describe(app.state.power_profile, offset)));
+    }
+    if let EditMode::FanEnablement { profile, fan } = app.edit_mode {
+        let on = on_off(Some(curve.enablement.get(profile, fan)));
+        lines.push(format!("Switching {} in {}: {}", fan.as_str(), profile.as_str(), on));
+    }
+    if let EditMode::FanCurve { point_index } = app.edit_mode {
+        if let Some(point) = curve.cpu_curve.get(point_index) {
+            lines.push(format!(
+                "Editing point {}: {} {}%",
+                point_index + 1,
+                unit.format(point.temp as f32),
+                point.speed
+            ));
+        }
+    }
+    lines
+}
+
+fn describe_lid(app: &App) -> Vec<String> {
+    if let Some(slash) = &app.state.slash {
+        let mut line = format!("Slash {}", on_off(slash.enabled));
+        if let Some(mode) = &slash.mode {
+            line.push_str(&format!(", pattern {}", mode));
+        }
+        if let Some(brightness) = slash.brightness {
+            line.push_str(&format!(", brightness {}", brightness));
+        }
+        if let Some(interval) = slash.interval {
+            line.push_str(&format!(", interval {}", interval));
+        }
+        return vec![line];
+    }
+    let Some(anime) = &app.state.anime else {
+        return vec!["no lid display".to_string()];
+    };
+    let line = AnimeBinding::ALL
+        .iter()
+        .map(|&binding| format!("{} {}", binding.as_str(), on_off(anime.shown(binding))))
+        .collect::<Vec<_>>()
+        .join(", ");
+    vec![format!("AniMe display: {}", line)]
+}
+
+fn describe_telemetry(app: &App) -> Vec<String> {
+    let unit = app.config.ui.temperature_unit;
+    let telemetry = &app.telemetry;
+    let mut line = format!("CPU load {:.0}%", telemetry.average_usage() * 100.0);
+    if let Some(celsius) = telemetry.cpu_temp() {
+        line.push_str(&format!(", CPU {}", unit.format(celsius)));
+    }
+    if let Some(celsius) = telemetry.gpu_temp() {
+        line.push_str(&format!(", GPU {}", unit.format(celsius)));
+    }
+    let memory = &telemetry.memory;
+    if let Some(percent) = (memory.used_kb() * 100).checked_div(memory.total_kb) {
+        line.push_str(&format!(", memory {}%", percent));
+    }
+
+    let mut lines = vec![line];
+    if !telemetry.fans.is_empty() {
+        let fans = telemetry
+            .fans
+            .iter()
+            .map(|fan| format!("{} {} RPM", fan.label, fan.rpm))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(format!("Fans: {}", fans));
+    }
+    lines.push(match telemetry.throttle.episodes {
+        0 => "No thermal throttling seen".to_string(),
+        n if telemetry.throttle.throttling() => format!("Thermal throttling now, {} episodes", n),
+        n => format!("Thermal throttling episodes: {}", n),
+    });
+    lines
+}
+
+fn describe_tdp(app: &App) -> Vec<String> {
+    let current = match app.state.tdp {
+        Some(watts) => format!("{} W", watts),
+        None => "unknown".to_string(),
+    };
+    let selected = TDP_PRESETS[app.selected_tdp.min(TDP_PRESETS.len() - 1)];
+    vec![format!("TDP {}, {} W preset selected", current, selected)]
+}
+
+fn describe_presets(app: &App) -> Vec<String> {
+    if let EditMode::PresetName { .. } = app.edit_mode {
+        return vec![format!("Name: {}", app.preset_input)];
+    }
+    if app.presets.is_empty() {
+        return vec!["none saved".to_string()];
+    }
+    let line = app
+        .presets
+        .iter()
+        .enumerate()
+        .map(|(i, preset)| {
+            let selected = if i == app.selected_preset { " (selected)" } else { "" };
+            format!("{}{}", preset.name, selected)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    vec![line]
+}
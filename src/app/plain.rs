@@ -0,0 +1,163 @@
+//! `--plain` screen reader mode: the panels as plain text lines without
+//! borders, art or color, followed by the keys and the latest status lines,
+//! so every change also shows up as a line of text
+
+use super::hints;
+use super::panels;
+use super::{App, EditMode};
+use crate::health::CheckStatus;
+use crate::stats::format_duration;
+
+/// Status lines listed below the panels
+const RECENT: usize = 5;
+
+/// Console output lines listed above its prompt
+const CONSOLE_LINES: usize = 20;
+
+/// Everything to show, top to bottom
+pub fn lines(app: &App) -> Vec<String> {
+    let mut lines = popup(app).unwrap_or_else(|| main_view(app));
+
+    let keys: Vec<String> = hints::hints(app)
+        .iter()
+        .map(|(key, action)| format!("{} {}", key, action))
+        .collect();
+    lines.push(String::new());
+    lines.push(format!("Keys: {}", keys.join(", ")));
+
+    let recent: Vec<_> = app.history.messages().collect();
+    let start = recent.len().saturating_sub(RECENT);
+    lines.extend(recent[start..].iter().map(|message| message.line()));
+    lines
+}
+
+/// The connection and every panel of the layout, the focused one marked `>`
+fn main_view(app: &App) -> Vec<String> {
+    let mut connection = match (app.state.connected, app.state.disconnect_reason) {
+        (true, _) => match app.state.asusd_version {
+            Some(version) => format!("hachi: connected to asusd {}", version),
+            None => "hachi: connected to asusd".to_string(),
+        },
+        (false, Some(reason)) => format!("hachi: disconnected, {}", reason),
+        (false, None) => "hachi: connecting".to_string(),
+    };
+    if app.read_only {
+        connection.push_str(", read-only");
+    }
+
+    let mut lines = vec![connection, clock(app)];
+    for &id in app.layout_columns().iter().flatten() {
+        let spec = panels::spec(id);
+        let marker = if spec.focus == Some(app.focused) { ">" } else { " " };
+        let mut described = (spec.describe)(app).into_iter();
+        let first = described.next().unwrap_or_default();
+        lines.push(format!("{} {}: {}", marker, spec.title, first));
+        lines.extend(described.map(|line| format!("    {}", line)));
+    }
+    lines
+}
+
+/// `14:32, up 3h 12m, on battery 45m 10s`
+fn clock(app: &App) -> String {
+    let mut line = app.clock();
+    if let Some(uptime) = app.telemetry.uptime {
+        line.push_str(&format!(", up {}", format_duration(uptime)));
+    }
+    if let Some(on_battery) = app.on_battery {
+        line.push_str(if on_battery { ", on battery" } else { ", on AC" });
+        if let Some(since) = app.power_since {
+            line.push_str(&format!(" {}", format_duration(since.elapsed())));
+        }
+    }
+    line
+}
+
+/// What a popup would show, as lines in place of the panels
+fn popup(app: &App) -> Option<Vec<String>> {
+    if let Some(health) = &app.health {
+        if health.finished.is_none() {
+            return Some(vec!["Health check running".to_string()]);
+        }
+        let mut lines = vec!["Health check".to_string()];
+        lines.extend(health.checks.iter().map(|check| {
+            let status = match check.status {
+                CheckStatus::Pass => "pass",
+                CheckStatus::Warn => "warning",
+                CheckStatus::Fail => "failed",
+            };
+            format!("  {}: {}, {}", check.name, status, check.detail)
+        }));
+        return Some(lines);
+    }
+
+    if let Some(view) = &app.messages {
+        let mut lines = vec![format!(
+            "Messages, newest first, {} and up, search: {}",
+            view.least.as_str(),
+            view.query
+        )];
+        let found = view.found(&app.history);
+        lines.extend(found.iter().map(|message| format!("  {}", message.line())));
+        return Some(lines);
+    }
+
+    if let Some(console) = &app.console {
+        let mut lines = vec!["asusd console".to_string()];
+        let start = console.output.len().saturating_sub(CONSOLE_LINES);
+        lines.extend(console.output[start..].iter().cloned());
+        lines.push(format!("> {}", console.input));
+        return Some(lines);
+    }
+
+    if app.show_tune {
+        let status = match &app.tuning.run {
+            Some(run) if run.loading() => format!("loading {}s", run.elapsed().as_secs()),
+            Some(run) => format!("cooling {}s", run.elapsed().as_secs()),
+            None => "idle".to_string(),
+        };
+        let mut lines = vec![format!("Tune, {}, load: {}", status, app.config.tune.command)];
+        let (before, after) = app.tuning.traces();
+        if before.is_none() && after.is_none() {
+            lines.push("  No runs yet: Enter starts the load command".to_string());
+        }
+        let unit = app.config.ui.temperature_unit;
+        for (name, trace) in [("before", before), ("after", after)] {
+            if let Some(trace) = trace {
+                lines.push(format!("  {}: {}", name, trace.summary(unit).join(", ")));
+            }
+        }
+        return Some(lines);
+    }
+
+    if app.show_help {
+        // One panel's keys, or every shown panel's
+        let ids = match app.help_panel {
+            Some(id) => vec![id],
+            None => app.layout_columns().concat(),
+        };
+        let mut lines = vec!["Help".to_string()];
+        for spec in ids.into_iter().map(panels::spec) {
+            lines.push(format!("{}:", spec.title));
+            lines.extend(spec.help.iter().map(|(key, text)| format!("  {}: {}", key, text)));
+        }
+        return Some(lines);
+    }
+
+    if app.edit_mode == EditMode::KbdColor {
+        let picker = &app.color_picker;
+        let hsv = picker.hsv;
+        let mut line = format!(
+            "Keyboard color {}: hue {}, saturation {}%, value {}%, editing {}",
+            picker.color,
+            hsv.hue,
+            hsv.saturation,
+            hsv.value,
+            picker.row.name()
+        );
+        if !picker.hex_input.is_empty() {
+            line.push_str(&format!(", typed #{}", picker.hex_input));
+        }
+        return Some(vec![line]);
+    }
+    None
+}
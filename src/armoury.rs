@@ -0,0 +1,82 @@
+//! asus-armoury firmware attributes under `/sys/class/firmware-attributes`
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::{HachiError, Result};
+use crate::sysfs::{self, read_trimmed};
+
+const ATTRIBUTES_DIR: &str = "/sys/class/firmware-attributes/asus-armoury/attributes";
+
+/// One firmware attribute with its allowed values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: String,
+    pub current: String,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    /// Allowed values for enumeration attributes
+    pub possible: Vec<String>,
+}
+
+impl Attribute {
+    /// Whether `value` is within the attribute's range or choices
+    pub fn accepts(&self, value: &str) -> bool {
+        if !self.possible.is_empty() {
+            return self.possible.iter().any(|p| p == value);
+        }
+        match value.parse::<i64>() {
+            Ok(n) => self.clamp(n) == n,
+            Err(_) => self.min.is_none() && self.max.is_none(),
+        }
+    }
+
+    /// Clamp a numeric value into the attribute's range
+    pub fn clamp(&self, value: i64) -> i64 {
+        let value = self.min.map_or(value, |min| value.max(min));
+        self.max.map_or(value, |max| value.min(max))
+    }
+}
+
+/// Whether the asus-armoury driver is loaded
+pub fn available() -> bool {
+    Path::new(ATTRIBUTES_DIR).is_dir()
+}
+
+/// Read one attribute, or `None` if this model doesn't have it
+pub fn attribute(name: &str) -> Option<Attribute> {
+    let dir = Path::new(ATTRIBUTES_DIR).join(name);
+    let number = |file: &str| read_trimmed(&dir.join(file)).and_then(|v| v.parse().ok());
+    Some(Attribute {
+        name: name.to_string(),
+        current: read_trimmed(&dir.join("current_value"))?,
+        min: number("min_value"),
+        max: number("max_value"),
+        possible: read_trimmed(&dir.join("possible_values"))
+            .map(|v| v.split(';').filter(|p| !p.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default(),
+    })
+}
+
+/// Every attribute exposed by the driver, sorted by name
+pub fn attributes() -> Vec<Attribute> {
+    let Ok(entries) = fs::read_dir(ATTRIBUTES_DIR) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names.iter().filter_map(|name| attribute(name)).collect()
+}
+
+/// Write an attribute's `current_value` after validating it
+pub fn write(name: &str, value: &str) -> Result<()> {
+    let attribute = attribute(name)
+        .ok_or_else(|| HachiError::Unsupported(format!("firmware attribute {}", name)))?;
+    if !attribute.accepts(value) {
+        return Err(HachiError::InvalidAttribute(format!("{} = {}", name, value)));
+    }
+    sysfs::write(&Path::new(ATTRIBUTES_DIR).join(name).join("current_value"), value)
+}
@@ -0,0 +1,196 @@
+//! Process-triggered profiles: while a configured program runs, switch to
+//! its power profile (and a preset's fan curve), reverting once it exits.
+//! Event rules do the same for lid, dock and power source changes, and idle
+//! quiet mode drops to Quiet while logind reports the user idle. Quiet
+//! hours cap the fans during a nightly time window. Profile rules run a
+//! command (e.g. to change the refresh rate) on switching to a profile.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::config::{ClockTime, EventRule, ProcessRule, QuietHours};
+use crate::daemon::{FanCurve, PowerProfile};
+use crate::error::{HachiError, Result};
+
+/// How often the process list is scanned
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Names of every running process: the kernel's `comm` (truncated to 15
+/// bytes) and the file name of `argv[0]`, which keeps the full name
+pub fn running_processes() -> Vec<String> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let is_pid = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.bytes().all(|b| b.is_ascii_digit()));
+        if !is_pid {
+            continue;
+        }
+
+        if let Ok(comm) = fs::read_to_string(dir.join("comm")) {
+            names.push(comm.trim_end().to_string());
+        }
+        if let Ok(cmdline) = fs::read(dir.join("cmdline")) {
+            let argv0 = cmdline.split(|&b| b == 0).next().unwrap_or_default();
+            let argv0 = String::from_utf8_lossy(argv0);
+            if let Some(name) = Path::new(argv0.as_ref()).file_name() {
+                names.push(name.to_string_lossy().into_owned());
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Match a process name against a rule pattern: exact, or a prefix when
+/// the pattern ends in `*` (e.g. `steam_app_*`)
+pub fn matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    }
+}
+
+/// First rule with a running process, if any
+pub fn active_rule(rules: &[ProcessRule], running: &[String]) -> Option<usize> {
+    rules.iter().position(|rule| {
+        rule.processes
+            .iter()
+            .any(|pattern| running.iter().any(|name| matches(pattern, name)))
+    })
+}
+
+/// Lid, dock and power source state that event rules are keyed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Environment {
+    pub lid_closed: bool,
+    pub docked: bool,
+    pub external_power: bool,
+}
+
+impl Default for Environment {
+    /// Lid open and undocked on AC, until logind and the battery say otherwise
+    fn default() -> Self {
+        Self {
+            lid_closed: false,
+            docked: false,
+            external_power: true,
+        }
+    }
+}
+
+/// First event rule whose conditions all hold, if any
+pub fn active_event(rules: &[EventRule], env: &Environment) -> Option<usize> {
+    let holds = |condition: Option<bool>, actual: bool| condition.is_none_or(|c| c == actual);
+    rules.iter().position(|rule| {
+        holds(rule.lid_closed, env.lid_closed)
+            && holds(rule.docked, env.docked)
+            && holds(rule.external_power, env.external_power)
+    })
+}
+
+/// A rule's conditions in words, e.g. `docked, on AC`
+pub fn describe(rule: &EventRule) -> String {
+    let conditions: Vec<&str> = [
+        rule.lid_closed.map(|c| if c { "lid closed" } else { "lid open" }),
+        rule.docked.map(|d| if d { "docked" } else { "undocked" }),
+        rule.external_power.map(|p| if p { "on AC" } else { "on battery" }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if conditions.is_empty() {
+        "always".to_string()
+    } else {
+        conditions.join(", ")
+    }
+}
+
+/// How long the user has been idle, given logind's `IdleSinceHint`
+/// (microseconds since the epoch). Clock skew into the future counts as zero.
+pub fn idle_duration(since_micros: u64, now: SystemTime) -> Duration {
+    now.duration_since(UNIX_EPOCH + Duration::from_micros(since_micros))
+        .unwrap_or_default()
+}
+
+/// Whether `now` falls in the window from `start` up to `end`, which wraps
+/// past midnight when it ends earlier in the day than it starts
+pub fn in_window(start: ClockTime, end: ClockTime, now: ClockTime) -> bool {
+    if start <= end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+/// First quiet hours window covering `now`, if any
+pub fn active_quiet_hours(rules: &[QuietHours], now: ClockTime) -> Option<usize> {
+    rules.iter().position(|rule| in_window(rule.start, rule.end, now))
+}
+
+/// Broken-down local time, or `None` when the timezone can't be resolved
+pub fn local_tm(time: SystemTime) -> Option<libc::tm> {
+    let secs = time.duration_since(UNIX_EPOCH).ok()?.as_secs() as libc::time_t;
+    // SAFETY: localtime_r only writes to the tm we pass it
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&secs, &mut tm) }.is_null() {
+        return None;
+    }
+    Some(tm)
+}
+
+/// Local time of day, or `None` when the timezone can't be resolved
+pub fn local_clock(time: SystemTime) -> Option<ClockTime> {
+    let tm = local_tm(time)?;
+    ClockTime::new(tm.tm_hour as u8, tm.tm_min as u8)
+}
+
+/// Run a configured shell command in the background, discarding its output.
+/// A thread waits for it so it doesn't linger as a zombie.
+pub fn run_command(command: &str) -> Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| HachiError::Config(format!("could not run {:?}: {}", command, e)))?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Polling state for the process rules
+#[derive(Debug, Default)]
+pub struct ProcessWatch {
+    last_poll: Option<Instant>,
+    /// Rule currently in effect
+    pub active: Option<usize>,
+    /// Profile to go back to once no rule matches, and the curve a preset
+    /// replaced along with the profile it belongs to, once written
+    pub restore: Option<(PowerProfile, Option<(PowerProfile, FanCurve)>)>,
+}
+
+impl ProcessWatch {
+    /// Whether the process list is due for another scan
+    pub fn due(&mut self) -> bool {
+        let now = Instant::now();
+        if self
+            .last_poll
+            .is_some_and(|last| now.duration_since(last) < POLL_INTERVAL)
+        {
+            return false;
+        }
+        self.last_poll = Some(now);
+        true
+    }
+}
@@ -0,0 +1,90 @@
+//! Command-line flags
+
+use std::path::PathBuf;
+
+use crate::error::{HachiError, Result};
+
+pub const USAGE: &str = "\
+Usage: hachi [OPTIONS] [COMMAND]
+
+Commands:
+  diagnose             Print a JSON bug report: versions, capabilities, state
+
+Options:
+  --config <FILE>      Config file (default: $XDG_CONFIG_HOME/hachi/config.toml)
+  --profile-dir <DIR>  Preset directory (default: $XDG_CONFIG_HOME/hachi/profiles)
+  --read-only          Monitor only; never change hardware settings
+  --safe-render <WHEN> 256 colors, ASCII and 10 FPS: auto (SSH, TERM=linux), on or off
+  --plain              Screen reader mode: plain text lines, no borders, art or color
+  --debug-console      Enable the raw asusd D-Bus console (`)
+  -h, --help           Print this help
+  -V, --version        Print the version";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Args {
+    pub config: Option<PathBuf>,
+    pub profile_dir: Option<PathBuf>,
+    pub read_only: bool,
+    /// Forced safe rendering, `None` to detect SSH and the Linux console
+    pub safe_render: Option<bool>,
+    /// Plain text lines for screen readers
+    pub plain: bool,
+    /// Raw D-Bus console on `` ` ``
+    pub debug_console: bool,
+    /// `hachi diagnose`: print the bug report bundle and exit
+    pub diagnose: bool,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Args {
+    /// Parse flags (without the program name), accepting both
+    /// `--flag value` and `--flag=value`
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .filter(|v| !v.is_empty())
+                    .ok_or_else(|| HachiError::InvalidArgs(format!("{} needs a value", flag)))
+            };
+
+            match flag.as_str() {
+                "--config" => parsed.config = Some(PathBuf::from(value()?)),
+                "--profile-dir" => parsed.profile_dir = Some(PathBuf::from(value()?)),
+                "--safe-render" => {
+                    parsed.safe_render = match value()?.as_str() {
+                        "auto" => None,
+                        "on" => Some(true),
+                        "off" => Some(false),
+                        other => {
+                            return Err(HachiError::InvalidArgs(format!(
+                                "--safe-render expects auto, on or off, not {}",
+                                other
+                            )))
+                        }
+                    }
+                }
+                "--read-only" => parsed.read_only = true,
+                "--plain" => parsed.plain = true,
+                "--debug-console" => parsed.debug_console = true,
+                "diagnose" => parsed.diagnose = true,
+                "-h" | "--help" => parsed.help = true,
+                "-V" | "--version" => parsed.version = true,
+                _ => return Err(HachiError::InvalidArgs(format!("unknown option {}", flag))),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
@@ -0,0 +1,99 @@
+//! Copy to the system clipboard with the OSC 52 escape sequence, which
+//! terminals forward to the host clipboard (over SSH too)
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::config::TemperatureUnit;
+use crate::daemon::{FanPoint, HardwareState};
+use crate::telemetry::Telemetry;
+
+/// Plain-text summary of the current state, for bug reports and forum posts
+pub fn summary(
+    model: Option<&str>,
+    state: &HardwareState,
+    telemetry: &Telemetry,
+    unit: TemperatureUnit,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "hachi · {}", model.unwrap_or("unknown model"));
+    let _ = writeln!(out, "  Profile      {}", state.power_profile);
+
+    let bypass = match state.charge_bypass {
+        Some(true) => " (bypass on)",
+        _ => "",
+    };
+    let _ = writeln!(out, "  Charge limit {}%{}", state.charge_limit, bypass);
+    if let Some(battery) = &telemetry.battery {
+        let _ = writeln!(
+            out,
+            "  Battery      {}% {}",
+            battery.capacity, battery.status
+        );
+    }
+
+    let curve = &state.fan_curve;
+    let status = if curve.enabled { "custom" } else { "default" };
+    for (name, points) in [("CPU", &curve.cpu_curve), ("GPU", &curve.gpu_curve)] {
+        let _ = writeln!(
+            out,
+            "  {} fan      {} · {}",
+            name,
+            status,
+            format_points(points, unit)
+        );
+    }
+
+    for (name, temp) in [("CPU", telemetry.cpu_temp()), ("GPU", telemetry.gpu_temp())] {
+        if let Some(celsius) = temp {
+            let _ = writeln!(out, "  {} temp     {}", name, unit.format(celsius));
+        }
+    }
+    if let Some(tdp) = state.tdp {
+        let _ = writeln!(out, "  TDP          {} W", tdp);
+    }
+
+    out
+}
+
+/// Curve points as `30°C 0%, 40°C 10%, …`
+fn format_points(curve: &[FanPoint], unit: TemperatureUnit) -> String {
+    curve
+        .iter()
+        .map(|point| format!("{} {}%", unit.format(point.temp as f32), point.speed))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escape sequence that sets the clipboard to `text`
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+/// Put `text` on the clipboard. Terminals without OSC 52 support ignore it.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52(text).as_bytes())?;
+    stdout.flush()
+}
+
+/// Standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
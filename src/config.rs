@@ -0,0 +1,697 @@
+//! User configuration loaded from `$XDG_CONFIG_HOME/hachi/config.toml`
+
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::app::{LayoutPreset, PanelId};
+use crate::daemon::{FanSteps, GpuMode, KbdBrightness, PowerProfile, Rgb, FAN_HYSTERESIS_RANGE};
+use crate::error::{HachiError, Result};
+use crate::ui::picker::Hsv;
+
+/// Top-level configuration file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub ui: UiConfig,
+    pub telemetry: TelemetryConfig,
+    pub session: SessionConfig,
+    pub recording: RecordingConfig,
+    pub alerts: AlertsConfig,
+    /// Step sizes fan curve points are snapped to
+    pub fan: FanSteps,
+    pub display: DisplayConfig,
+    pub elevation: ElevationConfig,
+    pub layout: LayoutConfig,
+    pub automation: AutomationConfig,
+    pub tune: TuneConfig,
+    pub startup: StartupConfig,
+    pub lighting: LightingConfig,
+    /// Scenarios after the built-in Travel and Desk; one named like a
+    /// built-in replaces it
+    pub scenarios: Vec<Scenario>,
+}
+
+/// Display preferences
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Unit used for every temperature shown in the UI
+    pub temperature_unit: TemperatureUnit,
+    /// Force the handheld layout on or off (auto-detected on the ROG Ally when unset)
+    pub handheld: Option<bool>,
+    /// Art shown in the header (cycle with `a`)
+    pub header_art: HeaderArt,
+    /// Force the one-row header on or off (automatic on short terminals when unset)
+    pub compact_header: Option<bool>,
+    /// Whether Tab / Shift+Tab wrap around from the last panel to the
+    /// first (default: on)
+    pub focus_wrap: Option<bool>,
+    /// Halve the frame rate, hide particles and poll sensors less often
+    /// while in the Quiet profile or on battery (default: on)
+    pub follow_power_profile: Option<bool>,
+    /// Sensor the fan graph's current point follows (cycle with `t` on the
+    /// fan panel)
+    pub fan_temp_source: FanTempSource,
+    /// Degrees Celsius the temperature must fall below a breakpoint before
+    /// the fan graph's target speed drops again (`+`/`-` on the fan panel).
+    /// asusd has no such setting, so hachi applies it to the target it shows.
+    #[serde(deserialize_with = "fan_hysteresis")]
+    pub fan_hysteresis: u8,
+}
+
+/// Header art style; the header's height follows the art
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderArt {
+    /// Bee logo beside the banner title
+    Bee,
+    /// Oni mask beside the banner title
+    Oni,
+    /// Gradient block-letter title
+    #[default]
+    BigText,
+    /// No header at all
+    None,
+}
+
+impl HeaderArt {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bee => "bee",
+            Self::Oni => "oni",
+            Self::BigText => "big text",
+            Self::None => "none",
+        }
+    }
+
+    pub fn cycle_next(self) -> Self {
+        match self {
+            Self::Bee => Self::Oni,
+            Self::Oni => Self::BigText,
+            Self::BigText => Self::None,
+            Self::None => Self::Bee,
+        }
+    }
+}
+
+/// Temperature the fan curve is keyed on. Firmware differs by model: most
+/// follow the CPU package, some the GPU or the EC's skin sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FanTempSource {
+    #[default]
+    Cpu,
+    Gpu,
+    Skin,
+}
+
+impl FanTempSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Gpu => "GPU",
+            Self::Skin => "skin",
+        }
+    }
+
+    pub fn cycle_next(self) -> Self {
+        match self {
+            Self::Cpu => Self::Gpu,
+            Self::Gpu => Self::Skin,
+            Self::Skin => Self::Cpu,
+        }
+    }
+}
+
+/// Display unit for temperatures (state is always kept in °C)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Convert a Celsius value into this unit
+    pub fn convert(self, celsius: f32) -> f32 {
+        match self {
+            Self::Celsius => celsius,
+            Self::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+        }
+    }
+
+    /// Format a Celsius value in this unit, e.g. `"104°F"`
+    pub fn format(self, celsius: f32) -> String {
+        format!("{:.0}{}", self.convert(celsius), self.symbol())
+    }
+
+    /// Format a temperature difference in Celsius in this unit, e.g. `"5°F"`
+    pub fn format_delta(self, celsius: f32) -> String {
+        let delta = match self {
+            Self::Celsius => celsius,
+            Self::Fahrenheit => celsius * 9.0 / 5.0,
+        };
+        format!("{:.0}{}", delta, self.symbol())
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Celsius => Self::Fahrenheit,
+            Self::Fahrenheit => Self::Celsius,
+        }
+    }
+}
+
+/// Telemetry panel settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// Sensors to hide, either as `chip/label` ids or bare chip names
+    pub hidden_sensors: Vec<String>,
+}
+
+/// Stress runs for fan curve tuning (`T`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuneConfig {
+    /// Shell command that loads the machine; the run lasts until it exits
+    pub command: String,
+    /// Seconds to keep recording after the load ends, to see the fans spin down
+    pub cooldown_secs: u64,
+}
+
+impl Default for TuneConfig {
+    fn default() -> Self {
+        Self {
+            command: "stress-ng --cpu 0 --timeout 60s".to_string(),
+            cooldown_secs: 30,
+        }
+    }
+}
+
+/// Settings written every time hachi starts, so launching it after boot
+/// also restores preferred settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartupConfig {
+    pub profile: Option<PowerProfile>,
+    /// Battery charge limit in percent
+    pub charge_limit: Option<u8>,
+}
+
+/// Keyboard lighting applied whenever the power profile changes, from
+/// hachi or elsewhere (Fn+F5, other tools)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LightingConfig {
+    pub quiet: Option<ProfileLighting>,
+    pub balanced: Option<ProfileLighting>,
+    pub performance: Option<ProfileLighting>,
+    pub reactive: ReactiveLighting,
+}
+
+impl LightingConfig {
+    pub fn for_profile(&self, profile: PowerProfile) -> Option<&ProfileLighting> {
+        match profile {
+            PowerProfile::Quiet => self.quiet.as_ref(),
+            PowerProfile::Balanced => self.balanced.as_ref(),
+            PowerProfile::Performance => self.performance.as_ref(),
+        }
+    }
+}
+
+/// Static color and backlight level for one profile; unset parts are left
+/// as they are
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileLighting {
+    pub color: Option<Rgb>,
+    pub brightness: Option<KbdBrightness>,
+}
+
+/// Keyboard color following the CPU temperature: blue at `cool`, green
+/// halfway and red from `hot` up, written as a static color
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReactiveLighting {
+    /// Start in this mode (toggled with `t` on the Lighting panel)
+    pub enabled: bool,
+    /// °C
+    pub cool: f32,
+    pub hot: f32,
+    /// Least time between two color writes, at least
+    /// [`Self::MIN_INTERVAL_SECS`]
+    pub interval_secs: u64,
+}
+
+impl Default for ReactiveLighting {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cool: 45.0,
+            hot: 90.0,
+            interval_secs: 60,
+        }
+    }
+}
+
+impl ReactiveLighting {
+    /// Hue steps the color moves in, so small temperature wobbles don't
+    /// each cost a write
+    const HUE_STEP: f32 = 15.0;
+
+    /// asusd stores every static color write in the keyboard MCU's flash
+    /// and has no non-persisting path, so writes are kept well apart
+    pub const MIN_INTERVAL_SECS: u64 = 30;
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(Self::MIN_INTERVAL_SECS))
+    }
+
+    pub fn color(&self, celsius: f32) -> Rgb {
+        let span = (self.hot - self.cool).max(1.0);
+        let heat = ((celsius - self.cool) / span).clamp(0.0, 1.0);
+        let hue = (240.0 * (1.0 - heat) / Self::HUE_STEP).round() * Self::HUE_STEP;
+        Hsv {
+            hue: hue as u16,
+            saturation: 100,
+            value: 100,
+        }
+        .to_rgb()
+    }
+}
+
+/// Settings applied together with one key (`F5` and up); unset parts are
+/// left as they are
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scenario {
+    pub name: String,
+    pub profile: Option<PowerProfile>,
+    /// Battery charge limit in percent
+    pub charge_limit: Option<u8>,
+    pub lighting: Option<ProfileLighting>,
+    /// Shell command to run, e.g. one setting the panel's refresh rate
+    pub command: Option<String>,
+    /// Sakura petals on or off
+    pub particles: Option<bool>,
+}
+
+impl Scenario {
+    /// Function keys from `F5`, one per scenario
+    pub const KEYS: usize = 5;
+
+    /// On the road: quiet, a full battery, dark keyboard, no petals
+    pub fn travel() -> Self {
+        Self {
+            name: "Travel".to_string(),
+            profile: Some(PowerProfile::Quiet),
+            charge_limit: Some(100),
+            lighting: Some(ProfileLighting {
+                color: None,
+                brightness: Some(KbdBrightness::Off),
+            }),
+            command: None,
+            particles: Some(false),
+        }
+    }
+
+    /// Plugged in at a desk: full speed, a battery-sparing limit
+    pub fn desk() -> Self {
+        Self {
+            name: "Desk".to_string(),
+            profile: Some(PowerProfile::Performance),
+            charge_limit: Some(80),
+            lighting: Some(ProfileLighting {
+                color: None,
+                brightness: Some(KbdBrightness::Med),
+            }),
+            command: None,
+            particles: Some(true),
+        }
+    }
+}
+
+/// Session bookkeeping
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// What to do with the session statistics summary on exit
+    pub summary: SummaryOutput,
+}
+
+/// Destination for the exit summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryOutput {
+    /// Print to stdout after the terminal is restored
+    #[default]
+    Print,
+    /// Write to `last-session.txt` in the state directory
+    File,
+    /// Don't produce a summary
+    None,
+}
+
+/// Telemetry recording settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingConfig {
+    /// Start recording as soon as hachi launches
+    pub enabled: bool,
+    pub format: RecordingFormat,
+    /// Output file (defaults to `telemetry.<ext>` in the state directory)
+    pub path: Option<PathBuf>,
+}
+
+/// File format for telemetry recordings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordingFormat {
+    #[default]
+    Csv,
+    Jsonl,
+}
+
+impl RecordingFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// Hardware alert thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// Warn when a fan reads 0 RPM while CPU/GPU exceed this temperature (°C)
+    pub fan_stall_temp: f32,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self { fan_stall_temp: 75.0 }
+    }
+}
+
+/// Display backlight automation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Dim the screen to this percentage when switching to battery,
+    /// restoring the previous brightness on AC
+    pub dim_on_battery: Option<u8>,
+}
+
+/// Profile switching driven by running programs, lid/dock events and user
+/// inactivity
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutomationConfig {
+    /// Checked in order; the first rule with a running process wins
+    pub processes: Vec<ProcessRule>,
+    /// Checked in order on lid, dock and power changes; the first match wins
+    pub events: Vec<EventRule>,
+    /// Drop to Quiet and dim the keyboard after this many minutes without
+    /// user activity (logind idle hint), restoring both on activity
+    pub idle_quiet_minutes: Option<u32>,
+    /// Time windows that cap the fans and force a profile; the first one
+    /// covering the current time wins
+    pub quiet_hours: Vec<QuietHours>,
+    /// What to do on switching to each profile
+    pub profiles: ProfileRules,
+}
+
+/// Actions on switching to a profile, from hachi or elsewhere (Fn+F5)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileRules {
+    pub quiet: Option<ProfileRule>,
+    pub balanced: Option<ProfileRule>,
+    pub performance: Option<ProfileRule>,
+}
+
+impl ProfileRules {
+    pub fn for_profile(&self, profile: PowerProfile) -> Option<&ProfileRule> {
+        match profile {
+            PowerProfile::Quiet => self.quiet.as_ref(),
+            PowerProfile::Balanced => self.balanced.as_ref(),
+            PowerProfile::Performance => self.performance.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileRule {
+    /// Shell command to run, e.g. one setting the panel's refresh rate
+    /// through the compositor (asusd has no refresh rate control)
+    pub command: Option<String>,
+    /// GPU MUX position to offer, switched only once confirmed since it
+    /// takes effect on the next boot
+    pub gpu: Option<GpuMode>,
+}
+
+/// Switch profile while any of these processes run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessRule {
+    /// Process names, or prefixes ending in `*`
+    #[serde(rename = "match")]
+    pub processes: Vec<String>,
+    #[serde(default = "performance")]
+    pub profile: PowerProfile,
+    /// Saved preset whose fan curve to apply as well
+    #[serde(default)]
+    pub preset: Option<String>,
+}
+
+/// Switch profile (and GPU MUX and keyboard brightness) while the lid, dock
+/// and power source are in a given state. Unset conditions match anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventRule {
+    pub lid_closed: Option<bool>,
+    pub docked: Option<bool>,
+    pub external_power: Option<bool>,
+    pub profile: Option<PowerProfile>,
+    /// MUX position to switch to, applied by the firmware on the next boot
+    pub gpu: Option<GpuMode>,
+    /// Keyboard backlight level while the rule holds
+    pub keyboard: Option<KbdBrightness>,
+}
+
+/// Cap fan speed and switch profile between two local times of day,
+/// e.g. 23:00 to 07:00. Windows may wrap past midnight.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: ClockTime,
+    pub end: ClockTime,
+    /// Highest fan speed in percent
+    #[serde(default = "fan_cap")]
+    pub fan_cap: u8,
+    #[serde(default = "quiet")]
+    pub profile: PowerProfile,
+}
+
+/// Time of day in minutes since midnight, written `HH:MM` in the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ClockTime(u16);
+
+impl ClockTime {
+    pub fn new(hour: u8, minute: u8) -> Option<Self> {
+        (hour < 24 && minute < 60).then(|| Self(hour as u16 * 60 + minute as u16))
+    }
+
+    /// Parse `HH:MM` (24-hour, the hour may be a single digit)
+    pub fn parse(s: &str) -> Option<Self> {
+        let (hour, minute) = s.trim().split_once(':')?;
+        if minute.len() != 2 {
+            return None;
+        }
+        Self::new(hour.parse().ok()?, minute.parse().ok()?)
+    }
+}
+
+impl fmt::Display for ClockTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 60, self.0 % 60)
+    }
+}
+
+impl Serialize for ClockTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClockTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| {
+            serde::de::Error::custom(format!("invalid time {:?}, expected HH:MM", s))
+        })
+    }
+}
+
+/// `fan_hysteresis`, rejected outside [`FAN_HYSTERESIS_RANGE`]
+fn fan_hysteresis<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<u8, D::Error> {
+    let degrees = u8::deserialize(deserializer)?;
+    let range = FAN_HYSTERESIS_RANGE;
+    if !(range.min..=range.max).contains(&degrees) {
+        return Err(serde::de::Error::custom(format!(
+            "fan_hysteresis {}°C outside {}-{}°C",
+            degrees, range.min, range.max
+        )));
+    }
+    Ok(degrees)
+}
+
+fn performance() -> PowerProfile {
+    PowerProfile::Performance
+}
+
+fn quiet() -> PowerProfile {
+    PowerProfile::Quiet
+}
+
+fn fan_cap() -> u8 {
+    40
+}
+
+/// Privileged writes for attributes that need root
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ElevationConfig {
+    /// Retry permission-denied writes through pkexec (defaults to on in
+    /// graphical sessions, where a polkit agent can show its dialog)
+    pub enabled: Option<bool>,
+}
+
+/// Panel placement and order; unset columns use the built-in layout
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// Left column of the desktop layout, top to bottom
+    pub left: Option<Vec<PanelId>>,
+    /// Right column of the desktop layout, top to bottom
+    pub right: Option<Vec<PanelId>>,
+    /// Single column of the handheld layout, top to bottom
+    pub handheld: Option<Vec<PanelId>>,
+    /// Built-in layout used instead of the columns above (switch with F1-F3)
+    pub preset: Option<LayoutPreset>,
+    /// Panels Tab visits first, in this order; the others follow in layout
+    /// order
+    pub focus_order: Vec<PanelId>,
+}
+
+/// `$XDG_CONFIG_HOME/hachi`, falling back to `~/.config/hachi`
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// Set by `--config`, replacing the default config file location
+static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+/// Set by `--profile-dir`, replacing the default preset directory
+static PROFILE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use this config file instead of the XDG default (first call wins)
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_PATH.set(path);
+}
+
+/// Use this preset directory instead of the XDG default (first call wins)
+pub fn set_profile_dir(dir: PathBuf) {
+    let _ = PROFILE_DIR.set(dir);
+}
+
+/// Directory of saved presets, `profiles/` in the config directory unless
+/// overridden with `--profile-dir`
+pub fn profile_dir() -> Option<PathBuf> {
+    PROFILE_DIR
+        .get()
+        .cloned()
+        .or_else(|| config_dir().map(|dir| dir.join("profiles")))
+}
+
+/// `$XDG_STATE_HOME/hachi`, falling back to `~/.local/state/hachi`
+pub fn state_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_STATE_HOME", ".local/state")
+}
+
+fn xdg_dir(var: &str, home_fallback: &str) -> Option<PathBuf> {
+    let base = std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(home_fallback)))?;
+    Some(base.join("hachi"))
+}
+
+impl Config {
+    /// Location of the config file, `--config` taking precedence
+    pub fn path() -> Option<PathBuf> {
+        CONFIG_PATH
+            .get()
+            .cloned()
+            .or_else(|| config_dir().map(|dir| dir.join("config.toml")))
+    }
+
+    /// Load the config file, falling back to defaults when it doesn't exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        toml::from_str(&contents)
+            .map_err(|e| HachiError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Write the config file, creating the directory if needed
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = Self::path() else {
+            return Err(HachiError::Config("No config directory available".to_string()));
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let contents =
+            toml::to_string_pretty(self).map_err(|e| HachiError::Config(e.to_string()))?;
+        fs::write(&path, contents)?;
+        Ok(())
+    }
+    /// Travel and Desk (or the user's versions of them) on `F5` and `F6`,
+    /// then the user's own scenarios on the following keys
+    pub fn scenarios(&self) -> Vec<Scenario> {
+        let builtins = [Scenario::travel(), Scenario::desk()];
+        let mut scenarios: Vec<Scenario> = builtins
+            .iter()
+            .map(|builtin| {
+                let replaced = self.scenarios.iter().find(|s| s.name == builtin.name);
+                replaced.unwrap_or(builtin).clone()
+            })
+            .collect();
+        let own = self.scenarios.iter().filter(|s| builtins.iter().all(|b| b.name != s.name));
+        scenarios.extend(own.cloned());
+        scenarios.truncate(Scenario::KEYS);
+        scenarios
+    }
+}
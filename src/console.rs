@@ -0,0 +1,250 @@
+//! Raw D-Bus console (`--debug-console`, `` ` `` in the app): read and
+//! write asusd properties and call its methods by name, busctl style, to
+//! try features hachi has no panel for yet
+
+use zbus::zvariant::Value;
+
+use crate::error::{HachiError, Result};
+
+/// Lines of output kept in the console
+const SCROLLBACK: usize = 200;
+
+/// asusd interfaces by the short names the console accepts
+const INTERFACES: [(&str, &str); 4] = [
+    ("platform", "xyz.ljones.Platform"),
+    ("aura", "xyz.ljones.Aura"),
+    ("anime", "xyz.ljones.Anime"),
+    ("slash", "xyz.ljones.Slash"),
+];
+
+pub const USAGE: &str = "\
+get <interface> <property>
+set <interface> <property> <type> <value>
+call <interface> <method> [<signature> <args>...]
+introspect <interface>
+interfaces: platform, aura, anime, slash or a full name;
+types: y b n q i u x t d s o, e.g. set platform ChargeControlEndThreshold y 80";
+
+/// One argument, typed by its D-Bus signature character
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawArg {
+    Byte(u8),
+    Bool(bool),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    Double(f64),
+    Str(String),
+    Path(String),
+}
+
+impl RawArg {
+    fn parse(kind: char, text: &str) -> Result<Self> {
+        let invalid = || HachiError::InvalidArgs(format!("{:?} is not a valid {}", text, kind));
+        let number = |_| invalid();
+        Ok(match kind {
+            'y' => Self::Byte(text.parse().map_err(number)?),
+            'b' => Self::Bool(match text {
+                "true" | "1" => true,
+                "false" | "0" => false,
+                _ => return Err(invalid()),
+            }),
+            'n' => Self::I16(text.parse().map_err(number)?),
+            'q' => Self::U16(text.parse().map_err(number)?),
+            'i' => Self::I32(text.parse().map_err(number)?),
+            'u' => Self::U32(text.parse().map_err(number)?),
+            'x' => Self::I64(text.parse().map_err(number)?),
+            't' => Self::U64(text.parse().map_err(number)?),
+            'd' => Self::Double(text.parse().map_err(|_| invalid())?),
+            's' => Self::Str(text.to_string()),
+            'o' if text.starts_with('/') => Self::Path(text.to_string()),
+            _ => {
+                return Err(HachiError::InvalidArgs(format!(
+                    "unsupported type {:?}: use y b n q i u x t d s o",
+                    kind
+                )))
+            }
+        })
+    }
+
+    pub fn value(&self) -> Result<Value<'static>> {
+        Ok(match self {
+            Self::Byte(v) => Value::U8(*v),
+            Self::Bool(v) => Value::Bool(*v),
+            Self::I16(v) => Value::I16(*v),
+            Self::U16(v) => Value::U16(*v),
+            Self::I32(v) => Value::I32(*v),
+            Self::U32(v) => Value::U32(*v),
+            Self::I64(v) => Value::I64(*v),
+            Self::U64(v) => Value::U64(*v),
+            Self::Double(v) => Value::F64(*v),
+            Self::Str(v) => Value::from(v.clone()),
+            Self::Path(v) => Value::ObjectPath(
+                v.clone()
+                    .try_into()
+                    .map_err(|e| HachiError::InvalidArgs(format!("{}: {}", v, e)))?,
+            ),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawAction {
+    Get(String),
+    Set(String, RawArg),
+    Call(String, Vec<RawArg>),
+    Introspect,
+}
+
+/// A console command, resolved to a full interface name
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawCall {
+    pub interface: String,
+    pub action: RawAction,
+}
+
+impl RawCall {
+    pub fn parse(line: &str) -> Result<Self> {
+        let words = split(line)?;
+        let usage = || HachiError::InvalidArgs(format!("expected: {}", line_usage(&words)));
+        let (command, interface) = match words.as_slice() {
+            [command, interface, ..] => (command.as_str(), interface_name(interface)),
+            _ => return Err(usage()),
+        };
+        let rest = &words[2..];
+
+        let action = match (command, rest) {
+            ("get", [property]) => RawAction::Get(property.clone()),
+            ("set", [property, kind, value]) => {
+                let [kind] = kind.chars().collect::<Vec<_>>()[..] else {
+                    return Err(usage());
+                };
+                RawAction::Set(property.clone(), RawArg::parse(kind, value)?)
+            }
+            ("call", [method]) => RawAction::Call(method.clone(), Vec::new()),
+            ("call", [method, signature, args @ ..]) => {
+                if signature.chars().count() != args.len() {
+                    return Err(HachiError::InvalidArgs(format!(
+                        "signature {} takes {} arguments, got {}",
+                        signature,
+                        signature.chars().count(),
+                        args.len()
+                    )));
+                }
+                let args = signature
+                    .chars()
+                    .zip(args)
+                    .map(|(kind, arg)| RawArg::parse(kind, arg))
+                    .collect::<Result<_>>()?;
+                RawAction::Call(method.clone(), args)
+            }
+            ("introspect", []) => RawAction::Introspect,
+            _ => return Err(usage()),
+        };
+        Ok(Self { interface, action })
+    }
+
+    /// Whether the command can change anything, refused in read-only mode
+    pub fn writes(&self) -> bool {
+        matches!(self.action, RawAction::Set(..) | RawAction::Call(..))
+    }
+}
+
+/// Expected form of the command being typed, or every form
+fn line_usage(words: &[String]) -> &'static str {
+    let command = words.first().map(String::as_str).unwrap_or_default();
+    USAGE
+        .lines()
+        .find(|form| form.split(' ').next() == Some(command))
+        .unwrap_or("get, set, call or introspect <interface> ...")
+}
+
+fn interface_name(name: &str) -> String {
+    INTERFACES
+        .iter()
+        .find(|(short, _)| short.eq_ignore_ascii_case(name))
+        .map_or(name, |(_, full)| full)
+        .to_string()
+}
+
+/// Split on spaces, keeping `"quoted strings"` together
+fn split(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if quoted {
+        return Err(HachiError::InvalidArgs("unclosed quote".to_string()));
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Console state: the line being typed, output so far and past commands
+#[derive(Debug, Default)]
+pub struct Console {
+    pub input: String,
+    /// Commands and replies, oldest first
+    pub output: Vec<String>,
+    commands: Vec<String>,
+    /// Position while stepping back through `commands` with Up
+    recalled: Option<usize>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        let mut console = Self::default();
+        console.output.extend(USAGE.lines().map(str::to_string));
+        console
+    }
+
+    pub fn print(&mut self, text: &str) {
+        self.output.extend(text.lines().map(str::to_string));
+        let over = self.output.len().saturating_sub(SCROLLBACK);
+        self.output.drain(..over);
+    }
+
+    /// Take the typed line, echoing it and remembering it for Up
+    pub fn submit(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.input).trim().to_string();
+        self.recalled = None;
+        if line.is_empty() {
+            return None;
+        }
+        self.print(&format!("> {}", line));
+        if self.commands.last() != Some(&line) {
+            self.commands.push(line.clone());
+        }
+        Some(line)
+    }
+
+    /// Put an earlier (Up) or later (Down) command in the input
+    pub fn recall(&mut self, earlier: bool) {
+        let last = self.commands.len().checked_sub(1);
+        self.recalled = match (self.recalled, earlier) {
+            (None, true) => last,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if Some(i) < last => Some(i + 1),
+            _ => None,
+        };
+        self.input = self
+            .recalled
+            .map(|i| self.commands[i].clone())
+            .unwrap_or_default();
+    }
+}
@@ -1,12 +1,26 @@
+use std::collections::HashMap;
 use std::fmt;
-use tokio::sync::{broadcast, mpsc};
-use std::sync::Arc;
+use std::mem::{self, Discriminant};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::task::JoinHandle;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::stream::{BoxStream, StreamExt};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zbus::{Connection, proxy};
+use zbus::zvariant::{
+    OwnedFd, OwnedObjectPath, OwnedValue, Structure, StructureBuilder, Type, Value,
+};
 
+use crate::console::{RawAction, RawCall};
 use crate::error::HachiError;
+use crate::health::Check;
+use crate::sysfs::{self, Backlight, Peripheral};
 
 /// Power profile modes for ASUS laptops
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PowerProfile {
     Quiet,
     #[default]
@@ -15,6 +29,18 @@ pub enum PowerProfile {
 }
 
 impl PowerProfile {
+    /// Profiles in UI order, least to most power
+    pub const ALL: [PowerProfile; 3] = [Self::Quiet, Self::Balanced, Self::Performance];
+
+    /// Position in `ALL` (and in the profile selector)
+    pub fn ui_index(self) -> usize {
+        match self {
+            Self::Quiet => 0,
+            Self::Balanced => 1,
+            Self::Performance => 2,
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Quiet => "Quiet",
@@ -50,6 +76,32 @@ impl PowerProfile {
         }
     }
 
+    /// Parse an ACPI `platform_profile` value
+    pub fn from_platform_profile(value: &str) -> Option<Self> {
+        match value {
+            "quiet" | "low-power" => Some(Self::Quiet),
+            "balanced" => Some(Self::Balanced),
+            "performance" => Some(Self::Performance),
+            _ => None,
+        }
+    }
+
+    /// `platform_profile` values for this profile, in order of preference
+    pub fn platform_profile_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Quiet => &["quiet", "low-power"],
+            Self::Balanced => &["balanced"],
+            Self::Performance => &["performance"],
+        }
+    }
+
+    /// The firmware's own name for this profile among the kernel's
+    /// `platform_profile` choices, e.g. `low-power` for Quiet
+    pub fn firmware_name(self, choices: &[String]) -> Option<&'static str> {
+        let names = self.platform_profile_names().iter();
+        names.copied().find(|name| choices.iter().any(|choice| choice == name))
+    }
+
     pub fn cycle_next(self) -> Self {
         match self {
             Self::Quiet => Self::Balanced,
@@ -66,18 +118,141 @@ impl fmt::Display for PowerProfile {
 }
 
 /// Fan curve point (temperature in °C, fan speed in %)
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FanPoint {
     pub temp: u8,
     pub speed: u8,
 }
 
+/// A fan asusd keeps a curve of its own for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fan {
+    Cpu,
+    Gpu,
+}
+
+impl Fan {
+    pub const ALL: [Fan; 2] = [Self::Cpu, Self::Gpu];
+
+    /// asusd's name for the fan (`FanCurvePU`)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Gpu => "GPU",
+        }
+    }
+}
+
+/// Which fans follow their custom curve in each profile, as asusd keeps
+/// it: rows in `PowerProfile::ALL` order, a column per fan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurveEnablement([[bool; 2]; 3]);
+
+impl CurveEnablement {
+    pub fn get(&self, profile: PowerProfile, fan: Fan) -> bool {
+        self.0[profile.ui_index()][fan as usize]
+    }
+
+    pub fn set(&mut self, profile: PowerProfile, fan: Fan, enabled: bool) {
+        self.0[profile.ui_index()][fan as usize] = enabled;
+    }
+
+    /// Whether any fan follows its custom curve in `profile`
+    pub fn any(&self, profile: PowerProfile) -> bool {
+        self.0[profile.ui_index()].contains(&true)
+    }
+}
+
+/// asusd's `CurveData`: one fan's curve in a profile, as eight points of
+/// PWM duty (0-255) and temperature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct CurveData {
+    /// `Fan::as_str`, or `MID` for a third fan hachi doesn't show
+    pub fan: String,
+    pub pwm: Vec<u8>,
+    pub temp: Vec<u8>,
+    pub enabled: bool,
+}
+
+impl CurveData {
+    pub fn new(fan: Fan, points: &[FanPoint], enabled: bool) -> Self {
+        Self {
+            fan: fan.as_str().to_string(),
+            pwm: points
+                .iter()
+                .map(|point| (point.speed.min(100) as u16 * 255 + 50) / 100)
+                .map(|pwm| pwm as u8)
+                .collect(),
+            temp: points.iter().map(|point| point.temp).collect(),
+            enabled,
+        }
+    }
+
+    /// The curve with duty cycles as percentages
+    pub fn points(&self) -> Vec<FanPoint> {
+        self.temp
+            .iter()
+            .zip(&self.pwm)
+            .map(|(&temp, &pwm)| FanPoint {
+                temp,
+                speed: ((pwm as u16 * 100 + 127) / 255) as u8,
+            })
+            .collect()
+    }
+}
+
+/// Granularity the EC stores fan curves in. Some only take certain PWM
+/// steps and round whatever they're given; neither asusd nor the kernel
+/// says which, so it comes from the `[fan]` config section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FanSteps {
+    /// Fan speed step in percent
+    pub speed_step: u8,
+    /// Temperature step in degrees Celsius
+    pub temp_step: u8,
+}
+
+impl Default for FanSteps {
+    fn default() -> Self {
+        Self { speed_step: 1, temp_step: 1 }
+    }
+}
+
+impl FanSteps {
+    /// Nearest multiple of `step` to `value`, at most `max`
+    fn snap(value: u8, step: u8, max: u8) -> u8 {
+        let step = step.max(1) as u16;
+        let snapped = (value as u16 + step / 2) / step * step;
+        let top = max as u16 / step * step;
+        snapped.min(top) as u8
+    }
+
+    pub fn snap_speed(self, speed: u8) -> u8 {
+        Self::snap(speed, self.speed_step, 100)
+    }
+
+    pub fn snap_temp(self, temp: u8) -> u8 {
+        Self::snap(temp, self.temp_step, u8::MAX)
+    }
+
+    /// Whether the EC takes any whole percent and degree
+    pub fn takes_any(self) -> bool {
+        self.speed_step <= 1 && self.temp_step <= 1
+    }
+}
+
 /// Fan curve data
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FanCurve {
     pub cpu_curve: Vec<FanPoint>,
     pub gpu_curve: Vec<FanPoint>,
+    /// Whether the active profile uses the custom curve
     pub enabled: bool,
+    /// Per fan and profile switches behind `enabled`
+    #[serde(default)]
+    pub enablement: CurveEnablement,
 }
 
 impl FanCurve {
@@ -104,318 +279,2571 @@ impl FanCurve {
                 FanPoint { temp: 100, speed: 100 },
             ],
             enabled: false,
+            enablement: CurveEnablement::default(),
         }
     }
-}
-
-/// Current hardware state snapshot
-#[derive(Debug, Clone, Default)]
-pub struct HardwareState {
-    pub power_profile: PowerProfile,
-    pub charge_limit: u8,
-    pub fan_curve: FanCurve,
-    pub connected: bool,
-}
 
-/// Intents sent from UI to Hardware Actor
-#[derive(Debug, Clone)]
-pub enum HardwareIntent {
-    /// Request current state refresh
-    RefreshState,
-    /// Set power profile
-    SetPowerProfile(PowerProfile),
-    /// Set battery charge limit (0-100)
-    SetChargeLimit(u8),
-    /// Set custom fan curve
-    SetFanCurve(FanCurve),
-    /// Enable/disable custom fan curves
-    SetFanCurveEnabled(bool),
-    /// Shutdown the actor
-    Shutdown,
-}
-
-/// Updates broadcast from Hardware Actor to UI
-#[derive(Debug, Clone)]
-pub enum HardwareUpdate {
-    /// Full state refresh
-    StateRefresh(HardwareState),
-    /// Power profile changed
-    PowerProfileChanged(PowerProfile),
-    /// Charge limit changed
-    ChargeLimitChanged(u8),
-    /// Fan curve changed
-    FanCurveChanged(FanCurve),
-    /// Connection status changed
-    ConnectionStatus(bool),
-    /// Error occurred
-    Error(Arc<HachiError>),
-}
-
-// =============================================================================
-// D-Bus Proxy Definitions for org.asuslinux.Daemon
-// =============================================================================
-
-#[proxy(
-    interface = "xyz.ljones.Platform",
-    default_service = "xyz.ljones.Asusd",
-    default_path = "/xyz/ljones"
-)]
-trait AsusPlatform {
-    /// Get the current platform profile (0=Quiet, 1=Balanced, 2=Performance, 3=?)
-    #[zbus(property)]
-    fn platform_profile(&self) -> zbus::Result<u32>;
-
-    /// Set the platform profile
-    #[zbus(property)]
-    fn set_platform_profile(&self, profile: u32) -> zbus::Result<()>;
-
-    /// Get charge control end threshold (battery limit)
-    #[zbus(property)]
-    fn charge_control_end_threshold(&self) -> zbus::Result<u8>;
-
-    /// Set charge control end threshold
-    #[zbus(property)]
-    fn set_charge_control_end_threshold(&self, limit: u8) -> zbus::Result<()>;
-
-    /// Cycle to next platform profile
-    fn next_platform_profile(&self) -> zbus::Result<()>;
-}
-
-// =============================================================================
-// Hardware Actor Implementation
-// =============================================================================
-
-pub struct HardwareActor {
-    intent_rx: mpsc::Receiver<HardwareIntent>,
-    update_tx: broadcast::Sender<HardwareUpdate>,
-    connection: Option<Connection>,
-}
+    /// The same breakpoints with every fan at 100%, enabled in `profile`
+    pub fn full_blast(&self, profile: PowerProfile) -> Self {
+        let flat = |points: &[FanPoint]| {
+            points
+                .iter()
+                .map(|point| FanPoint { temp: point.temp, speed: 100 })
+                .collect()
+        };
+        Self {
+            cpu_curve: flat(&self.cpu_curve),
+            gpu_curve: flat(&self.gpu_curve),
+            ..self.clone()
+        }
+        .enabled_in(profile)
+    }
 
-impl HardwareActor {
-    pub fn new(
-        intent_rx: mpsc::Receiver<HardwareIntent>,
-        update_tx: broadcast::Sender<HardwareUpdate>,
-    ) -> Self {
+    /// The same curve with no fan above `cap` percent, enabled in `profile`
+    pub fn capped(&self, cap: u8, profile: PowerProfile) -> Self {
+        let cap = cap.min(100);
+        let clamp = |points: &[FanPoint]| {
+            points
+                .iter()
+                .map(|point| FanPoint { temp: point.temp, speed: point.speed.min(cap) })
+                .collect()
+        };
         Self {
-            intent_rx,
-            update_tx,
-            connection: None,
+            cpu_curve: clamp(&self.cpu_curve),
+            gpu_curve: clamp(&self.gpu_curve),
+            ..self.clone()
         }
+        .enabled_in(profile)
     }
 
-    /// Run the actor loop
-    pub async fn run(mut self) {
-        use futures::StreamExt;
+    /// The same curve with both fans following it in `profile`
+    fn enabled_in(mut self, profile: PowerProfile) -> Self {
+        for fan in Fan::ALL {
+            self.enablement.set(profile, fan, true);
+        }
+        self.enabled = true;
+        self
+    }
 
-        // Try to establish D-Bus connection
-        self.connect().await;
+    /// One fan's points
+    pub fn points(&self, fan: Fan) -> &[FanPoint] {
+        match fan {
+            Fan::Cpu => &self.cpu_curve,
+            Fan::Gpu => &self.gpu_curve,
+        }
+    }
 
-        // Initial state fetch
-        if self.connection.is_some() {
-            self.refresh_state().await;
+    /// Take which fans asusd has following their curve in `profile`
+    fn read_enablement(&mut self, profile: PowerProfile, curves: &[CurveData]) {
+        for fan in Fan::ALL {
+            let enabled = curves.iter().any(|data| data.fan == fan.as_str() && data.enabled);
+            self.enablement.set(profile, fan, enabled);
         }
+    }
 
-        // Set up property change monitoring
-        let mut property_stream = if let Some(conn) = &self.connection {
-            match AsusPlatformProxy::new(conn).await {
-                Ok(proxy) => Some(proxy.receive_platform_profile_changed().await),
-                Err(_) => None,
+    /// Take the points of each fan asusd reports a curve for
+    fn read_asusd(&mut self, curves: &[CurveData]) {
+        for data in curves {
+            match data.fan.as_str() {
+                "CPU" => self.cpu_curve = data.points(),
+                "GPU" => self.gpu_curve = data.points(),
+                _ => {}
             }
-        } else {
-            None
+        }
+    }
+
+    /// The same curve with every speed moved by `offset` percentage points,
+    /// within 0-100%
+    pub fn shifted(&self, offset: i8) -> Self {
+        let shift = |points: &[FanPoint]| {
+            points
+                .iter()
+                .map(|point| FanPoint {
+                    temp: point.temp,
+                    speed: (point.speed as i16 + offset as i16).clamp(0, 100) as u8,
+                })
+                .collect()
         };
+        Self {
+            cpu_curve: shift(&self.cpu_curve),
+            gpu_curve: shift(&self.gpu_curve),
+            ..self.clone()
+        }
+    }
 
-        // Main event loop using select
-        loop {
-            tokio::select! {
-                // Handle intents from UI
-                Some(intent) = self.intent_rx.recv() => {
-                    match intent {
-                        HardwareIntent::RefreshState => {
-                            self.refresh_state().await;
-                        }
-                        HardwareIntent::SetPowerProfile(profile) => {
-                            self.set_power_profile(profile).await;
-                        }
-                        HardwareIntent::SetChargeLimit(limit) => {
-                            self.set_charge_limit(limit).await;
-                        }
-                        HardwareIntent::SetFanCurve(curve) => {
-                            self.set_fan_curve(curve).await;
-                        }
-                        HardwareIntent::SetFanCurveEnabled(enabled) => {
-                            self.set_fan_curve_enabled(enabled).await;
-                        }
-                        HardwareIntent::Shutdown => {
-                            break;
-                        }
-                    }
-                }
+    /// The GPU fan given the CPU fan's points, speeds moved by `offset`
+    pub fn cpu_to_gpu(&self, offset: i8) -> Self {
+        Self {
+            gpu_curve: self.shifted(offset).cpu_curve,
+            ..self.clone()
+        }
+    }
 
-                // Handle property changes from D-Bus
-                Some(change) = async {
-                    match &mut property_stream {
-                        Some(stream) => stream.next().await,
-                        None => std::future::pending().await,
-                    }
-                } => {
-                    if let Ok(profile) = change.get().await {
-                        let new_profile = PowerProfile::from_u32(profile);
-                        let _ = self.update_tx.send(HardwareUpdate::PowerProfileChanged(new_profile));
-                    }
+    /// The curve as the EC would store it, every point snapped to `steps`.
+    /// Neighbours that snap to the same temperature are spread a step apart
+    /// so the temperatures keep rising.
+    pub fn snapped(&self, steps: FanSteps) -> Self {
+        let snap = |points: &[FanPoint]| {
+            let mut snapped: Vec<FanPoint> = Vec::with_capacity(points.len());
+            for point in points {
+                let mut temp = steps.snap_temp(point.temp);
+                if let Some(previous) = snapped.last().filter(|previous| previous.temp >= temp) {
+                    temp = previous.temp.saturating_add(steps.temp_step.max(1));
                 }
-
-                else => break,
+                let speed = steps.snap_speed(point.speed);
+                snapped.push(FanPoint { temp, speed });
             }
+            snapped
+        };
+        Self {
+            cpu_curve: snap(&self.cpu_curve),
+            gpu_curve: snap(&self.gpu_curve),
+            ..self.clone()
         }
     }
 
-    async fn connect(&mut self) {
-        match Connection::system().await {
-            Ok(conn) => {
-                self.connection = Some(conn);
-                let _ = self.update_tx.send(HardwareUpdate::ConnectionStatus(true));
+    /// Check what the firmware relies on: rising temperatures and speeds
+    /// up to 100%
+    pub fn validate(&self) -> Result<(), HachiError> {
+        for (name, points) in [("CPU", &self.cpu_curve), ("GPU", &self.gpu_curve)] {
+            if points.iter().any(|point| point.speed > 100) {
+                return Err(HachiError::InvalidFanCurve(format!("{} speed above 100%", name)));
             }
-            Err(e) => {
-                let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-                    HachiError::from(e)
+            if points.windows(2).any(|pair| pair[0].temp >= pair[1].temp) {
+                return Err(HachiError::InvalidFanCurve(format!(
+                    "{} temperatures must rise point to point",
+                    name
                 )));
-                let _ = self.update_tx.send(HardwareUpdate::ConnectionStatus(false));
             }
         }
+        Ok(())
     }
 
-    async fn refresh_state(&mut self) {
-        let Some(conn) = &self.connection else {
-            return;
-        };
-
-        let mut state = HardwareState {
-            connected: true,
-            ..Default::default()
+    /// CPU fan speed the curve asks for at `celsius`: the speed of the last
+    /// breakpoint reached. When cooling from `previous`, the speed only
+    /// drops once the temperature is `hysteresis` degrees below the
+    /// breakpoint, so it doesn't flip back and forth around one.
+    pub fn target_speed(&self, celsius: f32, previous: Option<u8>, hysteresis: u8) -> u8 {
+        let speed_at = |celsius: f32| {
+            self.cpu_curve
+                .iter()
+                .take_while(|point| point.temp as f32 <= celsius)
+                .last()
+                .map_or(0, |point| point.speed)
         };
-
-        // Fetch power profile and charge limit from Platform interface
-        if let Ok(proxy) = AsusPlatformProxy::new(conn).await {
-            if let Ok(profile) = proxy.platform_profile().await {
-                state.power_profile = PowerProfile::from_u32(profile);
-            }
-            if let Ok(limit) = proxy.charge_control_end_threshold().await {
-                state.charge_limit = limit;
+        let rising = speed_at(celsius);
+        match previous {
+            Some(previous) if previous > rising => {
+                previous.min(speed_at(celsius + hysteresis as f32)).max(rising)
             }
+            _ => rising,
         }
-
-        // Use default fan curve (fan curves interface may not be available)
-        state.fan_curve = FanCurve::default_curve();
-
-        let _ = self.update_tx.send(HardwareUpdate::StateRefresh(state));
     }
+}
 
-    async fn set_power_profile(&mut self, profile: PowerProfile) {
-        let Some(conn) = &self.connection else {
-            let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-                HachiError::DbusCall("Not connected to D-Bus".to_string())
-            )));
-            return;
-        };
+/// Keyboard backlight brightness levels exposed by asusd
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KbdBrightness {
+    #[default]
+    Off,
+    Low,
+    Med,
+    High,
+}
 
-        match AsusPlatformProxy::new(conn).await {
-            Ok(proxy) => {
-                if let Err(e) = proxy.set_platform_profile(profile.to_u32()).await {
-                    let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-                        HachiError::from(e)
-                    )));
-                } else {
-                    let _ = self
-                        .update_tx
-                        .send(HardwareUpdate::PowerProfileChanged(profile));
-                }
-            }
-            Err(e) => {
-                let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-                    HachiError::from(e)
-                )));
-            }
+impl KbdBrightness {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Low => "Low",
+            Self::Med => "Med",
+            Self::High => "High",
         }
     }
 
-    async fn set_charge_limit(&mut self, limit: u8) {
-        let Some(conn) = &self.connection else {
-            let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-                HachiError::DbusCall("Not connected to D-Bus".to_string())
-            )));
-            return;
-        };
-
-        let limit = limit.clamp(20, 100);
-
-        match AsusPlatformProxy::new(conn).await {
-            Ok(proxy) => {
-                if let Err(e) = proxy.set_charge_control_end_threshold(limit).await {
-                    let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-                        HachiError::from(e)
-                    )));
-                } else {
-                    let _ = self
-                        .update_tx
-                        .send(HardwareUpdate::ChargeLimitChanged(limit));
-                }
-            }
-            Err(e) => {
-                let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-                    HachiError::from(e)
-                )));
-            }
+    pub fn from_u32(val: u32) -> Self {
+        match val {
+            1 => Self::Low,
+            2 => Self::Med,
+            3 => Self::High,
+            _ => Self::Off,
         }
     }
 
-    async fn set_fan_curve(&mut self, curve: FanCurve) {
-        // Fan curves not yet supported in this asusd version
-        let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-            HachiError::InvalidFanCurve("Fan curve control not available".to_string())
-        )));
-        // Still update local state for UI feedback
-        let _ = self.update_tx.send(HardwareUpdate::FanCurveChanged(curve));
+    pub fn to_u32(self) -> u32 {
+        match self {
+            Self::Off => 0,
+            Self::Low => 1,
+            Self::Med => 2,
+            Self::High => 3,
+        }
     }
+}
 
-    async fn set_fan_curve_enabled(&mut self, _enabled: bool) {
-        // Fan curves not yet supported in this asusd version
-        let _ = self.update_tx.send(HardwareUpdate::Error(Arc::new(
-            HachiError::InvalidFanCurve("Fan curve control not available".to_string())
-        )));
+impl fmt::Display for KbdBrightness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// 24-bit color, written `#rrggbb`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Parse `#rrggbb` (the `#` is optional)
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |at: usize| u8::from_str_radix(&hex[at..at + 2], 16).ok();
+        Some(Self::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl Serialize for Rgb {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| {
+            serde::de::Error::custom(format!("invalid color {:?}, expected #rrggbb", s))
+        })
+    }
+}
+
+/// Keyboard (Aura) lighting state
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuraState {
+    pub brightness: Option<KbdBrightness>,
+    /// Seconds of inactivity before the backlight turns off (0 = never),
+    /// `None` when this asusd doesn't expose the setting
+    pub idle_timeout: Option<u32>,
+    /// Primary color of the current lighting mode, `None` when asusd's mode
+    /// data couldn't be read
+    pub color: Option<Rgb>,
+}
+
+/// asusd's `AuraEffect`: a built-in lighting mode with its colors, speed and
+/// direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, Value, OwnedValue)]
+struct AuraEffect {
+    mode: u32,
+    zone: u32,
+    colour1: AuraColour,
+    colour2: AuraColour,
+    speed: u32,
+    direction: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, Value, OwnedValue)]
+struct AuraColour {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+impl AuraEffect {
+    /// Mode number of a single static color
+    const STATIC: u32 = 0;
+    /// Medium speed, the firmware default
+    const SPEED_MED: u32 = 1;
+
+    /// One color across the whole keyboard
+    fn static_color(color: Rgb) -> Self {
+        let colour = AuraColour {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+        };
+        Self {
+            mode: Self::STATIC,
+            zone: 0,
+            colour1: colour,
+            colour2: AuraColour { r: 0, g: 0, b: 0 },
+            speed: Self::SPEED_MED,
+            direction: 0,
+        }
+    }
+
+    fn color(&self) -> Rgb {
+        Rgb::new(self.colour1.r, self.colour1.g, self.colour1.b)
+    }
+}
+
+/// System states the AniMe Matrix can be shown or blanked in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimeBinding {
+    /// asusd's built-in boot and shutdown animations
+    BootShutdown,
+    Sleep,
+    LidClosed,
+}
+
+impl AnimeBinding {
+    pub const ALL: [AnimeBinding; 3] = [Self::BootShutdown, Self::Sleep, Self::LidClosed];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BootShutdown => "Boot/shutdown",
+            Self::Sleep => "Sleep",
+            Self::LidClosed => "Lid closed",
+        }
+    }
+}
+
+/// AniMe Matrix display settings; `None` when asusd lacks the property
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnimeState {
+    pub boot_shutdown: Option<bool>,
+    pub sleep: Option<bool>,
+    pub lid_closed: Option<bool>,
+}
+
+impl AnimeState {
+    /// Whether the display is shown in a system state
+    pub fn shown(&self, binding: AnimeBinding) -> Option<bool> {
+        match binding {
+            AnimeBinding::BootShutdown => self.boot_shutdown,
+            AnimeBinding::Sleep => self.sleep,
+            AnimeBinding::LidClosed => self.lid_closed,
+        }
+    }
+
+    pub fn set_shown(&mut self, binding: AnimeBinding, shown: bool) {
+        let slot = match binding {
+            AnimeBinding::BootShutdown => &mut self.boot_shutdown,
+            AnimeBinding::Sleep => &mut self.sleep,
+            AnimeBinding::LidClosed => &mut self.lid_closed,
+        };
+        *slot = Some(shown);
+    }
+}
+
+/// Built-in Slash lid lighting patterns, in asusd's order
+pub const SLASH_MODES: [&str; 15] = [
+    "Bounce",
+    "Slash",
+    "Loading",
+    "BitStream",
+    "Transmission",
+    "Flow",
+    "Flux",
+    "Phantom",
+    "Spectrum",
+    "Hazard",
+    "Interfacing",
+    "Ramp",
+    "GameOver",
+    "Start",
+    "Buzzer",
+];
+
+/// Slash lid lighting settings (2024+ models); `None` when asusd lacks the property
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlashState {
+    pub enabled: Option<bool>,
+    pub mode: Option<String>,
+    pub brightness: Option<u8>,
+    /// Animation interval, 0 (fastest) to 5
+    pub interval: Option<u8>,
+}
+
+impl SlashState {
+    pub fn apply(&mut self, change: &SlashChange) {
+        match change {
+            SlashChange::Enabled(enabled) => self.enabled = Some(*enabled),
+            SlashChange::Mode(mode) => self.mode = Some(mode.to_string()),
+            SlashChange::Brightness(brightness) => self.brightness = Some(*brightness),
+            SlashChange::Interval(interval) => self.interval = Some(*interval),
+        }
+    }
+}
+
+/// A single Slash setting change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlashChange {
+    Enabled(bool),
+    Mode(&'static str),
+    Brightness(u8),
+    Interval(u8),
+}
+
+/// Which GPU drives the internal panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GpuMode {
+    /// iGPU drives the panel, dGPU renders on demand
+    Hybrid,
+    /// MUX switched: the dGPU drives the panel directly
+    Discrete,
+}
+
+impl GpuMode {
+    /// From the firmware `gpu_mux_mode` value
+    pub fn from_mux(value: u8) -> Self {
+        if value == 0 {
+            Self::Discrete
+        } else {
+            Self::Hybrid
+        }
+    }
+
+    /// The firmware `gpu_mux_mode` value
+    pub fn to_mux(self) -> u8 {
+        match self {
+            Self::Discrete => 0,
+            Self::Hybrid => 1,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hybrid => "Hybrid",
+            Self::Discrete => "Discrete",
+        }
+    }
+}
+
+/// Inclusive range of values a setting accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueRange {
+    pub min: u8,
+    pub max: u8,
+}
+
+impl ValueRange {
+    pub fn clamp(self, value: u8) -> u8 {
+        value.clamp(self.min, self.max)
+    }
+}
+
+/// Package power limits a profile applies, in watts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerLimits {
+    /// Sustained limit (PL1 / SPL)
+    pub sustained: u8,
+    /// Short boost limit (PL2 / SPPT), when exposed
+    pub boost: Option<u8>,
+}
+
+/// Fan hysteresis the fan panel and config accept, in degrees Celsius
+pub const FAN_HYSTERESIS_RANGE: ValueRange = ValueRange { min: 0, max: 10 };
+
+/// Charge limits asusd accepts; neither asusd nor the battery driver
+/// publishes a per-model range
+pub const CHARGE_LIMIT_RANGE: ValueRange = ValueRange { min: 20, max: 100 };
+
+/// Where hardware state is read from and written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// asusd over D-Bus
+    #[default]
+    Asusd,
+    /// Kernel interfaces directly (platform_profile, power_supply, asus-armoury)
+    Kernel,
+}
+
+/// Why asusd can't be reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DisconnectReason {
+    /// The system bus socket is missing or refused the connection
+    NoSystemBus,
+    /// asusd isn't running and D-Bus can't activate it
+    AsusdMissing,
+    /// asusd is running but doesn't export `xyz.ljones.Platform`
+    /// (older releases used `org.asuslinux.Daemon`)
+    InterfaceMissing,
+    /// Bus policy denied access to asusd
+    PermissionDenied,
+}
+
+impl DisconnectReason {
+    /// Short label for the status bar
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::NoSystemBus => "No D-Bus",
+            Self::AsusdMissing => "asusd missing",
+            Self::InterfaceMissing => "No interface",
+            Self::PermissionDenied => "Access denied",
+        }
+    }
+
+    /// Classify a D-Bus error, or `None` when it says nothing about
+    /// connectivity (e.g. a rejected value)
+    pub fn from_error(e: &zbus::Error) -> Option<Self> {
+        use zbus::fdo::Error as Fdo;
+
+        match e {
+            zbus::Error::Address(_) | zbus::Error::Handshake(_) => Some(Self::NoSystemBus),
+            zbus::Error::InputOutput(io) if io.kind() == std::io::ErrorKind::PermissionDenied => {
+                Some(Self::PermissionDenied)
+            }
+            zbus::Error::InputOutput(_) => Some(Self::NoSystemBus),
+            zbus::Error::InterfaceNotFound => Some(Self::InterfaceMissing),
+            zbus::Error::FDO(e) => match **e {
+                Fdo::AccessDenied(_) => Some(Self::PermissionDenied),
+                Fdo::ServiceUnknown(_) | Fdo::NameHasNoOwner(_) => Some(Self::AsusdMissing),
+                Fdo::UnknownInterface(_)
+                | Fdo::UnknownObject(_)
+                | Fdo::UnknownProperty(_)
+                | Fdo::UnknownMethod(_) => Some(Self::InterfaceMissing),
+                _ => None,
+            },
+            zbus::Error::MethodError(name, _, _) => {
+                match name.as_str().strip_prefix("org.freedesktop.DBus.Error.")? {
+                    "AccessDenied" => Some(Self::PermissionDenied),
+                    "ServiceUnknown" | "NameHasNoOwner" => Some(Self::AsusdMissing),
+                    "UnknownInterface" | "UnknownObject" | "UnknownProperty" | "UnknownMethod" => {
+                        Some(Self::InterfaceMissing)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            Self::NoSystemBus => "the system D-Bus is not reachable",
+            Self::AsusdMissing => "asusd is not running and cannot be started",
+            Self::InterfaceMissing => {
+                "asusd does not provide xyz.ljones.Platform (version mismatch?)"
+            }
+            Self::PermissionDenied => "D-Bus policy denied access to asusd",
+        };
+        f.write_str(reason)
+    }
+}
+
+/// asusd release, from the Platform interface's `Version` property
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AsusdVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+/// Oldest asusd release hachi was written against
+pub const ASUSD_OLDEST: AsusdVersion = AsusdVersion::new(6, 1, 0);
+
+/// Newest asusd series hachi was written against; any 6.1.x release
+pub const ASUSD_NEWEST: AsusdVersion = AsusdVersion::new(6, 1, u16::MAX);
+
+/// Panels reading asusd properties, with the release those appeared in
+const ASUSD_PANELS: [(&str, AsusdVersion); 4] = [
+    ("Power profile", AsusdVersion::new(6, 0, 0)),
+    ("Battery", AsusdVersion::new(6, 0, 0)),
+    // Backlight idle timeout
+    ("Lighting", AsusdVersion::new(6, 1, 0)),
+    // Slash interface
+    ("Lid lighting", AsusdVersion::new(6, 1, 0)),
+];
+
+impl AsusdVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parse `6.1.12`, ignoring a leading `v` and any `-suffix` (distro
+    /// packages report e.g. `6.1.12-1`). A missing patch counts as 0.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = version.split(['-', '+']).next()?;
+        let mut parts = version.split('.').map(str::parse::<u16>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next()?.ok()?;
+        let patch = parts.next().transpose().ok()?.unwrap_or(0);
+        Some(Self::new(major, minor, patch))
+    }
+
+    /// Panels that may misbehave under this release, `None` within the
+    /// range hachi was written against. Older releases lack the newer
+    /// panels' properties; newer ones may have renamed any of them.
+    pub fn untested_panels(self) -> Option<Vec<&'static str>> {
+        if self < ASUSD_OLDEST {
+            let panels = ASUSD_PANELS.iter().filter(|(_, since)| self < *since);
+            Some(panels.map(|(panel, _)| *panel).collect())
+        } else if self > ASUSD_NEWEST {
+            Some(ASUSD_PANELS.iter().map(|(panel, _)| *panel).collect())
+        } else {
+            None
+        }
+    }
+
+    /// Warning to show when this release is outside the tested range
+    pub fn compatibility_warning(self) -> Option<String> {
+        let panels = self.untested_panels()?.join(", ");
+        let newest = ASUSD_NEWEST;
+        let range = format!("{} to {}.{}.x", ASUSD_OLDEST, newest.major, newest.minor);
+        let age = if self < ASUSD_OLDEST { "older" } else { "newer" };
+        Some(format!(
+            "asusd {} is {} than hachi supports ({}), these panels may misbehave: {}",
+            self, age, range, panels
+        ))
+    }
+}
+
+impl fmt::Display for AsusdVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Current hardware state snapshot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HardwareState {
+    pub power_profile: PowerProfile,
+    pub charge_limit: u8,
+    /// Bypass ("sustain") charging, `None` when unsupported
+    pub charge_bypass: Option<bool>,
+    pub fan_curve: FanCurve,
+    /// Whether asusd can write custom fan curves on this model
+    pub fan_curve_control: bool,
+    /// Keyboard lighting, `None` when no Aura device is present
+    pub aura: Option<AuraState>,
+    /// AniMe Matrix, `None` when the laptop has no matrix display
+    pub anime: Option<AnimeState>,
+    /// Slash lid lighting, `None` when the laptop has no Slash bar
+    pub slash: Option<SlashState>,
+    /// Fn-lock and touchpad switches, `None` entries are unavailable
+    pub fn_lock: Option<bool>,
+    pub touchpad: Option<bool>,
+    /// Display backlight, `None` when no backlight device exists
+    pub backlight: Option<Backlight>,
+    /// Sustained package power limit in watts, `None` when not exposed
+    pub tdp: Option<u8>,
+    /// Short boost power limit in watts, `None` when not exposed
+    pub tdp_boost: Option<u8>,
+    /// Accepted sustained power limits, when the firmware reports them
+    pub tdp_range: Option<ValueRange>,
+    /// GPU MUX position, `None` on laptops without a MUX
+    pub gpu_mode: Option<GpuMode>,
+    pub backend: Backend,
+    pub connected: bool,
+    /// Why `connected` is false, once a connection attempt has failed
+    pub disconnect_reason: Option<DisconnectReason>,
+    /// asusd release, when it reports one
+    pub asusd_version: Option<AsusdVersion>,
+}
+
+impl HardwareState {
+    /// Power limits in effect under the current profile
+    pub fn power_limits(&self) -> Option<PowerLimits> {
+        Some(PowerLimits {
+            sustained: self.tdp?,
+            boost: self.tdp_boost,
+        })
+    }
+
+    /// Current state of a peripheral switch
+    pub fn peripheral(&self, peripheral: Peripheral) -> Option<bool> {
+        match peripheral {
+            Peripheral::FnLock => self.fn_lock,
+            Peripheral::Touchpad => self.touchpad,
+        }
+    }
+
+    fn peripheral_mut(&mut self, peripheral: Peripheral) -> &mut Option<bool> {
+        match peripheral {
+            Peripheral::FnLock => &mut self.fn_lock,
+            Peripheral::Touchpad => &mut self.touchpad,
+        }
+    }
+
+    /// Fold an incremental update into the state
+    pub fn apply(&mut self, update: &HardwareUpdate) {
+        match update {
+            HardwareUpdate::StateRefresh(state) => *self = state.clone(),
+            HardwareUpdate::PowerProfileChanged(profile)
+            | HardwareUpdate::External(DaemonSignal::PowerProfile(profile)) => {
+                self.power_profile = *profile;
+            }
+            HardwareUpdate::ChargeLimitChanged(limit)
+            | HardwareUpdate::External(DaemonSignal::ChargeLimit(limit)) => {
+                self.charge_limit = *limit;
+            }
+            HardwareUpdate::ChargeBypassChanged(enabled) => self.charge_bypass = Some(*enabled),
+            HardwareUpdate::FanCurveChanged(profile, curve)
+            | HardwareUpdate::FanCurveLoaded(profile, curve) => {
+                // Other profiles' curves live in asusd until they're active
+                if *profile == self.power_profile {
+                    self.fan_curve = curve.clone();
+                }
+            }
+            HardwareUpdate::FanCurveEnabledChanged(enabled) => {
+                self.fan_curve.enabled = *enabled;
+                for fan in Fan::ALL {
+                    self.fan_curve.enablement.set(self.power_profile, fan, *enabled);
+                }
+            }
+            HardwareUpdate::FanEnabledChanged(profile, fan, enabled) => {
+                let enablement = &mut self.fan_curve.enablement;
+                enablement.set(*profile, *fan, *enabled);
+                if *profile == self.power_profile {
+                    self.fan_curve.enabled = enablement.any(*profile);
+                }
+            }
+            HardwareUpdate::KbdIdleTimeoutChanged(seconds) => {
+                if let Some(aura) = &mut self.aura {
+                    aura.idle_timeout = Some(*seconds);
+                }
+            }
+            HardwareUpdate::KbdBrightnessChanged(brightness)
+            | HardwareUpdate::External(DaemonSignal::KbdBrightness(brightness)) => {
+                if let Some(aura) = &mut self.aura {
+                    aura.brightness = Some(*brightness);
+                }
+            }
+            HardwareUpdate::KbdColorChanged(color) => {
+                if let Some(aura) = &mut self.aura {
+                    aura.color = Some(*color);
+                }
+            }
+            HardwareUpdate::PeripheralChanged(peripheral, enabled) => {
+                *self.peripheral_mut(*peripheral) = Some(*enabled);
+            }
+            HardwareUpdate::DisplayBrightnessChanged(brightness) => {
+                if let Some(backlight) = &mut self.backlight {
+                    backlight.brightness = *brightness;
+                }
+            }
+            HardwareUpdate::AnimeBindingChanged(binding, shown) => {
+                if let Some(anime) = &mut self.anime {
+                    anime.set_shown(*binding, *shown);
+                }
+            }
+            HardwareUpdate::SlashChanged(change) => {
+                if let Some(slash) = &mut self.slash {
+                    slash.apply(change);
+                }
+            }
+            HardwareUpdate::TdpChanged(watts) => self.tdp = Some(*watts),
+            HardwareUpdate::PowerLimitsChanged(_, limits) => {
+                self.tdp = Some(limits.sustained);
+                self.tdp_boost = limits.boost;
+            }
+            HardwareUpdate::GpuModeChanged(mode) => self.gpu_mode = Some(*mode),
+            HardwareUpdate::Connected => {
+                self.connected = true;
+                self.disconnect_reason = None;
+            }
+            HardwareUpdate::Disconnected(reason) => {
+                self.connected = false;
+                self.disconnect_reason = Some(*reason);
+            }
+            HardwareUpdate::Retrying(_)
+            | HardwareUpdate::Health(_)
+            | HardwareUpdate::RawReply(_)
+            | HardwareUpdate::External(
+                DaemonSignal::Idle(_)
+                | DaemonSignal::LidClosed(_)
+                | DaemonSignal::Docked(_)
+                | DaemonSignal::Resumed,
+            )
+            | HardwareUpdate::Error(_) => {}
+            HardwareUpdate::RolledBack(rollback) => match rollback {
+                Rollback::PowerProfile(profile) => self.power_profile = *profile,
+                Rollback::ChargeLimit(limit) => self.charge_limit = *limit,
+                Rollback::FanCurve(curve) => self.fan_curve = curve.clone(),
+                Rollback::DisplayBrightness(brightness) => {
+                    if let Some(backlight) = &mut self.backlight {
+                        backlight.brightness = *brightness;
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Intents sent from UI to Hardware Actor
+#[derive(Debug, Clone, PartialEq)]
+pub enum HardwareIntent {
+    /// Request current state refresh
+    RefreshState,
+    /// Check the bus, asusd and its interfaces for the health checklist
+    CheckHealth,
+    /// Set power profile
+    SetPowerProfile(PowerProfile),
+    /// Set battery charge limit (0-100)
+    SetChargeLimit(u8),
+    /// Enable/disable bypass charging (run from AC without cycling the battery)
+    SetChargeBypass(bool),
+    /// Write a profile's custom fan curves
+    SetFanCurve(PowerProfile, FanCurve),
+    /// Enable/disable custom fan curves
+    SetFanCurveEnabled(bool),
+    /// Enable/disable one fan's custom curve in a profile
+    SetFanEnabled(PowerProfile, Fan, bool),
+    /// Set keyboard backlight idle timeout in seconds (0 = never)
+    SetKbdIdleTimeout(u32),
+    /// Set keyboard backlight brightness
+    SetKbdBrightness(KbdBrightness),
+    /// Light the whole keyboard in one static color
+    SetKbdColor(Rgb),
+    /// Toggle a peripheral switch (Fn-lock, touchpad)
+    SetPeripheral(Peripheral, bool),
+    /// Set display backlight brightness (raw device value)
+    SetDisplayBrightness(u32),
+    /// Show or blank the AniMe Matrix in a system state
+    SetAnimeBinding(AnimeBinding, bool),
+    /// Change a Slash lid lighting setting
+    SetSlash(SlashChange),
+    /// Set package power limits (watts)
+    SetTdp(u8),
+    /// Move the GPU MUX (takes effect after a reboot)
+    SetGpuMode(GpuMode),
+    /// Keep the system from suspending for the reason given, until sent
+    /// again with `None`
+    InhibitSleep(Option<&'static str>),
+    /// Console command sent to asusd as typed (`--debug-console`)
+    RawCall(RawCall),
+    /// Shutdown the actor
+    Shutdown,
+}
+
+/// Sequence number `DaemonHandle` assigns to each intent it sends
+pub type Generation = u64;
+
+/// The setting an intent writes; a newer intent for the same slot
+/// supersedes older ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntentSlot(Discriminant<HardwareIntent>, u8);
+
+impl HardwareIntent {
+    /// Slot this intent writes, `None` for intents that aren't writes. A
+    /// sleep inhibitor isn't one either: a hold and a release both have to
+    /// run, in order, and read-only mode keeps them.
+    pub fn slot(&self) -> Option<IntentSlot> {
+        let target = match self {
+            Self::RefreshState
+            | Self::CheckHealth
+            | Self::InhibitSleep(_)
+            | Self::RawCall(_)
+            | Self::Shutdown => return None,
+            Self::SetPeripheral(peripheral, _) => *peripheral as u8,
+            Self::SetAnimeBinding(binding, _) => *binding as u8,
+            Self::SetFanCurve(profile, _) => profile.ui_index() as u8,
+            Self::SetFanEnabled(profile, fan, _) => (profile.ui_index() * 2 + *fan as usize) as u8,
+            Self::SetSlash(SlashChange::Enabled(_)) => 0,
+            Self::SetSlash(SlashChange::Mode(_)) => 1,
+            Self::SetSlash(SlashChange::Brightness(_)) => 2,
+            Self::SetSlash(SlashChange::Interval(_)) => 3,
+            _ => 0,
+        };
+        Some(IntentSlot(mem::discriminant(self), target))
+    }
+
+    /// Whether the intent can change a setting, dropped in read-only mode
+    pub fn writes(&self) -> bool {
+        match self {
+            Self::RawCall(call) => call.writes(),
+            _ => self.slot().is_some(),
+        }
+    }
+}
+
+/// Drop intents overridden by a later write to the same slot in the batch,
+/// then identical neighbours (repeated refreshes)
+pub fn supersede(batch: Vec<(Generation, HardwareIntent)>) -> Vec<(Generation, HardwareIntent)> {
+    let slots: Vec<_> = batch.iter().map(|(_, intent)| intent.slot()).collect();
+    let mut kept: Vec<(Generation, HardwareIntent)> = batch
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| match slots[*i] {
+            Some(slot) => !slots[i + 1..].contains(&Some(slot)),
+            None => true,
+        })
+        .map(|(_, entry)| entry)
+        .collect();
+    kept.dedup_by(|a, b| a.1 == b.1);
+    kept
+}
+
+/// An update tagged with the intent that produced it
+#[derive(Debug, Clone)]
+pub struct TaggedUpdate {
+    pub generation: Generation,
+    /// Slot of the intent being executed, `None` for refreshes and signals
+    pub slot: Option<IntentSlot>,
+    pub update: HardwareUpdate,
+}
+
+/// Updates broadcast from Hardware Actor to UI
+#[derive(Debug, Clone)]
+pub enum HardwareUpdate {
+    /// Full state refresh
+    StateRefresh(HardwareState),
+    /// Power profile changed
+    PowerProfileChanged(PowerProfile),
+    /// Charge limit changed
+    ChargeLimitChanged(u8),
+    /// Bypass charging toggled
+    ChargeBypassChanged(bool),
+    /// A profile's fan curves written
+    FanCurveChanged(PowerProfile, FanCurve),
+    /// Fan curves asusd keeps for the profile just switched to
+    FanCurveLoaded(PowerProfile, FanCurve),
+    /// Custom fan curves switched on or off for the active profile
+    FanCurveEnabledChanged(bool),
+    /// One fan's custom curve switched on or off in a profile
+    FanEnabledChanged(PowerProfile, Fan, bool),
+    /// Keyboard backlight idle timeout changed
+    KbdIdleTimeoutChanged(u32),
+    /// Keyboard backlight brightness changed
+    KbdBrightnessChanged(KbdBrightness),
+    /// Keyboard switched to a static color
+    KbdColorChanged(Rgb),
+    /// Peripheral switch toggled
+    PeripheralChanged(Peripheral, bool),
+    /// Display backlight brightness changed (raw device value)
+    DisplayBrightnessChanged(u32),
+    /// AniMe Matrix system-state binding changed
+    AnimeBindingChanged(AnimeBinding, bool),
+    /// Slash lid lighting setting changed
+    SlashChanged(SlashChange),
+    /// Package power limits changed (watts)
+    TdpChanged(u8),
+    /// Power limits the firmware applied after switching to a profile
+    PowerLimitsChanged(PowerProfile, PowerLimits),
+    /// GPU MUX moved, pending a reboot
+    GpuModeChanged(GpuMode),
+    /// Number of failed writes waiting to be retried
+    Retrying(usize),
+    /// Bus, asusd and interface checks for the health checklist
+    Health(Vec<Check>),
+    /// Reached asusd (or fell back to the kernel interfaces)
+    Connected,
+    /// Lost or couldn't establish the connection to asusd
+    Disconnected(DisconnectReason),
+    /// A setting changed outside hachi (hotkey, asusctl, another app)
+    External(DaemonSignal),
+    /// Error occurred
+    Error(Arc<HachiError>),
+    /// asusd's reply to a console command, as text
+    RawReply(Result<String, Arc<HachiError>>),
+    /// A write failed for good; the setting is back at its last confirmed
+    /// value, undoing the UI's optimistic update
+    RolledBack(Rollback),
+}
+
+/// Last confirmed value of a setting the UI updates optimistically
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rollback {
+    PowerProfile(PowerProfile),
+    ChargeLimit(u8),
+    FanCurve(FanCurve),
+    DisplayBrightness(u32),
+}
+
+impl Rollback {
+    /// The value `intent` is about to overwrite, for settings the UI shows
+    /// before the write is confirmed
+    pub fn confirmed(intent: &HardwareIntent, state: &HardwareState) -> Option<Self> {
+        match intent {
+            HardwareIntent::SetPowerProfile(_) => Some(Self::PowerProfile(state.power_profile)),
+            HardwareIntent::SetChargeLimit(_) => Some(Self::ChargeLimit(state.charge_limit)),
+            HardwareIntent::SetFanCurve(profile, _) if *profile == state.power_profile => {
+                Some(Self::FanCurve(state.fan_curve.clone()))
+            }
+            HardwareIntent::SetDisplayBrightness(_) => {
+                let backlight = state.backlight.as_ref()?;
+                Some(Self::DisplayBrightness(backlight.brightness))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Property change notification from asusd
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonSignal {
+    PowerProfile(PowerProfile),
+    ChargeLimit(u8),
+    KbdBrightness(KbdBrightness),
+    /// logind idle hint: idle since this many microseconds after the epoch,
+    /// or `None` once the user is active again
+    Idle(Option<u64>),
+    /// logind lid switch state
+    LidClosed(bool),
+    /// logind dock state (a dock or more than one display connected)
+    Docked(bool),
+    /// The system woke from suspend or hibernation
+    Resumed,
+}
+
+/// Attempts after the first for a write that failed transiently
+const MAX_WRITE_RETRIES: u32 = 3;
+/// Delay before the first retry, doubling each attempt
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How long to wait for asusd to echo one of our own writes
+const ECHO_WINDOW: Duration = Duration::from_secs(2);
+
+/// How long a write waits for room in a full intent queue before it's
+/// dropped
+const INTENT_SEND_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Minimum spacing between writes to the same slot, so holding a key
+/// doesn't hammer the EC and firmware with repeated writes
+fn write_interval(intent: &HardwareIntent) -> Option<Duration> {
+    match intent {
+        HardwareIntent::SetChargeLimit(_)
+        | HardwareIntent::SetTdp(_)
+        | HardwareIntent::SetFanCurve(..) => Some(Duration::from_secs(1)),
+        HardwareIntent::SetPowerProfile(_) => Some(Duration::from_millis(500)),
+        _ => None,
+    }
+}
+
+/// Run a kernel attribute write on the blocking pool: a write that needs
+/// the privileged helper waits on pkexec and its polkit prompt, which would
+/// otherwise stall the actor and every update behind it
+async fn blocking_write<F>(write: F) -> Result<(), HachiError>
+where
+    F: FnOnce() -> Result<(), HachiError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(write)
+        .await
+        .unwrap_or_else(|e| Err(HachiError::Elevation(format!("write task failed: {}", e))))
+}
+
+// =============================================================================
+// D-Bus Proxy Definitions for org.asuslinux.Daemon
+// =============================================================================
+
+#[proxy(
+    interface = "xyz.ljones.Platform",
+    default_service = "xyz.ljones.Asusd",
+    default_path = "/xyz/ljones"
+)]
+trait AsusPlatform {
+    /// Get the current platform profile (0=Quiet, 1=Balanced, 2=Performance, 3=?)
+    #[zbus(property)]
+    fn platform_profile(&self) -> zbus::Result<u32>;
+
+    /// Set the platform profile
+    #[zbus(property)]
+    fn set_platform_profile(&self, profile: u32) -> zbus::Result<()>;
+
+    /// Get charge control end threshold (battery limit)
+    #[zbus(property)]
+    fn charge_control_end_threshold(&self) -> zbus::Result<u8>;
+
+    /// Set charge control end threshold
+    #[zbus(property)]
+    fn set_charge_control_end_threshold(&self, limit: u8) -> zbus::Result<()>;
+
+    /// Cycle to next platform profile
+    fn next_platform_profile(&self) -> zbus::Result<()>;
+
+    /// asusd release, e.g. `"6.1.12"`
+    #[zbus(property)]
+    fn version(&self) -> zbus::Result<String>;
+}
+
+/// Keyboard lighting; one object per Aura device under `/xyz/ljones/aura/`
+#[proxy(interface = "xyz.ljones.Aura", default_service = "xyz.ljones.Asusd")]
+trait AsusAura {
+    /// Keyboard brightness (0=Off, 1=Low, 2=Med, 3=High)
+    #[zbus(property)]
+    fn brightness(&self) -> zbus::Result<u32>;
+
+    /// Set keyboard brightness
+    #[zbus(property)]
+    fn set_brightness(&self, brightness: u32) -> zbus::Result<()>;
+
+    /// Backlight idle timeout in seconds (only on newer asusd)
+    #[zbus(property)]
+    fn idle_timeout(&self) -> zbus::Result<u32>;
+
+    /// Set backlight idle timeout
+    #[zbus(property)]
+    fn set_idle_timeout(&self, seconds: u32) -> zbus::Result<()>;
+
+    /// Current lighting mode and its colors
+    #[zbus(property)]
+    fn led_mode_data(&self) -> zbus::Result<AuraEffect>;
+
+    /// Switch lighting mode, stored by asusd and restored on boot
+    #[zbus(property)]
+    fn set_led_mode_data(&self, effect: AuraEffect) -> zbus::Result<()>;
+}
+
+/// AniMe Matrix lid display, exported under `/xyz/ljones/` on supported models
+#[proxy(interface = "xyz.ljones.Anime", default_service = "xyz.ljones.Asusd")]
+trait AsusAnime {
+    /// Whether the built-in boot/shutdown animations are used
+    #[zbus(property)]
+    fn builtins_enabled(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn set_builtins_enabled(&self, enabled: bool) -> zbus::Result<()>;
+
+    /// Blank the display while suspended
+    #[zbus(property)]
+    fn off_when_suspended(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn set_off_when_suspended(&self, off: bool) -> zbus::Result<()>;
+
+    /// Blank the display while the lid is closed
+    #[zbus(property)]
+    fn off_when_lid_closed(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn set_off_when_lid_closed(&self, off: bool) -> zbus::Result<()>;
+}
+
+/// Slash lid lighting bar on 2024+ models, replacing the AniMe Matrix
+#[proxy(interface = "xyz.ljones.Slash", default_service = "xyz.ljones.Asusd")]
+trait AsusSlash {
+    #[zbus(property)]
+    fn enabled(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn set_enabled(&self, enabled: bool) -> zbus::Result<()>;
+
+    /// Built-in pattern name, e.g. `"Bounce"`
+    #[zbus(property)]
+    fn mode(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn set_mode(&self, mode: &str) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn brightness(&self) -> zbus::Result<u8>;
+
+    #[zbus(property)]
+    fn set_brightness(&self, brightness: u8) -> zbus::Result<()>;
+
+    /// Animation interval, 0 (fastest) to 5
+    #[zbus(property)]
+    fn interval(&self) -> zbus::Result<u8>;
+
+    #[zbus(property)]
+    fn set_interval(&self, interval: u8) -> zbus::Result<()>;
+}
+
+/// Custom fan curves, kept by asusd per power profile
+#[proxy(interface = "xyz.ljones.FanCurves", default_service = "xyz.ljones.Asusd")]
+trait AsusFanCurves {
+    /// Each fan's curve in `profile` (asusd numbering)
+    fn fan_curve_data(&self, profile: u32) -> zbus::Result<Vec<CurveData>>;
+
+    /// Store one fan's curve for `profile`, applied at once if it's active
+    fn set_fan_curve(&self, profile: u32, curve: CurveData) -> zbus::Result<()>;
+
+    /// Switch every custom curve of `profile` (asusd numbering) on or off
+    fn set_fan_curves_enabled(&self, profile: u32, enabled: bool) -> zbus::Result<()>;
+
+    /// Switch one fan's curve of `profile` on or off; `fan` as in `Fan::as_str`
+    fn set_profile_fan_curve_enabled(
+        &self,
+        profile: u32,
+        fan: &str,
+        enabled: bool,
+    ) -> zbus::Result<()>;
+}
+
+/// The caller's logind session, used to set the backlight without root
+#[proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait LogindSession {
+    /// Set brightness of a `backlight` or `leds` device
+    fn set_brightness(&self, subsystem: &str, name: &str, brightness: u32) -> zbus::Result<()>;
+
+    /// Set by the desktop once the user has been inactive for a while
+    #[zbus(property)]
+    fn idle_hint(&self) -> zbus::Result<bool>;
+
+    /// When the idle hint last changed (microseconds since the epoch)
+    #[zbus(property)]
+    fn idle_since_hint(&self) -> zbus::Result<u64>;
+}
+
+/// logind's seat manager, for lid and dock state
+#[proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+trait LogindManager {
+    #[zbus(property)]
+    fn lid_closed(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn docked(&self) -> zbus::Result<bool>;
+
+    /// Take an inhibitor lock, held until the returned descriptor is closed
+    fn inhibit(&self, what: &str, who: &str, why: &str, mode: &str) -> zbus::Result<OwnedFd>;
+
+    /// Sent with `true` before suspending and `false` after resuming
+    #[zbus(signal)]
+    fn prepare_for_sleep(&self, start: bool) -> zbus::Result<()>;
+}
+
+const PLATFORM_INTERFACE: &str = "xyz.ljones.Platform";
+const AURA_INTERFACE: &str = "xyz.ljones.Aura";
+const ANIME_INTERFACE: &str = "xyz.ljones.Anime";
+const SLASH_INTERFACE: &str = "xyz.ljones.Slash";
+const FAN_CURVES_INTERFACE: &str = "xyz.ljones.FanCurves";
+
+/// Find the first object exported by asusd that implements `interface`
+async fn find_object_path(conn: &Connection, interface: &str) -> Option<OwnedObjectPath> {
+    let manager = zbus::fdo::ObjectManagerProxy::builder(conn)
+        .destination("xyz.ljones.Asusd")
+        .ok()?
+        .path("/")
+        .ok()?
+        .build()
+        .await
+        .ok()?;
+    let objects = manager.get_managed_objects().await.ok()?;
+
+    let mut paths: Vec<OwnedObjectPath> = objects
+        .into_iter()
+        .filter(|(_, interfaces)| interfaces.keys().any(|i| i.as_str() == interface))
+        .map(|(path, _)| path)
+        .collect();
+    paths.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    paths.into_iter().next()
+}
+
+/// Check that asusd is running (or activatable) and exports the Platform
+/// interface, reading a property to prove it
+async fn probe_asusd(conn: &Connection) -> std::result::Result<(), DisconnectReason> {
+    let classify = |e: zbus::Error| {
+        DisconnectReason::from_error(&e).unwrap_or(DisconnectReason::NoSystemBus)
+    };
+
+    let dbus = zbus::fdo::DBusProxy::new(conn).await.map_err(classify)?;
+    let name = zbus::names::BusName::try_from("xyz.ljones.Asusd").map_err(|e| classify(e.into()))?;
+    let running = dbus.name_has_owner(name).await.map_err(|e| classify(e.into()))?;
+    if !running {
+        let activatable = dbus.list_activatable_names().await.map_err(|e| classify(e.into()))?;
+        if !activatable.iter().any(|n| n.as_str() == "xyz.ljones.Asusd") {
+            return Err(DisconnectReason::AsusdMissing);
+        }
+    }
+
+    let proxy = AsusPlatformProxy::new(conn).await.map_err(classify)?;
+    proxy.platform_profile().await.map(|_| ()).map_err(|e| {
+        DisconnectReason::from_error(&e).unwrap_or(DisconnectReason::InterfaceMissing)
+    })
+}
+
+/// Kernel attributes read the same way regardless of backend
+fn read_kernel_extras(state: &mut HardwareState) {
+    state.charge_bypass = sysfs::charge_bypass();
+    state.fn_lock = Peripheral::FnLock.read();
+    state.touchpad = Peripheral::Touchpad.read();
+    state.backlight = sysfs::backlight();
+    state.tdp = sysfs::tdp();
+    state.tdp_boost = sysfs::tdp_boost();
+    state.tdp_range = sysfs::tdp_range().map(|(min, max)| ValueRange { min, max });
+    state.gpu_mode = sysfs::gpu_mux_mode().map(GpuMode::from_mux);
+}
+
+// =============================================================================
+// Hardware Actor Implementation
+// =============================================================================
+
+pub struct HardwareActor {
+    intent_rx: mpsc::Receiver<(Generation, HardwareIntent)>,
+    update_tx: broadcast::Sender<TaggedUpdate>,
+    /// Authoritative latest state, kept in step with every update sent
+    state_tx: watch::Sender<HardwareState>,
+    connection: Option<Connection>,
+    aura_path: Option<OwnedObjectPath>,
+    anime_path: Option<OwnedObjectPath>,
+    slash_path: Option<OwnedObjectPath>,
+    fan_curves_path: Option<OwnedObjectPath>,
+    backend: Backend,
+    /// Signals expected as echoes of our own writes, so they aren't
+    /// reported as external changes
+    expected_signals: Vec<(DaemonSignal, Instant)>,
+    /// When each rate-limited slot was last written
+    last_write: HashMap<IntentSlot, Instant>,
+    /// Rate-limited writes waiting for their interval, with when they're due
+    deferred: Vec<(Generation, HardwareIntent, Instant)>,
+    /// Intent being executed, used to tag the updates it produces
+    current: (Generation, Option<IntentSlot>),
+    /// Writes that failed transiently (e.g. asusd restarting), waiting to retry
+    retries: Vec<Retry>,
+    /// Retry count last reported to the UI
+    reported_retries: usize,
+    /// Whether the running write may still be retried, in which case a
+    /// transient error is held in `failure` instead of being published
+    retryable: bool,
+    failure: Option<Arc<HachiError>>,
+    /// Whether the running write has published an error
+    write_failed: bool,
+    /// How long the last intent sent to asusd took, in microseconds
+    latency: Arc<AtomicU64>,
+    /// Bus to find asusd on instead of the system bus (tests run a private one)
+    bus_address: Option<String>,
+    /// Sleep inhibitor taken for the UI (a tune run), closed on release
+    sleep_lock: Option<OwnedFd>,
+}
+
+/// A failed write scheduled for another attempt
+struct Retry {
+    generation: Generation,
+    intent: HardwareIntent,
+    attempt: u32,
+    due: Instant,
+}
+
+impl HardwareActor {
+    pub fn new(
+        intent_rx: mpsc::Receiver<(Generation, HardwareIntent)>,
+        update_tx: broadcast::Sender<TaggedUpdate>,
+        state_tx: watch::Sender<HardwareState>,
+        latency: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            intent_rx,
+            update_tx,
+            state_tx,
+            connection: None,
+            aura_path: None,
+            anime_path: None,
+            slash_path: None,
+            fan_curves_path: None,
+            backend: Backend::Asusd,
+            expected_signals: Vec::new(),
+            last_write: HashMap::new(),
+            deferred: Vec::new(),
+            current: (0, None),
+            retries: Vec::new(),
+            reported_retries: 0,
+            retryable: false,
+            failure: None,
+            write_failed: false,
+            latency,
+            bus_address: None,
+            sleep_lock: None,
+        }
+    }
+
+    /// Run the actor loop
+    pub async fn run(mut self) {
+        // Try to establish D-Bus connection
+        self.connect().await;
+
+        // Initial state fetch
+        if self.connection.is_some() || self.backend == Backend::Kernel {
+            self.refresh_state().await;
+        }
+
+        // Set up property change monitoring
+        let mut signals = self.subscribe().await;
+
+        // Main event loop using select
+        loop {
+            let next_due = self
+                .deferred
+                .iter()
+                .map(|(_, _, due)| *due)
+                .chain(self.retries.iter().map(|retry| retry.due))
+                .min();
+
+            tokio::select! {
+                // Handle intents from UI
+                Some(entry) = self.intent_rx.recv() => {
+                    let mut batch = vec![entry];
+                    while let Ok(next) = self.intent_rx.try_recv() {
+                        batch.push(next);
+                    }
+
+                    for (generation, intent) in supersede(batch) {
+                        if !self.dispatch(generation, intent).await {
+                            return;
+                        }
+                    }
+                }
+
+                // Run rate-limited writes and retries once they're due
+                _ = tokio::time::sleep_until(next_due.unwrap_or_else(Instant::now).into()),
+                    if next_due.is_some() =>
+                {
+                    self.run_deferred().await;
+                }
+
+                // Handle property changes from D-Bus
+                Some(signal) = signals.next() => {
+                    if signal == DaemonSignal::Resumed {
+                        // Firmware values (fan curves, charge state) can
+                        // change across suspend, and asusd may have restarted
+                        self.refresh_state().await;
+                        signals = self.subscribe().await;
+                    }
+                    self.handle_signal(signal);
+                    // Each profile has curves of its own
+                    if let DaemonSignal::PowerProfile(profile) = signal {
+                        self.read_fan_curve(profile).await;
+                    }
+                }
+
+                else => break,
+            }
+
+            self.report_retries();
+        }
+    }
+
+    /// Handle an intent, deferring it if the same kind of write ran too
+    /// recently. Returns false on shutdown.
+    async fn dispatch(&mut self, generation: Generation, intent: HardwareIntent) -> bool {
+        if intent == HardwareIntent::Shutdown {
+            // Apply writes still waiting on the rate limiter rather than lose
+            // them, and give failed ones a last attempt
+            for (generation, intent, _) in mem::take(&mut self.deferred) {
+                self.execute(generation, intent, 0).await;
+            }
+            for retry in mem::take(&mut self.retries) {
+                self.execute(retry.generation, retry.intent, MAX_WRITE_RETRIES).await;
+            }
+            return false;
+        }
+
+        // A new value replaces one still waiting to be retried, or waiting
+        // on the rate limiter, whether it runs now or is deferred itself
+        let slot = intent.slot();
+        if let Some(slot) = slot {
+            self.retries.retain(|retry| retry.intent.slot() != Some(slot));
+            self.deferred.retain(|(_, pending, _)| pending.slot() != Some(slot));
+        }
+
+        let last = slot.and_then(|slot| self.last_write.get(&slot));
+        if let (Some(interval), Some(last)) = (write_interval(&intent), last) {
+            let due = *last + interval;
+            if Instant::now() < due {
+                self.deferred.push((generation, intent, due));
+                return true;
+            }
+        }
+
+        self.execute(generation, intent, 0).await;
+        true
+    }
+
+    /// Execute deferred writes and retries that are now due
+    async fn run_deferred(&mut self) {
+        let now = Instant::now();
+        let (due, later) = mem::take(&mut self.deferred)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(_, _, at)| *at <= now);
+        self.deferred = later;
+
+        for (generation, intent, _) in due {
+            self.execute(generation, intent, 0).await;
+        }
+
+        let (due, later) = mem::take(&mut self.retries)
+            .into_iter()
+            .partition::<Vec<_>, _>(|retry| retry.due <= now);
+        self.retries = later;
+
+        for retry in due {
+            self.execute(retry.generation, retry.intent, retry.attempt).await;
+        }
+    }
+
+    /// Run an intent; `attempt` counts earlier transient failures of the
+    /// same write
+    async fn execute(&mut self, generation: Generation, intent: HardwareIntent, attempt: u32) {
+        if let (Some(_), Some(slot)) = (write_interval(&intent), intent.slot()) {
+            self.last_write.insert(slot, Instant::now());
+        }
+        let started = Instant::now();
+        let timed = intent != HardwareIntent::Shutdown && self.backend == Backend::Asusd;
+        self.current = (generation, intent.slot());
+        self.write_failed = false;
+        self.retryable = intent.slot().is_some() && attempt < MAX_WRITE_RETRIES;
+        let retry = self.retryable.then(|| intent.clone());
+        let confirmed = Rollback::confirmed(&intent, &self.state_tx.borrow());
+
+        match intent {
+            HardwareIntent::RefreshState => {
+                self.refresh_state().await;
+            }
+            HardwareIntent::CheckHealth => {
+                let checks = self.health_checks().await;
+                self.publish(HardwareUpdate::Health(checks));
+            }
+            HardwareIntent::SetPowerProfile(profile) => {
+                self.set_power_profile(profile).await;
+            }
+            HardwareIntent::SetChargeLimit(limit) => {
+                self.set_charge_limit(limit).await;
+            }
+            HardwareIntent::SetChargeBypass(enabled) => {
+                self.set_charge_bypass(enabled).await;
+            }
+            HardwareIntent::SetFanCurve(profile, curve) => {
+                self.set_fan_curve(profile, curve).await;
+            }
+            HardwareIntent::SetFanCurveEnabled(enabled) => {
+                self.set_fan_curve_enabled(enabled).await;
+            }
+            HardwareIntent::SetFanEnabled(profile, fan, enabled) => {
+                self.set_fan_enabled(profile, fan, enabled).await;
+            }
+            HardwareIntent::SetKbdIdleTimeout(seconds) => {
+                self.set_kbd_idle_timeout(seconds).await;
+            }
+            HardwareIntent::SetKbdBrightness(brightness) => {
+                self.set_kbd_brightness(brightness).await;
+            }
+            HardwareIntent::SetKbdColor(color) => {
+                self.set_kbd_color(color).await;
+            }
+            HardwareIntent::SetPeripheral(peripheral, enabled) => {
+                self.set_peripheral(peripheral, enabled).await;
+            }
+            HardwareIntent::SetDisplayBrightness(brightness) => {
+                self.set_display_brightness(brightness).await;
+            }
+            HardwareIntent::SetAnimeBinding(binding, shown) => {
+                self.set_anime_binding(binding, shown).await;
+            }
+            HardwareIntent::SetSlash(change) => {
+                self.set_slash(change).await;
+            }
+            HardwareIntent::SetTdp(watts) => {
+                self.set_tdp(watts).await;
+            }
+            HardwareIntent::SetGpuMode(mode) => {
+                self.set_gpu_mode(mode).await;
+            }
+            HardwareIntent::RawCall(call) => {
+                let reply = self.raw_call(call).await.map_err(Arc::new);
+                self.publish(HardwareUpdate::RawReply(reply));
+            }
+            HardwareIntent::InhibitSleep(why) => {
+                self.sleep_lock = match why {
+                    Some(why) => self.inhibit_sleep(why).await,
+                    None => None,
+                };
+            }
+            HardwareIntent::Shutdown => {}
+        }
+
+        if let (Some(intent), Some(_)) = (retry, self.failure.take()) {
+            self.retries.push(Retry {
+                generation,
+                intent,
+                attempt: attempt + 1,
+                due: Instant::now() + RETRY_BACKOFF * 2u32.pow(attempt),
+            });
+        } else if let (true, Some(rollback)) = (self.write_failed, confirmed) {
+            // Tagged like the write, so it's dropped if a newer value is on its way
+            self.publish(HardwareUpdate::RolledBack(rollback));
+        }
+        self.retryable = false;
+        self.current = (0, None);
+        if timed && self.connection.is_some() {
+            let micros = started.elapsed().as_micros() as u64;
+            self.latency.store(micros.max(1), Ordering::Relaxed);
+        }
+    }
+
+    /// Tell the UI when the number of writes awaiting a retry changes
+    fn report_retries(&mut self) {
+        if self.retries.len() != self.reported_retries {
+            self.reported_retries = self.retries.len();
+            self.publish(HardwareUpdate::Retrying(self.reported_retries));
+        }
+    }
+
+    /// Fold an update into the latest state, then broadcast it
+    fn publish(&mut self, update: HardwareUpdate) {
+        // Transient write failures are held back while a retry is still possible
+        if let HardwareUpdate::Error(error) = &update {
+            if self.retryable && error.is_transient() {
+                self.failure = Some(error.clone());
+                return;
+            }
+        }
+
+        if matches!(update, HardwareUpdate::Error(_)) {
+            self.write_failed = true;
+        }
+        self.state_tx.send_modify(|state| state.apply(&update));
+        let (generation, slot) = self.current;
+        let _ = self.update_tx.send(TaggedUpdate { generation, slot, update });
+    }
+
+    /// Merge asusd's property change streams into one stream of signals
+    async fn subscribe(&self) -> futures::stream::SelectAll<BoxStream<'static, DaemonSignal>> {
+        let mut streams: Vec<BoxStream<'static, DaemonSignal>> = Vec::new();
+        let Some(conn) = &self.connection else {
+            return futures::stream::select_all(streams);
+        };
+
+        if let Ok(proxy) = AsusPlatformProxy::new(conn).await {
+            streams.push(
+                proxy
+                    .receive_platform_profile_changed()
+                    .await
+                    .filter_map(|change| async move {
+                        let profile = PowerProfile::from_u32(change.get().await.ok()?);
+                        Some(DaemonSignal::PowerProfile(profile))
+                    })
+                    .boxed(),
+            );
+            streams.push(
+                proxy
+                    .receive_charge_control_end_threshold_changed()
+                    .await
+                    .filter_map(|change| async move {
+                        Some(DaemonSignal::ChargeLimit(change.get().await.ok()?))
+                    })
+                    .boxed(),
+            );
+        }
+
+        if let Some(proxy) = self.aura_proxy().await {
+            streams.push(
+                proxy
+                    .receive_brightness_changed()
+                    .await
+                    .filter_map(|change| async move {
+                        let brightness = KbdBrightness::from_u32(change.get().await.ok()?);
+                        Some(DaemonSignal::KbdBrightness(brightness))
+                    })
+                    .boxed(),
+            );
+        }
+
+        if let Ok(proxy) = LogindSessionProxy::new(conn).await {
+            let session = proxy.clone();
+            streams.push(
+                proxy
+                    .receive_idle_hint_changed()
+                    .await
+                    .filter_map(move |change| {
+                        let session = session.clone();
+                        async move {
+                            let since = match change.get().await.ok()? {
+                                true => Some(session.idle_since_hint().await.ok()?),
+                                false => None,
+                            };
+                            Some(DaemonSignal::Idle(since))
+                        }
+                    })
+                    .boxed(),
+            );
+        }
+
+        if let Ok(proxy) = LogindManagerProxy::new(conn).await {
+            // Report the current state once so rules apply at startup
+            let initial = [
+                proxy.lid_closed().await.ok().map(DaemonSignal::LidClosed),
+                proxy.docked().await.ok().map(DaemonSignal::Docked),
+            ];
+            streams.push(futures::stream::iter(initial.into_iter().flatten()).boxed());
+            streams.push(
+                proxy
+                    .receive_lid_closed_changed()
+                    .await
+                    .filter_map(|change| async move {
+                        Some(DaemonSignal::LidClosed(change.get().await.ok()?))
+                    })
+                    .boxed(),
+            );
+            streams.push(
+                proxy
+                    .receive_docked_changed()
+                    .await
+                    .filter_map(|change| async move {
+                        Some(DaemonSignal::Docked(change.get().await.ok()?))
+                    })
+                    .boxed(),
+            );
+            if let Ok(sleep) = proxy.receive_prepare_for_sleep().await {
+                streams.push(
+                    sleep
+                        .filter_map(|signal| async move {
+                            let start = signal.args().ok()?.start;
+                            (!start).then_some(DaemonSignal::Resumed)
+                        })
+                        .boxed(),
+                );
+            }
+        }
+
+        futures::stream::select_all(streams)
+    }
+
+    /// Remember a write so its property change echo is ignored
+    fn expect_signal(&mut self, signal: DaemonSignal) {
+        self.expected_signals.push((signal, Instant::now()));
+    }
+
+    /// Report property changes that weren't caused by our own writes
+    fn handle_signal(&mut self, signal: DaemonSignal) {
+        self.expected_signals
+            .retain(|(_, sent)| sent.elapsed() < ECHO_WINDOW);
+        if let Some(index) = self.expected_signals.iter().position(|(s, _)| *s == signal) {
+            self.expected_signals.remove(index);
+            return;
+        }
+        self.publish(HardwareUpdate::External(signal));
+    }
+
+    async fn connect(&mut self) {
+        let connection = match &self.bus_address {
+            Some(address) => match zbus::connection::Builder::address(address.as_str()) {
+                Ok(builder) => builder.build().await,
+                Err(e) => Err(e),
+            },
+            None => Connection::system().await,
+        };
+        match connection {
+            Ok(conn) => {
+                // Without asusd, fall back to the kernel interfaces when present
+                match probe_asusd(&conn).await {
+                    Ok(()) => self.publish(HardwareUpdate::Connected),
+                    Err(_) if sysfs::kernel_backend_available() => {
+                        self.backend = Backend::Kernel;
+                        self.publish(HardwareUpdate::Connected);
+                    }
+                    Err(reason) => self.publish(HardwareUpdate::Disconnected(reason)),
+                }
+                self.connection = Some(conn);
+            }
+            Err(_) if sysfs::kernel_backend_available() => {
+                self.backend = Backend::Kernel;
+                self.publish(HardwareUpdate::Connected);
+            }
+            Err(e) => {
+                let reason =
+                    DisconnectReason::from_error(&e).unwrap_or(DisconnectReason::NoSystemBus);
+                self.publish(HardwareUpdate::Disconnected(reason));
+            }
+        }
+    }
+
+    /// System bus, asusd and the interfaces the panels rely on
+    async fn health_checks(&self) -> Vec<Check> {
+        let kernel = self.backend == Backend::Kernel;
+        let Some(conn) = &self.connection else {
+            let reason = DisconnectReason::NoSystemBus.to_string();
+            return vec![if kernel {
+                Check::warn("System bus", format!("{}, using kernel interfaces", reason))
+            } else {
+                Check::fail("System bus", reason)
+            }];
+        };
+
+        let mut checks = vec![Check::pass("System bus", "connected")];
+        match probe_asusd(conn).await {
+            Ok(()) => {
+                let version = match AsusPlatformProxy::new(conn).await {
+                    Ok(proxy) => proxy.version().await.ok(),
+                    Err(_) => None,
+                };
+                let version = version.as_deref().and_then(AsusdVersion::parse);
+                checks.push(match version {
+                    Some(v) if v.untested_panels().is_some() => {
+                        Check::warn("asusd", format!("{}, outside the tested range", v))
+                    }
+                    Some(v) => Check::pass("asusd", format!("running, {}", v)),
+                    None => Check::pass("asusd", "running"),
+                });
+                checks.push(Check::pass("Platform interface", PLATFORM_INTERFACE));
+            }
+            Err(DisconnectReason::InterfaceMissing) => {
+                checks.push(Check::pass("asusd", "running"));
+                let reason = DisconnectReason::InterfaceMissing.to_string();
+                checks.push(Check::fail("Platform interface", reason));
+                return checks;
+            }
+            Err(reason) if kernel => {
+                checks.push(Check::warn("asusd", format!("{}, using kernel interfaces", reason)));
+                return checks;
+            }
+            Err(reason) => {
+                checks.push(Check::fail("asusd", reason.to_string()));
+                return checks;
+            }
+        }
+
+        checks.push(match find_object_path(conn, AURA_INTERFACE).await {
+            Some(_) => Check::pass("Aura interface", AURA_INTERFACE),
+            None => Check::warn("Aura interface", "missing: no keyboard lighting controls"),
+        });
+        let anime = find_object_path(conn, ANIME_INTERFACE).await;
+        let slash = find_object_path(conn, SLASH_INTERFACE).await;
+        checks.push(match (anime, slash) {
+            (_, Some(_)) => Check::pass("Lid lighting", SLASH_INTERFACE),
+            (Some(_), None) => Check::pass("Lid lighting", ANIME_INTERFACE),
+            (None, None) => Check::warn("Lid lighting", "no AniMe or Slash: lid panel hidden"),
+        });
+        checks
+    }
+
+    async fn refresh_state(&mut self) {
+        if self.backend == Backend::Kernel {
+            self.refresh_kernel_state();
+            return;
+        }
+
+        let Some(conn) = &self.connection else {
+            return;
+        };
+
+        // Re-probe so a refresh picks up asusd starting (or going away)
+        let previous = self.state_tx.borrow().disconnect_reason;
+        match probe_asusd(conn).await {
+            Err(reason) => {
+                if previous != Some(reason) {
+                    self.publish(HardwareUpdate::Disconnected(reason));
+                }
+                return;
+            }
+            Ok(()) if previous.is_some() => self.publish(HardwareUpdate::Connected),
+            Ok(()) => {}
+        }
+        let Some(conn) = &self.connection else {
+            return;
+        };
+
+        let mut state = HardwareState {
+            connected: true,
+            ..Default::default()
+        };
+
+        // Fetch power profile, charge limit and version from Platform interface
+        if let Ok(proxy) = AsusPlatformProxy::new(conn).await {
+            if let Ok(profile) = proxy.platform_profile().await {
+                state.power_profile = PowerProfile::from_u32(profile);
+            }
+            if let Ok(limit) = proxy.charge_control_end_threshold().await {
+                state.charge_limit = limit;
+            }
+            if let Ok(version) = proxy.version().await {
+                state.asusd_version = AsusdVersion::parse(&version);
+            }
+        }
+
+        // Bypass charging, peripherals, backlight and power limits aren't asusd properties
+        read_kernel_extras(&mut state);
+
+        // Keyboard lighting lives on a separate per-device object
+        self.aura_path = find_object_path(conn, AURA_INTERFACE).await;
+        if let Some(proxy) = self.aura_proxy().await {
+            state.aura = Some(AuraState {
+                brightness: proxy.brightness().await.ok().map(KbdBrightness::from_u32),
+                idle_timeout: proxy.idle_timeout().await.ok(),
+                color: proxy.led_mode_data().await.ok().map(|effect| effect.color()),
+            });
+        }
+
+        // AniMe Matrix, stored as "shown" rather than asusd's "off when"
+        self.anime_path = find_object_path(conn, ANIME_INTERFACE).await;
+        if let Some(proxy) = self.anime_proxy().await {
+            state.anime = Some(AnimeState {
+                boot_shutdown: proxy.builtins_enabled().await.ok(),
+                sleep: proxy.off_when_suspended().await.ok().map(|off| !off),
+                lid_closed: proxy.off_when_lid_closed().await.ok().map(|off| !off),
+            });
+        }
+
+        // Slash lid lighting replaces the AniMe Matrix on 2024+ models
+        self.slash_path = find_object_path(conn, SLASH_INTERFACE).await;
+        if let Some(proxy) = self.slash_proxy().await {
+            state.slash = Some(SlashState {
+                enabled: proxy.enabled().await.ok(),
+                mode: proxy.mode().await.ok(),
+                brightness: proxy.brightness().await.ok(),
+                interval: proxy.interval().await.ok(),
+            });
+        }
+
+        // Fan curves, on models whose EC takes custom ones
+        state.fan_curve = FanCurve::default_curve();
+        self.fan_curves_path = find_object_path(conn, FAN_CURVES_INTERFACE).await;
+        if let Some(proxy) = self.fan_curves_proxy().await {
+            state.fan_curve_control = true;
+            // Every profile's switches for the grid, the active one's points for the graph
+            for profile in PowerProfile::ALL {
+                let Ok(curves) = proxy.fan_curve_data(profile.to_u32()).await else {
+                    continue;
+                };
+                if profile == state.power_profile {
+                    state.fan_curve.read_asusd(&curves);
+                }
+                state.fan_curve.read_enablement(profile, &curves);
+            }
+            state.fan_curve.enabled = state.fan_curve.enablement.any(state.power_profile);
+        }
+
+        self.publish(HardwareUpdate::StateRefresh(state));
+    }
+
+    /// Read state straight from the kernel when asusd isn't running
+    fn refresh_kernel_state(&mut self) {
+        let mut state = HardwareState {
+            connected: true,
+            backend: Backend::Kernel,
+            ..Default::default()
+        };
+
+        state.power_profile = sysfs::platform_profile()
+            .as_deref()
+            .and_then(PowerProfile::from_platform_profile)
+            .unwrap_or_default();
+        state.charge_limit = sysfs::charge_limit().unwrap_or(100);
+        read_kernel_extras(&mut state);
+        state.fan_curve = FanCurve::default_curve();
+
+        self.publish(HardwareUpdate::StateRefresh(state));
+    }
+
+    async fn set_power_profile(&mut self, profile: PowerProfile) {
+        if self.backend == Backend::Kernel {
+            let choices = sysfs::platform_profile_choices();
+            let result = match profile
+                .platform_profile_names()
+                .iter()
+                .find(|name| choices.iter().any(|c| c == *name))
+            {
+                Some(name) => blocking_write(move || sysfs::set_platform_profile(name)).await,
+                None => Err(HachiError::InvalidPowerProfile(profile.to_string())),
+            };
+            match result {
+                Ok(()) => {
+                    self.publish(HardwareUpdate::PowerProfileChanged(profile));
+                    self.publish_power_limits(profile);
+                }
+                Err(e) => {
+                    self.publish(HardwareUpdate::Error(Arc::new(e)));
+                }
+            }
+            return;
+        }
+
+        let Some(conn) = &self.connection else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::Disconnected(DisconnectReason::NoSystemBus)
+            )));
+            return;
+        };
+
+        match AsusPlatformProxy::new(conn).await {
+            Ok(proxy) => {
+                self.expect_signal(DaemonSignal::PowerProfile(profile));
+                if let Err(e) = proxy.set_platform_profile(profile.to_u32()).await {
+                    self.publish(HardwareUpdate::Error(Arc::new(
+                        HachiError::from(e)
+                    )));
+                } else {
+                    self.publish(HardwareUpdate::PowerProfileChanged(profile));
+                    self.publish_power_limits(profile);
+                    self.read_fan_curve(profile).await;
+                }
+            }
+            Err(e) => {
+                self.publish(HardwareUpdate::Error(Arc::new(
+                    HachiError::from(e)
+                )));
+            }
+        }
+    }
+
+    async fn set_charge_limit(&mut self, limit: u8) {
+        let limit = CHARGE_LIMIT_RANGE.clamp(limit);
+
+        if self.backend == Backend::Kernel {
+            match blocking_write(move || sysfs::set_charge_limit(limit)).await {
+                Ok(()) => {
+                    self.publish(HardwareUpdate::ChargeLimitChanged(limit));
+                }
+                Err(e) => {
+                    self.publish(HardwareUpdate::Error(Arc::new(e)));
+                }
+            }
+            return;
+        }
+
+        let Some(conn) = &self.connection else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::Disconnected(DisconnectReason::NoSystemBus)
+            )));
+            return;
+        };
+
+        match AsusPlatformProxy::new(conn).await {
+            Ok(proxy) => {
+                self.expect_signal(DaemonSignal::ChargeLimit(limit));
+                if let Err(e) = proxy.set_charge_control_end_threshold(limit).await {
+                    self.publish(HardwareUpdate::Error(Arc::new(
+                        HachiError::from(e)
+                    )));
+                } else {
+                    self.publish(HardwareUpdate::ChargeLimitChanged(limit));
+                }
+            }
+            Err(e) => {
+                self.publish(HardwareUpdate::Error(Arc::new(
+                    HachiError::from(e)
+                )));
+            }
+        }
+    }
+
+    async fn set_charge_bypass(&mut self, enabled: bool) {
+        match blocking_write(move || sysfs::set_charge_bypass(enabled)).await {
+            Ok(()) => {
+                self.publish(HardwareUpdate::ChargeBypassChanged(enabled));
+            }
+            Err(e) => {
+                self.publish(HardwareUpdate::Error(Arc::new(e)));
+            }
+        }
+    }
+
+    async fn set_peripheral(&mut self, peripheral: Peripheral, enabled: bool) {
+        match blocking_write(move || peripheral.write(enabled)).await {
+            Ok(()) => {
+                self.publish(HardwareUpdate::PeripheralChanged(peripheral, enabled));
+            }
+            Err(e) => {
+                self.publish(HardwareUpdate::Error(Arc::new(e)));
+            }
+        }
+    }
+
+    /// Each profile carries its own power limits; report the ones the
+    /// firmware just applied
+    fn publish_power_limits(&mut self, profile: PowerProfile) {
+        if let Some(sustained) = sysfs::tdp() {
+            let limits = PowerLimits {
+                sustained,
+                boost: sysfs::tdp_boost(),
+            };
+            self.publish(HardwareUpdate::PowerLimitsChanged(profile, limits));
+        }
+    }
+
+    async fn set_tdp(&mut self, watts: u8) {
+        match blocking_write(move || sysfs::set_tdp(watts)).await {
+            Ok(()) => {
+                self.publish(HardwareUpdate::TdpChanged(watts));
+            }
+            Err(e) => {
+                self.publish(HardwareUpdate::Error(Arc::new(e)));
+            }
+        }
+    }
+
+    async fn set_gpu_mode(&mut self, mode: GpuMode) {
+        match blocking_write(move || sysfs::set_gpu_mux_mode(mode.to_mux())).await {
+            Ok(()) => {
+                self.publish(HardwareUpdate::GpuModeChanged(mode));
+            }
+            Err(e) => {
+                self.publish(HardwareUpdate::Error(Arc::new(e)));
+            }
+        }
+    }
+
+    async fn set_display_brightness(&mut self, brightness: u32) {
+        let Some(backlight) = sysfs::backlight() else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::Unsupported("No display backlight found".to_string())
+            )));
+            return;
+        };
+        let brightness = brightness.min(backlight.max_brightness);
+
+        // Prefer logind, which allows the session user to change the backlight;
+        // fall back to writing sysfs directly
+        let via_logind = match &self.connection {
+            Some(conn) => match LogindSessionProxy::new(conn).await {
+                Ok(proxy) => proxy
+                    .set_brightness("backlight", &backlight.device, brightness)
+                    .await
+                    .is_ok(),
+                Err(_) => false,
+            },
+            None => false,
+        };
+
+        let result = if via_logind {
+            Ok(())
+        } else {
+            let device = backlight.device.clone();
+            blocking_write(move || sysfs::set_backlight(&device, brightness)).await
+        };
+        match result {
+            Ok(()) => {
+                self.publish(HardwareUpdate::DisplayBrightnessChanged(brightness));
+            }
+            Err(e) => {
+                self.publish(HardwareUpdate::Error(Arc::new(e)));
+            }
+        }
+    }
+
+    /// Build a proxy for the discovered Aura device
+    async fn aura_proxy(&self) -> Option<AsusAuraProxy<'static>> {
+        let conn = self.connection.as_ref()?;
+        let path = self.aura_path.clone()?;
+        AsusAuraProxy::builder(conn).path(path).ok()?.build().await.ok()
+    }
+
+    /// Build a proxy for the discovered AniMe Matrix
+    async fn anime_proxy(&self) -> Option<AsusAnimeProxy<'static>> {
+        let conn = self.connection.as_ref()?;
+        let path = self.anime_path.clone()?;
+        AsusAnimeProxy::builder(conn).path(path).ok()?.build().await.ok()
+    }
+
+    async fn set_anime_binding(&mut self, binding: AnimeBinding, shown: bool) {
+        let Some(proxy) = self.anime_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::DbusCall("No AniMe Matrix found".to_string())
+            )));
+            return;
+        };
+
+        let result = match binding {
+            AnimeBinding::BootShutdown => proxy.set_builtins_enabled(shown).await,
+            AnimeBinding::Sleep => proxy.set_off_when_suspended(!shown).await,
+            AnimeBinding::LidClosed => proxy.set_off_when_lid_closed(!shown).await,
+        };
+        if let Err(e) = result {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::from(e)
+            )));
+        } else {
+            self.publish(HardwareUpdate::AnimeBindingChanged(binding, shown));
+        }
+    }
+
+    /// Build a proxy for the discovered Slash lighting bar
+    async fn slash_proxy(&self) -> Option<AsusSlashProxy<'static>> {
+        let conn = self.connection.as_ref()?;
+        let path = self.slash_path.clone()?;
+        AsusSlashProxy::builder(conn).path(path).ok()?.build().await.ok()
+    }
+
+    async fn set_slash(&mut self, change: SlashChange) {
+        let Some(proxy) = self.slash_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::DbusCall("No Slash lighting found".to_string())
+            )));
+            return;
+        };
+
+        let result = match &change {
+            SlashChange::Enabled(enabled) => proxy.set_enabled(*enabled).await,
+            SlashChange::Mode(mode) => proxy.set_mode(mode).await,
+            SlashChange::Brightness(brightness) => proxy.set_brightness(*brightness).await,
+            SlashChange::Interval(interval) => proxy.set_interval(*interval).await,
+        };
+        if let Err(e) = result {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::from(e)
+            )));
+        } else {
+            self.publish(HardwareUpdate::SlashChanged(change));
+        }
+    }
+
+    async fn set_kbd_idle_timeout(&mut self, seconds: u32) {
+        let Some(proxy) = self.aura_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::DbusCall("No Aura keyboard found".to_string())
+            )));
+            return;
+        };
+
+        if let Err(e) = proxy.set_idle_timeout(seconds).await {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::from(e)
+            )));
+        } else {
+            self.publish(HardwareUpdate::KbdIdleTimeoutChanged(seconds));
+        }
+    }
+
+    async fn set_kbd_brightness(&mut self, brightness: KbdBrightness) {
+        let Some(proxy) = self.aura_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::DbusCall("No Aura keyboard found".to_string())
+            )));
+            return;
+        };
+
+        self.expect_signal(DaemonSignal::KbdBrightness(brightness));
+        if let Err(e) = proxy.set_brightness(brightness.to_u32()).await {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::from(e)
+            )));
+        } else {
+            self.publish(HardwareUpdate::KbdBrightnessChanged(brightness));
+        }
+    }
+
+    async fn set_kbd_color(&mut self, color: Rgb) {
+        let Some(proxy) = self.aura_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::DbusCall("No Aura keyboard found".to_string())
+            )));
+            return;
+        };
+
+        match proxy.set_led_mode_data(AuraEffect::static_color(color)).await {
+            Ok(()) => self.publish(HardwareUpdate::KbdColorChanged(color)),
+            Err(e) => self.publish(HardwareUpdate::Error(Arc::new(HachiError::from(e)))),
+        }
+    }
+
+    /// Run a console command against the first object implementing its
+    /// interface, returning the reply's values in GVariant text form
+    async fn raw_call(&self, call: RawCall) -> Result<String, HachiError> {
+        const PROPERTIES: &str = "org.freedesktop.DBus.Properties";
+        const INTROSPECTABLE: &str = "org.freedesktop.DBus.Introspectable";
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or(HachiError::Disconnected(DisconnectReason::NoSystemBus))?;
+        let path = find_object_path(conn, &call.interface).await.ok_or_else(|| {
+            HachiError::Unsupported(format!("no asusd object implements {}", call.interface))
+        })?;
+        let destination = Some("xyz.ljones.Asusd");
+        let interface = call.interface.as_str();
+        let failed = |e: zbus::Error| HachiError::DbusCall(e.to_string());
+
+        let reply = match &call.action {
+            RawAction::Get(property) => {
+                let body = (interface, property.as_str());
+                conn.call_method(destination, &path, Some(PROPERTIES), "Get", &body).await
+            }
+            RawAction::Set(property, value) => {
+                let body = (interface, property.as_str(), value.value()?);
+                conn.call_method(destination, &path, Some(PROPERTIES), "Set", &body).await
+            }
+            RawAction::Call(method, args) if args.is_empty() => {
+                let method = method.as_str();
+                conn.call_method(destination, &path, Some(interface), method, &()).await
+            }
+            RawAction::Call(method, args) => {
+                let mut body = StructureBuilder::new();
+                for arg in args {
+                    body = body.append_field(arg.value()?);
+                }
+                let body = body.build().map_err(|e| HachiError::InvalidArgs(e.to_string()))?;
+                let method = method.as_str();
+                conn.call_method(destination, &path, Some(interface), method, &body).await
+            }
+            RawAction::Introspect => {
+                conn.call_method(destination, &path, Some(INTROSPECTABLE), "Introspect", &()).await
+            }
+        }
+        .map_err(failed)?;
+
+        let body = reply.body();
+        if call.action == RawAction::Introspect {
+            return body.deserialize::<String>().map_err(failed);
+        }
+        if body.is_empty() {
+            return Ok("ok".to_string());
+        }
+        let values = body.deserialize::<Structure>().map_err(failed)?;
+        let fields: Vec<String> = values.fields().iter().map(ToString::to_string).collect();
+        Ok(fields.join(", "))
+    }
+
+    /// Write both fans' curves for `profile`, each on or off as
+    /// `curve.enablement` has it. asusd applies them at once if `profile`
+    /// is active, otherwise when it next becomes active.
+    async fn set_fan_curve(&mut self, profile: PowerProfile, curve: FanCurve) {
+        if let Err(e) = curve.validate() {
+            self.publish(HardwareUpdate::Error(Arc::new(e)));
+            return;
+        }
+        let Some(proxy) = self.fan_curves_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::Unsupported("Fan curve control not available".to_string())
+            )));
+            return;
+        };
+
+        // A suspend halfway through leaves the firmware with part of a curve
+        let _sleep = self.inhibit_sleep("Writing a fan curve").await;
+        for fan in Fan::ALL {
+            let data = CurveData::new(fan, curve.points(fan), curve.enablement.get(profile, fan));
+            if let Err(e) = proxy.set_fan_curve(profile.to_u32(), data).await {
+                self.publish(HardwareUpdate::Error(Arc::new(
+                    HachiError::from(e)
+                )));
+                return;
+            }
+        }
+        self.publish(HardwareUpdate::FanCurveChanged(profile, curve));
+    }
+
+    /// Read the curves asusd keeps for `profile` after switching to it
+    async fn read_fan_curve(&mut self, profile: PowerProfile) {
+        let Some(proxy) = self.fan_curves_proxy().await else {
+            return;
+        };
+        let Ok(curves) = proxy.fan_curve_data(profile.to_u32()).await else {
+            return;
+        };
+        let mut curve = self.state_tx.borrow().fan_curve.clone();
+        curve.read_asusd(&curves);
+        curve.read_enablement(profile, &curves);
+        curve.enabled = curve.enablement.any(profile);
+        self.publish(HardwareUpdate::FanCurveLoaded(profile, curve));
+    }
+
+    /// Block suspend through logind while the returned lock is open; `None`
+    /// without logind, which only costs the protection
+    async fn inhibit_sleep(&self, why: &str) -> Option<OwnedFd> {
+        let proxy = LogindManagerProxy::new(self.connection.as_ref()?).await.ok()?;
+        proxy.inhibit("sleep", "hachi", why, "block").await.ok()
+    }
+
+    /// Build a proxy for asusd's fan curves, on models that have them
+    async fn fan_curves_proxy(&self) -> Option<AsusFanCurvesProxy<'static>> {
+        let conn = self.connection.as_ref()?;
+        let path = self.fan_curves_path.clone()?;
+        AsusFanCurvesProxy::builder(conn).path(path).ok()?.build().await.ok()
+    }
+
+    async fn set_fan_curve_enabled(&mut self, enabled: bool) {
+        let Some(proxy) = self.fan_curves_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::InvalidFanCurve("Fan curve control not available".to_string())
+            )));
+            return;
+        };
+
+        // asusd keeps curves per profile; the active one is what the fans follow
+        let profile = self.state_tx.borrow().power_profile;
+        if let Err(e) = proxy.set_fan_curves_enabled(profile.to_u32(), enabled).await {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::from(e)
+            )));
+        } else {
+            self.publish(HardwareUpdate::FanCurveEnabledChanged(enabled));
+        }
+    }
+
+    async fn set_fan_enabled(&mut self, profile: PowerProfile, fan: Fan, enabled: bool) {
+        let Some(proxy) = self.fan_curves_proxy().await else {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::InvalidFanCurve("Fan curve control not available".to_string())
+            )));
+            return;
+        };
+
+        let result = proxy
+            .set_profile_fan_curve_enabled(profile.to_u32(), fan.as_str(), enabled)
+            .await;
+        if let Err(e) = result {
+            self.publish(HardwareUpdate::Error(Arc::new(
+                HachiError::from(e)
+            )));
+        } else {
+            self.publish(HardwareUpdate::FanEnabledChanged(profile, fan, enabled));
+        }
     }
 }
 
 // =============================================================================
-// Actor Handle (for UI thread to communicate with actor)
-// =============================================================================
+// Actor Handle (for UI thread to communicate with actor)
+// =============================================================================
 
 pub struct DaemonHandle {
-    intent_tx: mpsc::Sender<HardwareIntent>,
-    update_rx: broadcast::Receiver<HardwareUpdate>,
+    intent_tx: mpsc::Sender<(Generation, HardwareIntent)>,
+    /// For rolling back writes dropped before they reached the actor
+    update_tx: broadcast::Sender<TaggedUpdate>,
+    update_rx: broadcast::Receiver<TaggedUpdate>,
+    state_rx: watch::Receiver<HardwareState>,
+    next_generation: AtomicU64,
+    /// Generation of the newest intent sent for each slot
+    latest: Arc<Mutex<HashMap<IntentSlot, Generation>>>,
+    /// Generation of the newest intent waiting for room for each slot
+    waiting: Arc<Mutex<HashMap<IntentSlot, Generation>>>,
+    /// Intents dropped because the queue stayed full, since last taken
+    dropped: Arc<AtomicUsize>,
+    /// Actor task, awaited on shutdown
+    task: Option<JoinHandle<()>>,
+    /// Drop every write intent (`--read-only`)
+    read_only: bool,
+    /// Duration of the actor's last asusd call in microseconds, 0 before the first
+    latency: Arc<AtomicU64>,
 }
 
 impl DaemonHandle {
     /// Spawn the hardware actor and return a handle
     pub fn spawn() -> Self {
+        Self::spawn_on(None)
+    }
+
+    /// Spawn the hardware actor against the bus at `bus_address`, or the
+    /// system bus when `None`
+    pub fn spawn_on(bus_address: Option<String>) -> Self {
         let (intent_tx, intent_rx) = mpsc::channel(32);
         let (update_tx, update_rx) = broadcast::channel(64);
+        let (state_tx, state_rx) = watch::channel(HardwareState::default());
 
-        let actor = HardwareActor::new(intent_rx, update_tx);
+        let latency = Arc::new(AtomicU64::new(0));
+        let mut actor =
+            HardwareActor::new(intent_rx, update_tx.clone(), state_tx, latency.clone());
+        actor.bus_address = bus_address;
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             actor.run().await;
         });
 
         Self {
             intent_tx,
+            update_tx,
             update_rx,
+            state_rx,
+            next_generation: AtomicU64::new(1),
+            latest: Arc::new(Mutex::new(HashMap::new())),
+            waiting: Arc::new(Mutex::new(HashMap::new())),
+            dropped: Arc::new(AtomicUsize::new(0)),
+            task: Some(task),
+            read_only: false,
+            latency,
         }
     }
 
+    /// Monitor only: refreshes still go through, writes are dropped
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// Send an intent to the hardware actor (non-blocking)
     pub fn send(&self, intent: HardwareIntent) {
-        let _ = self.intent_tx.try_send(intent);
+        if self.read_only && intent.writes() {
+            return;
+        }
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        let slot = intent.slot();
+        let entry = match self.intent_tx.try_send((generation, intent)) {
+            Ok(()) => {
+                if let Some(slot) = slot {
+                    self.latest.lock().unwrap().insert(slot, generation);
+                }
+                return;
+            }
+            Err(mpsc::error::TrySendError::Full(entry)) => entry,
+            Err(mpsc::error::TrySendError::Closed(_)) => return,
+        };
+
+        // The actor is busy. Refreshes and health checks can go; a write or
+        // sleep inhibitor the user asked for waits a moment for room.
+        if matches!(entry.1, HardwareIntent::RefreshState | HardwareIntent::CheckHealth) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        if let Some(slot) = slot {
+            self.waiting.lock().unwrap().insert(slot, generation);
+        }
+        let intent_tx = self.intent_tx.clone();
+        let update_tx = self.update_tx.clone();
+        let state_rx = self.state_rx.clone();
+        let latest = self.latest.clone();
+        let waiting = self.waiting.clone();
+        let dropped = self.dropped.clone();
+        tokio::spawn(async move {
+            let permit = tokio::time::timeout(INTENT_SEND_TIMEOUT, intent_tx.reserve()).await;
+            // A newer write to the same slot, sent meanwhile, replaces this one
+            let mut latest = latest.lock().unwrap();
+            if let Some(slot) = slot {
+                let newer_sent = latest.get(&slot).is_some_and(|&newest| newest > generation);
+                if waiting.lock().unwrap().get(&slot) != Some(&generation) || newer_sent {
+                    return;
+                }
+            }
+            match permit {
+                Ok(Ok(permit)) => {
+                    if let Some(slot) = slot {
+                        latest.insert(slot, generation);
+                    }
+                    permit.send(entry);
+                }
+                Ok(Err(_)) => {}
+                Err(_) => {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                    // The UI already shows the value; put back the confirmed one
+                    let (_, intent) = entry;
+                    if let Some(rollback) = Rollback::confirmed(&intent, &state_rx.borrow()) {
+                        let update = HardwareUpdate::RolledBack(rollback);
+                        let _ = update_tx.send(TaggedUpdate { generation, slot, update });
+                    }
+                }
+            }
+        });
+    }
+
+    /// Number of intents dropped because the actor was too busy to take
+    /// them, since the last call
+    pub fn take_dropped(&self) -> usize {
+        self.dropped.swap(0, Ordering::Relaxed)
+    }
+
+    /// How long the actor's last asusd call took, including the reply
+    pub fn last_call_latency(&self) -> Option<Duration> {
+        match self.latency.load(Ordering::Relaxed) {
+            0 => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// Updates waiting for the UI and intents waiting for the actor
+    pub fn queue_depths(&self) -> (usize, usize) {
+        let intents = self.intent_tx.max_capacity() - self.intent_tx.capacity();
+        (self.update_rx.len(), intents)
     }
 
     /// Request a state refresh
@@ -423,6 +2851,11 @@ impl DaemonHandle {
         self.send(HardwareIntent::RefreshState);
     }
 
+    /// Run the bus checks for the health checklist
+    pub fn check_health(&self) {
+        self.send(HardwareIntent::CheckHealth);
+    }
+
     /// Set power profile
     pub fn set_power_profile(&self, profile: PowerProfile) {
         self.send(HardwareIntent::SetPowerProfile(profile));
@@ -433,9 +2866,14 @@ impl DaemonHandle {
         self.send(HardwareIntent::SetChargeLimit(limit));
     }
 
-    /// Set fan curve
-    pub fn set_fan_curve(&self, curve: FanCurve) {
-        self.send(HardwareIntent::SetFanCurve(curve));
+    /// Toggle bypass charging
+    pub fn set_charge_bypass(&self, enabled: bool) {
+        self.send(HardwareIntent::SetChargeBypass(enabled));
+    }
+
+    /// Write a profile's fan curves
+    pub fn set_fan_curve(&self, profile: PowerProfile, curve: FanCurve) {
+        self.send(HardwareIntent::SetFanCurve(profile, curve));
     }
 
     /// Toggle fan curve control
@@ -443,13 +2881,125 @@ impl DaemonHandle {
         self.send(HardwareIntent::SetFanCurveEnabled(enabled));
     }
 
-    /// Try to receive an update (non-blocking)
+    pub fn set_fan_enabled(&self, profile: PowerProfile, fan: Fan, enabled: bool) {
+        self.send(HardwareIntent::SetFanEnabled(profile, fan, enabled));
+    }
+
+    /// Set keyboard backlight idle timeout
+    pub fn set_kbd_idle_timeout(&self, seconds: u32) {
+        self.send(HardwareIntent::SetKbdIdleTimeout(seconds));
+    }
+
+    /// Set keyboard backlight brightness
+    pub fn set_kbd_brightness(&self, brightness: KbdBrightness) {
+        self.send(HardwareIntent::SetKbdBrightness(brightness));
+    }
+
+    /// Light the whole keyboard in one static color
+    pub fn set_kbd_color(&self, color: Rgb) {
+        self.send(HardwareIntent::SetKbdColor(color));
+    }
+
+    /// Send a console command; the reply comes back as `RawReply`
+    pub fn raw_call(&self, call: RawCall) {
+        self.send(HardwareIntent::RawCall(call));
+    }
+
+    /// Hold off suspend for `why`, or let it happen again with `None`
+    pub fn inhibit_sleep(&self, why: Option<&'static str>) {
+        self.send(HardwareIntent::InhibitSleep(why));
+    }
+
+    /// Show or blank the AniMe Matrix in a system state
+    pub fn set_anime_binding(&self, binding: AnimeBinding, shown: bool) {
+        self.send(HardwareIntent::SetAnimeBinding(binding, shown));
+    }
+
+    /// Set package power limits (watts)
+    pub fn set_tdp(&self, watts: u8) {
+        self.send(HardwareIntent::SetTdp(watts));
+    }
+
+    /// Move the GPU MUX (takes effect after a reboot)
+    pub fn set_gpu_mode(&self, mode: GpuMode) {
+        self.send(HardwareIntent::SetGpuMode(mode));
+    }
+
+    /// Change a Slash lid lighting setting
+    pub fn set_slash(&self, change: SlashChange) {
+        self.send(HardwareIntent::SetSlash(change));
+    }
+
+    /// Set display backlight brightness (raw device value)
+    pub fn set_display_brightness(&self, brightness: u32) {
+        self.send(HardwareIntent::SetDisplayBrightness(brightness));
+    }
+
+    /// Toggle a peripheral switch
+    pub fn set_peripheral(&self, peripheral: Peripheral, enabled: bool) {
+        self.send(HardwareIntent::SetPeripheral(peripheral, enabled));
+    }
+
+    /// Try to receive an update (non-blocking). If the receiver fell behind
+    /// and updates were dropped, a full refresh from the latest state is
+    /// returned instead.
     pub fn try_recv(&mut self) -> Option<HardwareUpdate> {
-        self.update_rx.try_recv().ok()
+        loop {
+            match self.update_rx.try_recv() {
+                Ok(tagged) if self.is_stale(&tagged) => continue,
+                Ok(tagged) => return Some(tagged.update),
+                Err(broadcast::error::TryRecvError::Lagged(_)) => {
+                    return Some(HardwareUpdate::StateRefresh(self.state()));
+                }
+                Err(_) => return None,
+            }
+        }
     }
 
-    /// Shutdown the actor
-    pub fn shutdown(&self) {
-        let _ = self.intent_tx.try_send(HardwareIntent::Shutdown);
+    /// Whether an update acknowledges a write that a newer intent has since
+    /// superseded; applying it would briefly revert the UI. Errors are kept.
+    fn is_stale(&self, tagged: &TaggedUpdate) -> bool {
+        if matches!(tagged.update, HardwareUpdate::Error(_)) {
+            return false;
+        }
+        let Some(slot) = tagged.slot else {
+            return false;
+        };
+        let latest = self.latest.lock().unwrap();
+        latest.get(&slot).is_some_and(|&newest| newest > tagged.generation)
+    }
+
+    /// Snapshot of the latest hardware state
+    pub fn state(&self) -> HardwareState {
+        self.state_rx.borrow().clone()
+    }
+
+    /// Receiver for consumers that want to await state changes
+    pub fn watch_state(&self) -> watch::Receiver<HardwareState> {
+        self.state_rx.clone()
+    }
+
+    /// Stop the actor after it finishes in-flight and rate-limited writes,
+    /// waiting at most `timeout`. Returns false if the actor had to be
+    /// aborted with writes still pending.
+    pub async fn shutdown_and_wait(&mut self, timeout: Duration) -> bool {
+        let Some(task) = self.task.take() else {
+            return true;
+        };
+        let abort = task.abort_handle();
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+
+        let finished = tokio::time::timeout(timeout, async {
+            // Unlike `send`, wait for queue space so the request isn't dropped
+            let _ = self.intent_tx.send((generation, HardwareIntent::Shutdown)).await;
+            let _ = task.await;
+        })
+        .await
+        .is_ok();
+
+        if !finished {
+            abort.abort();
+        }
+        finished
     }
 }
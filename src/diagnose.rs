@@ -0,0 +1,221 @@
+//! Bug report bundle (`hachi diagnose`, `B` in the app): versions,
+//! capabilities, health checks, hardware state and recent messages as
+//! JSON. The home directory, user and host names are scrubbed from free
+//! text such as error messages.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::app::{Message, Severity};
+use crate::config::state_dir;
+use crate::daemon::{Backend, DaemonHandle, HardwareState, HardwareUpdate};
+use crate::error::{HachiError, Result};
+use crate::health::{self, Check};
+use crate::sysfs::{self, read_trimmed};
+use crate::telemetry;
+
+/// How long `hachi diagnose` waits for the actor's health checks
+const COLLECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+pub struct Bundle {
+    pub hachi: &'static str,
+    pub asusd: Option<String>,
+    pub kernel: Option<String>,
+    pub model: Option<String>,
+    pub capabilities: Capabilities,
+    pub checks: Vec<Check>,
+    pub state: HardwareState,
+    /// Recent status messages and errors, oldest first
+    pub log: Vec<LogEntry>,
+}
+
+/// What the laptop and asusd expose, as found by the last refresh
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    pub backend: Backend,
+    pub keyboard_lighting: bool,
+    pub anime: bool,
+    pub slash: bool,
+    pub charge_bypass: bool,
+    pub fn_lock: bool,
+    pub touchpad: bool,
+    pub backlight: bool,
+    pub power_limits: bool,
+    pub gpu_mux: bool,
+}
+
+impl Capabilities {
+    pub fn of(state: &HardwareState) -> Self {
+        Self {
+            backend: state.backend,
+            keyboard_lighting: state.aura.is_some(),
+            anime: state.anime.is_some(),
+            slash: state.slash.is_some(),
+            charge_bypass: state.charge_bypass.is_some(),
+            fn_lock: state.fn_lock.is_some(),
+            touchpad: state.touchpad.is_some(),
+            backlight: state.backlight.is_some(),
+            power_limits: state.tdp.is_some(),
+            gpu_mux: state.gpu_mode.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    /// Local time, `HH:MM:SS`
+    pub time: String,
+    pub level: &'static str,
+    pub text: String,
+}
+
+impl From<&Message> for LogEntry {
+    fn from(message: &Message) -> Self {
+        Self {
+            time: message.timestamp(),
+            level: message.severity.as_str(),
+            text: message.text.clone(),
+        }
+    }
+}
+
+impl Bundle {
+    /// Assemble a bundle, scrubbing check details and log text
+    pub fn new(state: &HardwareState, checks: &[Check], log: Vec<LogEntry>) -> Self {
+        let scrubber = Scrubber::from_env();
+        let checks = checks
+            .iter()
+            .map(|check| Check {
+                detail: scrubber.scrub(&check.detail),
+                ..check.clone()
+            })
+            .collect();
+        let log = log
+            .into_iter()
+            .map(|entry| LogEntry {
+                text: scrubber.scrub(&entry.text),
+                ..entry
+            })
+            .collect();
+
+        Self {
+            hachi: env!("CARGO_PKG_VERSION"),
+            asusd: state.asusd_version.map(|version| version.to_string()),
+            kernel: read_trimmed("/proc/sys/kernel/osrelease".as_ref()),
+            model: sysfs::product_name(),
+            capabilities: Capabilities::of(state),
+            checks,
+            state: state.clone(),
+            log,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the bundle to `diagnose-<unix time>.json` in the state
+    /// directory, returning the path
+    pub fn save(&self) -> Result<PathBuf> {
+        let dir = state_dir()
+            .ok_or_else(|| HachiError::Config("No state directory available".to_string()))?;
+        fs::create_dir_all(&dir)?;
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let path = dir.join(format!("diagnose-{}.json", secs));
+        fs::write(&path, self.to_json()?)?;
+        Ok(path)
+    }
+}
+
+/// Gather a bundle for `hachi diagnose`: wait for the actor's health
+/// checks, which run after its first refresh, keeping errors as the log
+pub async fn collect(daemon: &mut DaemonHandle) -> Bundle {
+    daemon.check_health();
+
+    let mut log = Vec::new();
+    let waiting = async {
+        loop {
+            while let Some(update) = daemon.try_recv() {
+                match update {
+                    HardwareUpdate::Health(checks) => return checks,
+                    HardwareUpdate::Error(error) => log.push(LogEntry::from(&Message {
+                        time: SystemTime::now(),
+                        severity: Severity::Error,
+                        text: error.to_string(),
+                    })),
+                    _ => {}
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    };
+    let mut checks = tokio::time::timeout(COLLECT_TIMEOUT, waiting)
+        .await
+        .unwrap_or_default();
+
+    let sensors = telemetry::read_hwmon().0.len();
+    let colorterm = std::env::var("COLORTERM").ok();
+    checks.extend(health::local_checks(sensors, colorterm.as_deref(), false));
+    Bundle::new(&daemon.state(), &checks, log)
+}
+
+/// Replaces the home directory, user name and host name in free text
+#[derive(Debug, Default)]
+pub struct Scrubber {
+    pub home: Option<String>,
+    pub user: Option<String>,
+    pub host: Option<String>,
+}
+
+impl Scrubber {
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+        Self {
+            home: var("HOME").filter(|home| home != "/"),
+            user: var("USER"),
+            host: read_trimmed("/proc/sys/kernel/hostname".as_ref()).filter(|h| !h.is_empty()),
+        }
+    }
+
+    pub fn scrub(&self, text: &str) -> String {
+        let mut text = match &self.home {
+            Some(home) => text.replace(home.as_str(), "~"),
+            None => text.to_string(),
+        };
+        if let Some(user) = &self.user {
+            text = replace_word(&text, user, "<user>");
+        }
+        if let Some(host) = &self.host {
+            text = replace_word(&text, host, "<host>");
+        }
+        text
+    }
+}
+
+/// Replace `word` where it stands on its own, so a short user name like
+/// `me` leaves "timed out" alone
+fn replace_word(text: &str, word: &str, with: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find(word) {
+        let (before, after) = (&rest[..at], &rest[at + word.len()..]);
+        let preceded = before
+            .chars()
+            .next_back()
+            .or_else(|| out.chars().next_back());
+        let joined = preceded.is_some_and(is_word) || after.chars().next().is_some_and(is_word);
+        out.push_str(before);
+        out.push_str(if joined { word } else { with });
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
@@ -1,10 +1,17 @@
 use thiserror::Error;
 
+use zbus::fdo;
+
+use crate::daemon::DisconnectReason;
+
 #[derive(Error, Debug)]
 pub enum HachiError {
     #[error("D-Bus connection failed: {0}")]
     DbusConnection(#[from] zbus::Error),
 
+    #[error("Disconnected: {0}")]
+    Disconnected(DisconnectReason),
+
     #[error("D-Bus method call failed: {0}")]
     DbusCall(String),
 
@@ -20,8 +27,102 @@ pub enum HachiError {
     #[error("Battery limit out of range: {0}")]
     BatteryLimitOutOfRange(u8),
 
+    #[error("Permission denied writing {0}")]
+    PermissionDenied(String),
+
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+
+    #[error("Elevation failed: {0}")]
+    Elevation(String),
+
+    #[error("Invalid firmware attribute value: {0}")]
+    InvalidAttribute(String),
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArgs(String),
+
+    #[error("Config error: {0}")]
+    Config(String),
+
+    #[error("Serialization failed: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("Terminal error: {0}")]
     Terminal(#[from] std::io::Error),
 }
 
+/// How polkit turned down a request to asusd
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthDenial {
+    /// Authentication could have been requested, but no agent answered
+    AuthenticationRequired,
+    /// Policy refuses the change outright
+    NotAuthorized,
+}
+
+impl AuthDenial {
+    /// What to tell the user instead of the raw D-Bus error
+    pub fn hint(self) -> &'static str {
+        match self {
+            Self::AuthenticationRequired => {
+                "Authentication required: start a polkit agent (e.g. polkit-gnome) and retry"
+            }
+            Self::NotAuthorized => "Not authorized: polkit policy for asusd denies this change",
+        }
+    }
+}
+
+impl HachiError {
+    /// Recognize a polkit rejection, as opposed to any other failed call
+    pub fn auth_denial(&self) -> Option<AuthDenial> {
+        let Self::DbusConnection(e) = self else {
+            return None;
+        };
+        match e {
+            zbus::Error::FDO(fdo) => match **fdo {
+                fdo::Error::InteractiveAuthorizationRequired(_) => {
+                    Some(AuthDenial::AuthenticationRequired)
+                }
+                fdo::Error::AccessDenied(_) | fdo::Error::AuthFailed(_) => {
+                    Some(AuthDenial::NotAuthorized)
+                }
+                _ => None,
+            },
+            zbus::Error::MethodError(name, _, _) => match name.as_str() {
+                "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired" => {
+                    Some(AuthDenial::AuthenticationRequired)
+                }
+                "org.freedesktop.DBus.Error.AccessDenied"
+                | "org.freedesktop.DBus.Error.AuthFailed"
+                | "org.freedesktop.PolicyKit1.Error.NotAuthorized" => {
+                    Some(AuthDenial::NotAuthorized)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether the same call may succeed later (asusd restarting, a bus
+    /// timeout) rather than having been rejected outright
+    pub fn is_transient(&self) -> bool {
+        let Self::DbusConnection(e) = self else {
+            return false;
+        };
+        if let zbus::Error::FDO(fdo) = e {
+            if matches!(
+                **fdo,
+                fdo::Error::NoReply(_) | fdo::Error::Timeout(_) | fdo::Error::TimedOut(_)
+            ) {
+                return true;
+            }
+        }
+        matches!(
+            DisconnectReason::from_error(e),
+            Some(DisconnectReason::AsusdMissing | DisconnectReason::NoSystemBus)
+        )
+    }
+}
+
 pub type Result<T> = std::result::Result<T, HachiError>;
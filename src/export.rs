@@ -0,0 +1,142 @@
+//! Fan curve export as a standalone SVG image, for sharing tuning outside
+//! the terminal
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::{state_dir, TemperatureUnit};
+use crate::daemon::{FanCurve, FanPoint, PowerProfile};
+use crate::error::{HachiError, Result};
+
+const WIDTH: f32 = 640.0;
+const HEIGHT: f32 = 400.0;
+
+/// Plot area inside the image, leaving room for the title and axis labels
+const PLOT_X: f32 = 60.0;
+const PLOT_Y: f32 = 50.0;
+const PLOT_WIDTH: f32 = 550.0;
+const PLOT_HEIGHT: f32 = 290.0;
+
+/// Temperature span of the x axis, matching the in-app graph
+const TEMP_MIN: f32 = 30.0;
+const TEMP_MAX: f32 = 100.0;
+
+/// Theme colors (see `ui::theme::colors`)
+const BACKGROUND: &str = "#0d0d15";
+const GRID: &str = "#191923";
+const LABEL: &str = "#646478";
+const TITLE: &str = "#e6e6f0";
+const CPU: &str = "#3ccbe1";
+const GPU: &str = "#ff0055";
+
+/// Render the CPU and GPU curves as an SVG document
+pub fn fan_curve_svg(curve: &FanCurve, profile: PowerProfile, unit: TemperatureUnit) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="monospace" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{BACKGROUND}"/>"#
+    );
+
+    let status = if curve.enabled { "custom" } else { "default" };
+    let _ = writeln!(
+        svg,
+        r#"<text x="{PLOT_X}" y="28" fill="{TITLE}" font-size="16">hachi · {profile} · fan curve ({status})</text>"#
+    );
+
+    // Speed grid and labels every 25%
+    for speed in (0..=100).step_by(25) {
+        let y = y_for(speed as u8);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{PLOT_X}" y1="{y}" x2="{}" y2="{y}" stroke="{GRID}"/>"#,
+            PLOT_X + PLOT_WIDTH
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" fill="{LABEL}" text-anchor="end">{speed}%</text>"#,
+            PLOT_X - 8.0,
+            y + 4.0
+        );
+    }
+
+    // Temperature grid and labels every 10°C
+    for celsius in (TEMP_MIN as u8..=TEMP_MAX as u8).step_by(10) {
+        let x = x_for(celsius);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x}" y1="{PLOT_Y}" x2="{x}" y2="{}" stroke="{GRID}"/>"#,
+            PLOT_Y + PLOT_HEIGHT
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" fill="{LABEL}" text-anchor="middle">{}</text>"#,
+            PLOT_Y + PLOT_HEIGHT + 18.0,
+            unit.format(celsius as f32)
+        );
+    }
+
+    for (name, points, color, legend_x) in [
+        ("CPU", &curve.cpu_curve, CPU, PLOT_X),
+        ("GPU", &curve.gpu_curve, GPU, PLOT_X + 70.0),
+    ] {
+        plot(&mut svg, points, color);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{legend_x}" y="{}" fill="{color}">● {name}</text>"#,
+            HEIGHT - 16.0
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// One curve as a polyline with a dot per point
+fn plot(svg: &mut String, points: &[FanPoint], color: &str) {
+    let coords: Vec<String> = points
+        .iter()
+        .map(|point| format!("{:.1},{:.1}", x_for(point.temp), y_for(point.speed)))
+        .collect();
+    let _ = writeln!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="2"/>"#,
+        coords.join(" ")
+    );
+    for point in points {
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{:.1}" cy="{:.1}" r="4" fill="{color}"/>"#,
+            x_for(point.temp),
+            y_for(point.speed)
+        );
+    }
+}
+
+fn x_for(celsius: u8) -> f32 {
+    let ratio = ((celsius as f32).clamp(TEMP_MIN, TEMP_MAX) - TEMP_MIN) / (TEMP_MAX - TEMP_MIN);
+    PLOT_X + PLOT_WIDTH * ratio
+}
+
+fn y_for(speed: u8) -> f32 {
+    PLOT_Y + PLOT_HEIGHT * (1.0 - speed.min(100) as f32 / 100.0)
+}
+
+/// Write the curve to `fan-curve-<profile>.svg` in the state directory,
+/// returning the path
+pub fn export_fan_curve(
+    curve: &FanCurve,
+    profile: PowerProfile,
+    unit: TemperatureUnit,
+) -> Result<PathBuf> {
+    let dir = state_dir()
+        .ok_or_else(|| HachiError::Config("No state directory available".to_string()))?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("fan-curve-{}.svg", profile.as_str().to_lowercase()));
+    fs::write(&path, fan_curve_svg(curve, profile, unit))?;
+    Ok(path)
+}
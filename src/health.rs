@@ -0,0 +1,107 @@
+//! Startup health check: system bus, asusd and its interfaces, hwmon
+//! sensors and truecolor output, shown as a checklist before the dashboard
+//! (`D` runs it again)
+
+use std::time::Instant;
+
+use serde::Serialize;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Works, with some panels or features missing
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Pass => "✓",
+            Self::Warn => "!",
+            Self::Fail => "✗",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    pub fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn warn(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+        }
+    }
+
+    pub fn fail(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Checks that don't need the bus: temperature sensors and color support
+pub fn local_checks(temp_sensors: usize, colorterm: Option<&str>, safe_render: bool) -> Vec<Check> {
+    let sensors = match temp_sensors {
+        0 => Check::warn(
+            "hwmon sensors",
+            "none found: no temperatures or fan graph point",
+        ),
+        n => Check::pass("hwmon sensors", format!("{} temperature sensors", n)),
+    };
+    let truecolor = matches!(colorterm, Some("truecolor" | "24bit"));
+    let colors = if safe_render {
+        Check::pass("Truecolor", "safe rendering on: 256 colors and ASCII")
+    } else if truecolor {
+        Check::pass("Truecolor", "supported")
+    } else {
+        Check::warn(
+            "Truecolor",
+            "COLORTERM doesn't advertise it, try --safe-render on",
+        )
+    };
+    vec![sensors, colors]
+}
+
+/// Checklist popup state
+pub struct HealthView {
+    /// Bus checks from the hardware actor followed by the local ones,
+    /// empty while the actor is still checking
+    pub checks: Vec<Check>,
+    pub started: Instant,
+    /// When the results came in
+    pub finished: Option<Instant>,
+}
+
+impl HealthView {
+    pub fn running() -> Self {
+        Self {
+            checks: Vec::new(),
+            started: Instant::now(),
+            finished: None,
+        }
+    }
+
+    pub fn all_pass(&self) -> bool {
+        self.finished.is_some() && self.checks.iter().all(|c| c.status == CheckStatus::Pass)
+    }
+}
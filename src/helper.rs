@@ -0,0 +1,117 @@
+//! Privileged write helper, run through pkexec so the TUI never needs root
+//!
+//! `hachi --elevated-write <path> <value>` writes a single kernel attribute
+//! after checking it against a fixed allowlist; nothing else is accepted.
+
+use std::fs;
+use std::path::{Component, Path};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::{HachiError, Result};
+
+/// Command-line flag that switches the binary into helper mode
+pub const HELPER_FLAG: &str = "--elevated-write";
+
+/// Attributes the helper may write; `*` matches exactly one path component
+const ALLOWED: &[&str] = &[
+    "/sys/firmware/acpi/platform_profile",
+    "/sys/class/power_supply/*/charge_control_end_threshold",
+    "/sys/class/power_supply/*/charge_behaviour",
+    "/sys/class/backlight/*/brightness",
+    "/sys/devices/platform/asus-nb-wmi/fn_lock",
+    "/sys/devices/platform/asus-nb-wmi/touchpad",
+    "/sys/devices/platform/asus-nb-wmi/ppt_pl1_spl",
+    "/sys/devices/platform/asus-nb-wmi/ppt_pl2_sppt",
+    "/sys/devices/platform/asus-nb-wmi/ppt_fppt",
+    "/sys/class/firmware-attributes/asus-armoury/attributes/*/current_value",
+];
+
+/// Longest value the helper will write
+const MAX_VALUE_LEN: usize = 64;
+
+/// Whether permission failures are retried through pkexec
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether a graphical polkit agent is likely available; without one pkexec
+/// would prompt on the terminal underneath the TUI
+pub fn graphical_session() -> bool {
+    ["WAYLAND_DISPLAY", "DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// Whether the helper may write `path`
+pub fn is_allowed(path: &Path) -> bool {
+    let normal = path
+        .components()
+        .all(|c| matches!(c, Component::RootDir | Component::Normal(_)));
+    path.is_absolute() && normal && ALLOWED.iter().any(|pattern| matches_pattern(pattern, path))
+}
+
+fn matches_pattern(pattern: &str, path: &Path) -> bool {
+    let mut pattern = Path::new(pattern).components();
+    let mut path = path.components();
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some(p), Some(c)) if p.as_os_str() == "*" || p == c => {}
+            _ => return false,
+        }
+    }
+}
+
+/// Values are short, single-line printable ASCII (numbers and choice names)
+pub fn is_valid_value(value: &str) -> bool {
+    !value.is_empty() && value.len() <= MAX_VALUE_LEN && value.chars().all(|c| c.is_ascii_graphic())
+}
+
+/// Write `value` to `path` as root through pkexec
+pub fn elevated_write(path: &Path, value: &str) -> Result<()> {
+    if !is_allowed(path) || !is_valid_value(value) {
+        return Err(HachiError::PermissionDenied(path.display().to_string()));
+    }
+
+    let exe = std::env::current_exe()?;
+    let status = Command::new("pkexec")
+        .arg(exe)
+        .arg(HELPER_FLAG)
+        .arg(path)
+        .arg(value)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| HachiError::Elevation(format!("pkexec: {}", e)))?;
+
+    match status.code() {
+        Some(0) => Ok(()),
+        // pkexec exits 126 when authentication is dismissed and 127 when denied
+        Some(126) | Some(127) => Err(HachiError::PermissionDenied(path.display().to_string())),
+        _ => Err(HachiError::Elevation(format!("helper failed writing {}", path.display()))),
+    }
+}
+
+/// Entry point for `hachi --elevated-write <path> <value>` (running as root)
+pub fn run(args: &[String]) -> Result<()> {
+    let [path, value] = args else {
+        return Err(HachiError::Elevation(format!("usage: hachi {} <path> <value>", HELPER_FLAG)));
+    };
+    let path = Path::new(path);
+    if !is_allowed(path) {
+        return Err(HachiError::Elevation(format!("{} is not an allowed attribute", path.display())));
+    }
+    if !is_valid_value(value) {
+        return Err(HachiError::Elevation(format!("invalid value {:?}", value)));
+    }
+    fs::write(path, value)?;
+    Ok(())
+}
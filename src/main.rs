@@ -1,50 +1,119 @@
 #![allow(dead_code)]
 mod  app;
+mod armoury;
+mod automation;
+mod cli;
+mod clipboard;
+mod config;
+mod console;
 mod daemon;
+mod diagnose;
 mod error;
+mod export;
+mod health;
+mod helper;
+mod preset;
+mod recorder;
+mod session;
+mod stats;
+mod sysfs;
+mod telemetry;
+mod tune;
 mod ui;
 
 #[cfg(test)]
 mod tests;
 
+use std::fs;
 use std::io;
-use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
+    event::{
+        self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+        KeyCode, KeyEventKind, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use futures::FutureExt;
 use ratatui::{backend::CrosstermBackend, Terminal};
+use tokio::signal::unix::{signal, SignalKind};
 
 use crate::app::App;
+use crate::config::{state_dir, Config, SummaryOutput};
 use crate::daemon::DaemonHandle;
 
-/// Target frame rate
-const TARGET_FPS: u64 = 60;
-const FRAME_DURATION: Duration = Duration::from_millis(1000 / TARGET_FPS);
-
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Privileged helper mode, invoked through pkexec
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some(helper::HELPER_FLAG) {
+        helper::run(&args[1..])?;
+        return Ok(());
+    }
+
+    let args = match cli::Args::parse(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("hachi: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+    if args.version {
+        println!("hachi {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+    if let Some(path) = args.config {
+        config::set_config_path(path);
+    }
+    if let Some(dir) = args.profile_dir {
+        config::set_profile_dir(dir);
+    }
+
+    // Load config before touching the terminal so errors print cleanly
+    let config = Config::load()?;
+    helper::set_enabled(config.elevation.enabled.unwrap_or_else(helper::graphical_session));
+
+    if args.diagnose {
+        let mut daemon = DaemonHandle::spawn().read_only(true);
+        let bundle = diagnose::collect(&mut daemon).await;
+        println!("{}", bundle.to_json()?);
+        daemon.shutdown_and_wait(std::time::Duration::from_secs(1)).await;
+        return Ok(());
+    }
+
     // Initialize terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
-
-    // Hide cursor
-    terminal.hide_cursor()?;
+    enter_terminal(&mut terminal)?;
 
     // Spawn hardware actor
-    let daemon = DaemonHandle::spawn();
+    let daemon = DaemonHandle::spawn().read_only(args.read_only);
 
     // Request initial state
     daemon.refresh();
 
+    // Preferred settings; queued until the actor has connected
+    if let Some(profile) = config.startup.profile {
+        daemon.set_power_profile(profile);
+    }
+    if let Some(limit) = config.startup.charge_limit {
+        daemon.set_charge_limit(limit);
+    }
+
     // Create application (daemon ownership transferred)
-    let mut app = App::new(daemon);
+    let mut app = App::new(daemon, config);
+    app.safe_render = args.safe_render.unwrap_or_else(ui::compat::needs_safe_render);
+    app.debug_console = args.debug_console;
+    if args.plain {
+        app.set_plain();
+    }
 
     // Initialize sakura particles with terminal size
     let size = terminal.size()?;
@@ -53,26 +122,96 @@ async fn main() -> Result<()> {
     // Run the main loop
     let result = run_app(&mut terminal, &mut app).await;
 
-    // Shutdown hardware actor (app owns daemon)
-    app.shutdown();
+    // Shutdown hardware actor (app owns daemon), letting pending writes land
+    let flushed = app.shutdown().await;
 
     // Restore terminal
+    leave_terminal(&mut terminal)?;
+
+    if !flushed {
+        eprintln!("hachi: timed out waiting for hardware writes; some may not have been applied");
+    }
+    if let Err(e) = app.session().save() {
+        eprintln!("hachi: couldn't save session state: {}", e);
+    }
+    report_session(&app)?;
+
+    result
+}
+
+/// Switch to raw mode and the alternate screen
+fn enter_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
+    terminal.hide_cursor()?;
+    Ok(())
+}
+
+/// Give the terminal back in the state we found it
+fn leave_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableFocusChange
     )?;
     terminal.show_cursor()?;
+    Ok(())
+}
 
-    result
+/// Stop like any job-controlled program (Ctrl+Z / SIGTSTP), restoring the
+/// terminal first, and take it back with a full redraw once resumed (`fg`)
+fn suspend(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
+    leave_terminal(terminal)?;
+
+    // SIGTSTP is handled by us, so stop with SIGSTOP; execution resumes
+    // here on SIGCONT
+    // SAFETY: raise only signals the calling thread
+    unsafe {
+        libc::raise(libc::SIGSTOP);
+    }
+
+    enter_terminal(terminal)?;
+    terminal.clear()?;
+    let size = terminal.size()?;
+    app.resize(size.width, size.height);
+    Ok(())
+}
+
+/// Emit the session statistics summary according to config
+fn report_session(app: &App) -> Result<()> {
+    let summary = app.stats.summary(app.config.ui.temperature_unit);
+    match app.config.session.summary {
+        SummaryOutput::Print => print!("{}", summary),
+        SummaryOutput::File => {
+            if let Some(dir) = state_dir() {
+                fs::create_dir_all(&dir)?;
+                fs::write(dir.join("last-session.txt"), summary)?;
+            }
+        }
+        SummaryOutput::None => {}
+    }
+    Ok(())
 }
 
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
 ) -> Result<()> {
+    // Raw mode swallows Ctrl+Z, but `kill -TSTP` still needs handling
+    let mut sigtstp = signal(SignalKind::from_raw(libc::SIGTSTP))?;
+
     loop {
+        if sigtstp.recv().now_or_never().is_some() {
+            suspend(terminal, app)?;
+        }
+
         // Process hardware updates
         app.process_updates();
 
@@ -80,19 +219,33 @@ async fn run_app(
         app.tick();
 
         // Render
-        terminal.draw(|frame| {
-            app.render(frame);
-        })?;
+        if app.should_draw() {
+            let started = Instant::now();
+            terminal.draw(|frame| {
+                app.render(frame);
+            })?;
+            app.record_frame(started.elapsed());
+        }
 
         // Handle input with timeout for smooth animation
-        if event::poll(FRAME_DURATION)? {
+        if event::poll(app.frame_duration())? {
             match event::read()? {
+                Event::Key(key)
+                    if key.kind == KeyEventKind::Press
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('z') =>
+                {
+                    suspend(terminal, app)?;
+                }
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
                     app.handle_key(key);
                 }
+                Event::Mouse(mouse) => app.handle_mouse(mouse),
                 Event::Resize(width, height) => {
                     app.resize(width, height);
                 }
+                Event::FocusGained => app.set_terminal_focus(true),
+                Event::FocusLost => app.set_terminal_focus(false),
                 _ => {}
             }
         }
@@ -0,0 +1,119 @@
+//! Saved setting bundles (profile, charge limit, fan curve, keyboard
+//! lighting), one TOML file each in the preset directory
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::profile_dir;
+use crate::daemon::{FanCurve, HardwareState, KbdBrightness, PowerProfile, Rgb};
+use crate::error::{HachiError, Result};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    /// File stem, not stored in the file itself
+    #[serde(skip)]
+    pub name: String,
+    pub power_profile: PowerProfile,
+    pub charge_limit: u8,
+    /// Keyboard backlight idle timeout in seconds, when supported
+    #[serde(default)]
+    pub kbd_idle_timeout: Option<u32>,
+    /// Keyboard brightness and static color, when an Aura keyboard was found
+    #[serde(default)]
+    pub kbd_brightness: Option<KbdBrightness>,
+    #[serde(default)]
+    pub kbd_color: Option<Rgb>,
+    pub fan_curve: FanCurve,
+}
+
+impl Preset {
+    /// Capture the current hardware state under a name
+    pub fn from_state(name: &str, state: &HardwareState) -> Self {
+        Self {
+            name: name.to_string(),
+            power_profile: state.power_profile,
+            charge_limit: state.charge_limit,
+            kbd_idle_timeout: state.aura.as_ref().and_then(|aura| aura.idle_timeout),
+            kbd_brightness: state.aura.as_ref().and_then(|aura| aura.brightness),
+            kbd_color: state.aura.as_ref().and_then(|aura| aura.color),
+            fan_curve: state.fan_curve.clone(),
+        }
+    }
+
+    /// Every readable preset, sorted by name. Unparseable files are skipped.
+    pub fn load_all() -> Vec<Preset> {
+        let Some(entries) = profile_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+            return Vec::new();
+        };
+
+        let mut presets: Vec<Preset> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_str()?.to_string();
+                Self::parse(&name, &fs::read_to_string(&path).ok()?).ok()
+            })
+            .collect();
+        presets.sort_by(|a, b| a.name.cmp(&b.name));
+        presets
+    }
+
+    pub fn parse(name: &str, contents: &str) -> Result<Self> {
+        let mut preset: Preset = toml::from_str(contents)
+            .map_err(|e| HachiError::Config(format!("preset {}: {}", name, e)))?;
+        preset
+            .fan_curve
+            .validate()
+            .map_err(|e| HachiError::Config(format!("preset {}: {}", name, e)))?;
+        preset.name = name.to_string();
+        Ok(preset)
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| HachiError::Config(e.to_string()))
+    }
+
+    /// Write the preset file, creating the directory if needed
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path(&self.name)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    pub fn delete(&self) -> Result<()> {
+        fs::remove_file(Self::path(&self.name)?)?;
+        Ok(())
+    }
+
+    /// Move the preset file to a new name, refusing to overwrite another
+    pub fn rename(&mut self, name: &str) -> Result<()> {
+        let from = Self::path(&self.name)?;
+        let to = Self::path(name)?;
+        if to.exists() {
+            return Err(HachiError::Config(format!("preset {} already exists", name)));
+        }
+        fs::rename(from, to)?;
+        self.name = name.to_string();
+        Ok(())
+    }
+
+    fn path(name: &str) -> Result<PathBuf> {
+        if !valid_name(name) {
+            return Err(HachiError::Config(format!("invalid preset name {:?}", name)));
+        }
+        let dir = profile_dir()
+            .ok_or_else(|| HachiError::Config("No config directory available".to_string()))?;
+        Ok(dir.join(format!("{}.toml", name)))
+    }
+}
+
+/// Names become file names: no path separators, no hidden files
+pub fn valid_name(name: &str) -> bool {
+    !name.trim().is_empty() && !name.contains('/') && !name.starts_with('.')
+}
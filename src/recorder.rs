@@ -0,0 +1,112 @@
+//! Telemetry recording to CSV or JSON Lines for offline analysis
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::config::{state_dir, RecordingConfig, RecordingFormat};
+use crate::daemon::PowerProfile;
+use crate::error::{HachiError, Result};
+use crate::telemetry::Telemetry;
+
+/// One timestamped telemetry sample
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryRecord {
+    /// Unix timestamp in seconds
+    pub timestamp: f64,
+    pub profile: Option<&'static str>,
+    pub cpu_load: f32,
+    pub cpu_freq_mhz: Option<u32>,
+    pub cpu_temp: Option<f32>,
+    pub gpu_temp: Option<f32>,
+    pub mem_used: f32,
+    pub battery_percent: Option<u8>,
+    pub battery_power_w: Option<f32>,
+}
+
+impl TelemetryRecord {
+    const CSV_HEADER: &'static str = "timestamp,profile,cpu_load,cpu_freq_mhz,cpu_temp,gpu_temp,mem_used,battery_percent,battery_power_w";
+
+    pub fn capture(profile: Option<PowerProfile>, telemetry: &Telemetry) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        Self {
+            timestamp,
+            profile: profile.map(|p| p.as_str()),
+            cpu_load: telemetry.average_usage(),
+            cpu_freq_mhz: telemetry.average_freq_mhz(),
+            cpu_temp: telemetry.cpu_temp(),
+            gpu_temp: telemetry.gpu_temp(),
+            mem_used: telemetry.memory.used_ratio(),
+            battery_percent: telemetry.battery.as_ref().map(|b| b.capacity),
+            battery_power_w: telemetry.battery.as_ref().and_then(|b| b.power_w),
+        }
+    }
+
+    /// Format as a CSV row (empty fields for missing values)
+    pub fn to_csv(&self) -> String {
+        fn opt<T: ToString>(v: Option<T>) -> String {
+            v.map(|v| v.to_string()).unwrap_or_default()
+        }
+        format!(
+            "{:.3},{},{:.3},{},{},{},{:.3},{},{}",
+            self.timestamp,
+            self.profile.unwrap_or_default(),
+            self.cpu_load,
+            opt(self.cpu_freq_mhz),
+            opt(self.cpu_temp),
+            opt(self.gpu_temp),
+            self.mem_used,
+            opt(self.battery_percent),
+            opt(self.battery_power_w),
+        )
+    }
+}
+
+/// Appends telemetry records to a file while active
+pub struct Recorder {
+    file: File,
+    format: RecordingFormat,
+    pub path: PathBuf,
+}
+
+impl Recorder {
+    /// Open (or create) the recording file in append mode
+    pub fn start(config: &RecordingConfig) -> Result<Self> {
+        let path = match &config.path {
+            Some(path) => path.clone(),
+            None => state_dir()
+                .ok_or_else(|| HachiError::Config("No state directory available".to_string()))?
+                .join(format!("telemetry.{}", config.format.extension())),
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        if config.format == RecordingFormat::Csv && file.metadata()?.len() == 0 {
+            writeln!(file, "{}", TelemetryRecord::CSV_HEADER)?;
+        }
+
+        Ok(Self {
+            file,
+            format: config.format,
+            path,
+        })
+    }
+
+    /// Append a single record
+    pub fn write(&mut self, record: &TelemetryRecord) -> Result<()> {
+        let line = match self.format {
+            RecordingFormat::Csv => record.to_csv(),
+            RecordingFormat::Jsonl => serde_json::to_string(record)?,
+        };
+        writeln!(self.file, "{}", line)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! UI state remembered between runs, kept in `$XDG_STATE_HOME/hachi/session.toml`
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::{FocusedPanel, Tab};
+use crate::config::state_dir;
+use crate::daemon::{PowerLimits, PowerProfile};
+use crate::error::{HachiError, Result};
+
+/// Where the user left off
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiSession {
+    pub tab: Tab,
+    pub focused: FocusedPanel,
+    pub sakura_enabled: bool,
+    pub profile_limits: ProfileLimits,
+    /// Fastest fan speed seen under each profile once settled, in RPM
+    pub profile_fans: PerProfile<u32>,
+}
+
+/// Power limits last seen under each profile, so the profile selector can
+/// show them without switching
+pub type ProfileLimits = PerProfile<PowerLimits>;
+
+/// One value per power profile, learned while that profile was active
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerProfile<T> {
+    quiet: Option<T>,
+    balanced: Option<T>,
+    performance: Option<T>,
+}
+
+impl<T> Default for PerProfile<T> {
+    fn default() -> Self {
+        Self {
+            quiet: None,
+            balanced: None,
+            performance: None,
+        }
+    }
+}
+
+impl<T: Copy> PerProfile<T> {
+    pub fn get(&self, profile: PowerProfile) -> Option<T> {
+        match profile {
+            PowerProfile::Quiet => self.quiet,
+            PowerProfile::Balanced => self.balanced,
+            PowerProfile::Performance => self.performance,
+        }
+    }
+
+    pub fn set(&mut self, profile: PowerProfile, value: T) {
+        let slot = match profile {
+            PowerProfile::Quiet => &mut self.quiet,
+            PowerProfile::Balanced => &mut self.balanced,
+            PowerProfile::Performance => &mut self.performance,
+        };
+        *slot = Some(value);
+    }
+}
+
+impl Default for UiSession {
+    fn default() -> Self {
+        Self {
+            tab: Tab::Dashboard,
+            focused: FocusedPanel::PowerProfile,
+            sakura_enabled: true,
+            profile_limits: ProfileLimits::default(),
+            profile_fans: PerProfile::default(),
+        }
+    }
+}
+
+impl UiSession {
+    pub fn path() -> Option<PathBuf> {
+        state_dir().map(|dir| dir.join("session.toml"))
+    }
+
+    /// Restore the previous session; a missing or unreadable file just
+    /// means starting fresh
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = Self::path() else {
+            return Err(HachiError::Config("No state directory available".to_string()));
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = toml::to_string(self).map_err(|e| HachiError::Config(e.to_string()))?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+//! Per-session statistics, summarized when the user quits
+
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::config::TemperatureUnit;
+use crate::daemon::PowerProfile;
+use crate::telemetry::Telemetry;
+
+/// Running min/max/average for one temperature source
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TempStats {
+    pub min: f32,
+    pub max: f32,
+    sum: f64,
+    count: u64,
+}
+
+impl TempStats {
+    pub fn record(&mut self, celsius: f32) {
+        if self.count == 0 {
+            self.min = celsius;
+            self.max = celsius;
+        } else {
+            self.min = self.min.min(celsius);
+            self.max = self.max.max(celsius);
+        }
+        self.sum += celsius as f64;
+        self.count += 1;
+    }
+
+    pub fn average(&self) -> Option<f32> {
+        (self.count > 0).then(|| (self.sum / self.count as f64) as f32)
+    }
+}
+
+/// Statistics accumulated over the lifetime of the app
+pub struct SessionStats {
+    started: Instant,
+    last_record: Option<Instant>,
+    pub cpu_temp: TempStats,
+    pub gpu_temp: TempStats,
+    /// Time spent in Quiet, Balanced, Performance (UI order)
+    pub profile_time: [Duration; 3],
+    /// Energy drawn from the battery while discharging
+    pub battery_energy_wh: f64,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            last_record: None,
+            cpu_temp: TempStats::default(),
+            gpu_temp: TempStats::default(),
+            profile_time: [Duration::ZERO; 3],
+            battery_energy_wh: 0.0,
+        }
+    }
+
+    /// Fold a fresh telemetry sample into the statistics
+    pub fn record(&mut self, profile: Option<PowerProfile>, telemetry: &Telemetry) {
+        let now = Instant::now();
+        let elapsed = self
+            .last_record
+            .map(|last| now.duration_since(last))
+            .unwrap_or_default();
+        self.last_record = Some(now);
+
+        if let Some(temp) = telemetry.cpu_temp() {
+            self.cpu_temp.record(temp);
+        }
+        if let Some(temp) = telemetry.gpu_temp() {
+            self.gpu_temp.record(temp);
+        }
+
+        if let Some(profile) = profile {
+            self.profile_time[profile.ui_index()] += elapsed;
+        }
+
+        let discharge_w = telemetry
+            .battery
+            .as_ref()
+            .filter(|battery| battery.is_discharging())
+            .and_then(|battery| battery.power_w);
+        if let Some(watts) = discharge_w {
+            self.battery_energy_wh += watts as f64 * elapsed.as_secs_f64() / 3600.0;
+        }
+    }
+
+    /// Human-readable multi-line summary
+    pub fn summary(&self, unit: TemperatureUnit) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "hachi session · {}", format_duration(self.started.elapsed()));
+
+        for (name, stats) in [("CPU", &self.cpu_temp), ("GPU", &self.gpu_temp)] {
+            if let Some(avg) = stats.average() {
+                let _ = writeln!(
+                    out,
+                    "  {} temp   min {}  avg {}  max {}",
+                    name,
+                    unit.format(stats.min),
+                    unit.format(avg),
+                    unit.format(stats.max)
+                );
+            }
+        }
+
+        let profiles: Vec<String> = PowerProfile::ALL
+            .iter()
+            .zip(self.profile_time.iter())
+            .filter(|(_, time)| !time.is_zero())
+            .map(|(profile, time)| format!("{} {}", profile, format_duration(*time)))
+            .collect();
+        if !profiles.is_empty() {
+            let _ = writeln!(out, "  Profiles   {}", profiles.join(" · "));
+        }
+
+        if self.battery_energy_wh > 0.0 {
+            let _ = writeln!(out, "  Energy     ≈ {:.1} Wh on battery", self.battery_energy_wh);
+        }
+
+        out
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Format a duration as `1h 02m`, `3m 15s` or `42s`
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{}h {:02}m", h, m)
+    } else if m > 0 {
+        format!("{}m {:02}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
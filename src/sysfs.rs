@@ -0,0 +1,314 @@
+//! Helpers for kernel attributes under `/sys`
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::armoury;
+use crate::error::{HachiError, Result};
+use crate::helper;
+
+/// Read an attribute, trimming the trailing newline
+pub fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Write an attribute, retrying through the privileged helper when
+/// permission is denied and elevation is enabled
+pub fn write(path: &Path, value: &str) -> Result<()> {
+    match fs::write(path, value) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied && helper::enabled() => {
+            helper::elevated_write(path, value)
+        }
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            Err(HachiError::PermissionDenied(path.display().to_string()))
+        }
+        Err(e) => Err(HachiError::from(e)),
+    }
+}
+
+/// Parse a choice attribute like `[auto] inhibit-charge force-discharge`
+/// into (available choices, active choice)
+pub fn parse_choices(contents: &str) -> (Vec<String>, Option<String>) {
+    let mut active = None;
+    let choices = contents
+        .split_whitespace()
+        .map(|word| match word.strip_prefix('[').and_then(|w| w.strip_suffix(']')) {
+            Some(selected) => {
+                active = Some(selected.to_string());
+                selected.to_string()
+            }
+            None => word.to_string(),
+        })
+        .collect();
+    (choices, active)
+}
+
+/// First power supply of type `Battery`
+pub fn battery_dir() -> Option<PathBuf> {
+    let mut supplies: Vec<_> = fs::read_dir("/sys/class/power_supply")
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    supplies.sort();
+
+    supplies
+        .into_iter()
+        .find(|dir| read_trimmed(&dir.join("type")).as_deref() == Some("Battery"))
+}
+
+/// Battery charge limit in percent
+pub fn charge_limit() -> Option<u8> {
+    read_trimmed(&battery_dir()?.join("charge_control_end_threshold"))?
+        .parse()
+        .ok()
+}
+
+pub fn set_charge_limit(limit: u8) -> Result<()> {
+    let dir = battery_dir()
+        .ok_or_else(|| HachiError::Unsupported("No battery found".to_string()))?;
+    write(&dir.join("charge_control_end_threshold"), &limit.to_string())
+}
+
+const PLATFORM_PROFILE: &str = "/sys/firmware/acpi/platform_profile";
+
+/// Active ACPI platform profile, e.g. `balanced`
+pub fn platform_profile() -> Option<String> {
+    read_trimmed(Path::new(PLATFORM_PROFILE))
+}
+
+/// Profiles accepted by `platform_profile`
+pub fn platform_profile_choices() -> Vec<String> {
+    read_trimmed(Path::new("/sys/firmware/acpi/platform_profile_choices"))
+        .map(|v| v.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+pub fn set_platform_profile(profile: &str) -> Result<()> {
+    write(Path::new(PLATFORM_PROFILE), profile)
+}
+
+/// Whether the kernel interfaces can stand in for asusd
+pub fn kernel_backend_available() -> bool {
+    Path::new(PLATFORM_PROFILE).exists() || armoury::available()
+}
+
+/// Whether the battery is in bypass ("inhibit-charge") mode,
+/// or `None` when the firmware doesn't support it
+pub fn charge_bypass() -> Option<bool> {
+    let contents = read_trimmed(&battery_dir()?.join("charge_behaviour"))?;
+    let (choices, active) = parse_choices(&contents);
+    if !choices.iter().any(|c| c == "inhibit-charge") {
+        return None;
+    }
+    Some(active.as_deref() == Some("inhibit-charge"))
+}
+
+/// Switch between bypass ("inhibit-charge") and normal ("auto") charging
+pub fn set_charge_bypass(enabled: bool) -> Result<()> {
+    let dir = battery_dir()
+        .ok_or_else(|| HachiError::Unsupported("No battery found".to_string()))?;
+    let value = if enabled { "inhibit-charge" } else { "auto" };
+    write(&dir.join("charge_behaviour"), value)
+}
+
+/// Display backlight under `/sys/class/backlight`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Backlight {
+    /// Device name, e.g. `amdgpu_bl1` or `intel_backlight`
+    pub device: String,
+    pub brightness: u32,
+    pub max_brightness: u32,
+}
+
+impl Backlight {
+    /// Brightness as a percentage of the maximum
+    pub fn percent(&self) -> u8 {
+        if self.max_brightness == 0 {
+            return 0;
+        }
+        (self.brightness as u64 * 100 / self.max_brightness as u64) as u8
+    }
+
+    /// Raw brightness value for a percentage of the maximum
+    pub fn raw_for_percent(&self, percent: u8) -> u32 {
+        (self.max_brightness as u64 * percent.min(100) as u64 / 100) as u32
+    }
+
+    /// Raw brightness `delta` percent away from the current one. Moves at
+    /// least one raw unit, so coarse ranges (say 0-10) don't round back to
+    /// the same value, and stays above 0 so the screen never goes dark.
+    pub fn stepped(&self, delta: i16) -> u32 {
+        let percent = (self.percent() as i16 + delta).clamp(1, 100) as u8;
+        let raw = self.raw_for_percent(percent);
+        let raw = match delta.signum() {
+            1 => raw.max(self.brightness + 1),
+            -1 => raw.min(self.brightness.saturating_sub(1)),
+            _ => raw,
+        };
+        raw.clamp(1, self.max_brightness.max(1))
+    }
+}
+
+/// First display backlight device, if any
+pub fn backlight() -> Option<Backlight> {
+    let mut devices: Vec<_> = fs::read_dir("/sys/class/backlight")
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    devices.sort();
+
+    devices.into_iter().find_map(|dir| {
+        Some(Backlight {
+            device: dir.file_name()?.to_string_lossy().into_owned(),
+            brightness: read_trimmed(&dir.join("brightness"))?.parse().ok()?,
+            max_brightness: read_trimmed(&dir.join("max_brightness"))?.parse().ok()?,
+        })
+    })
+}
+
+/// Write the backlight directly (needs root or a udev rule)
+pub fn set_backlight(device: &str, brightness: u32) -> Result<()> {
+    let path = Path::new("/sys/class/backlight").join(device).join("brightness");
+    write(&path, &brightness.to_string())
+}
+
+/// asus-wmi platform device attributes
+const ASUS_WMI_DIR: &str = "/sys/devices/platform/asus-nb-wmi";
+
+/// Boolean peripheral switches exposed by asus-wmi on some models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Peripheral {
+    FnLock,
+    Touchpad,
+}
+
+impl Peripheral {
+    pub const ALL: [Peripheral; 2] = [Peripheral::FnLock, Peripheral::Touchpad];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FnLock => "Fn-lock",
+            Self::Touchpad => "Touchpad",
+        }
+    }
+
+    fn attribute(&self) -> &'static str {
+        match self {
+            Self::FnLock => "fn_lock",
+            Self::Touchpad => "touchpad",
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        Path::new(ASUS_WMI_DIR).join(self.attribute())
+    }
+
+    /// Current state, or `None` when the kernel doesn't expose the switch
+    pub fn read(&self) -> Option<bool> {
+        read_trimmed(&self.path()).map(|v| v == "1")
+    }
+
+    pub fn write(&self, enabled: bool) -> Result<()> {
+        let path = self.path();
+        if !path.exists() {
+            return Err(HachiError::Unsupported(format!("{} switch", self.as_str())));
+        }
+        write(&path, if enabled { "1" } else { "0" })
+    }
+}
+
+/// Raw GPU MUX setting (0 = dGPU drives the panel, 1 = hybrid), preferring
+/// asus-armoury over asus-wmi
+pub fn gpu_mux_mode() -> Option<u8> {
+    if let Some(attribute) = armoury::attribute("gpu_mux_mode") {
+        return attribute.current.parse().ok();
+    }
+    read_trimmed(&Path::new(ASUS_WMI_DIR).join("gpu_mux_mode"))?
+        .parse()
+        .ok()
+}
+
+/// Move the GPU MUX, preferring asus-armoury over asus-wmi. The firmware
+/// applies it on the next boot.
+pub fn set_gpu_mux_mode(value: u8) -> Result<()> {
+    if armoury::attribute("gpu_mux_mode").is_some() {
+        return armoury::write("gpu_mux_mode", &value.to_string());
+    }
+    let path = Path::new(ASUS_WMI_DIR).join("gpu_mux_mode");
+    if !path.exists() {
+        return Err(HachiError::Unsupported("GPU MUX".to_string()));
+    }
+    write(&path, &value.to_string())
+}
+
+/// Marketing model name from DMI, e.g. `ROG Zephyrus G14 GA402RJ`
+pub fn product_name() -> Option<String> {
+    read_trimmed(Path::new("/sys/class/dmi/id/product_name")).filter(|name| !name.is_empty())
+}
+
+/// Whether this machine is an ROG Ally handheld (RC71L / RC72L)
+pub fn is_rog_ally() -> bool {
+    let dmi = Path::new("/sys/class/dmi/id");
+    let board = read_trimmed(&dmi.join("board_name")).unwrap_or_default();
+    let product = read_trimmed(&dmi.join("product_name")).unwrap_or_default();
+    board.starts_with("RC71L") || board.starts_with("RC72L") || product.contains("ROG Ally")
+}
+
+/// Sustained, short-boost and fast-boost package power limits
+const PPT_ATTRIBUTES: [&str; 3] = ["ppt_pl1_spl", "ppt_pl2_sppt", "ppt_fppt"];
+
+/// Current sustained power limit in watts, if exposed
+pub fn tdp() -> Option<u8> {
+    power_limit(PPT_ATTRIBUTES[0])
+}
+
+/// Current short boost power limit in watts, if exposed
+pub fn tdp_boost() -> Option<u8> {
+    power_limit(PPT_ATTRIBUTES[1])
+}
+
+fn power_limit(name: &str) -> Option<u8> {
+    if let Some(attribute) = armoury::attribute(name) {
+        return attribute.current.parse().ok();
+    }
+    read_trimmed(&Path::new(ASUS_WMI_DIR).join(name))?
+        .parse()
+        .ok()
+}
+
+/// Accepted sustained power limit range in watts (asus-armoury only;
+/// asus-wmi doesn't report one)
+pub fn tdp_range() -> Option<(u8, u8)> {
+    let attribute = armoury::attribute(PPT_ATTRIBUTES[0])?;
+    let min = u8::try_from(attribute.min?).ok()?;
+    let max = u8::try_from(attribute.max?).unwrap_or(u8::MAX);
+    Some((min, max))
+}
+
+/// Set all package power limits to the same wattage, preferring asus-armoury
+/// (clamped to each limit's range) over the older asus-wmi attributes
+pub fn set_tdp(watts: u8) -> Result<()> {
+    if armoury::attribute(PPT_ATTRIBUTES[0]).is_some() {
+        for name in PPT_ATTRIBUTES {
+            if let Some(attribute) = armoury::attribute(name) {
+                armoury::write(name, &attribute.clamp(watts as i64).to_string())?;
+            }
+        }
+        return Ok(());
+    }
+
+    let dir = Path::new(ASUS_WMI_DIR);
+    if !dir.join(PPT_ATTRIBUTES[0]).exists() {
+        return Err(HachiError::Unsupported("Power limits".to_string()));
+    }
+    for attribute in PPT_ATTRIBUTES {
+        write(&dir.join(attribute), &watts.to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,735 @@
+//! System telemetry sampled from procfs and sysfs
+//!
+//! Unlike hardware control, telemetry needs no daemon round-trip, so it is
+//! sampled directly from the UI loop at a low rate.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::config::FanTempSource;
+use crate::sysfs::{self, read_trimmed};
+
+/// How often telemetry is resampled
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Resample interval during low-power rendering
+pub const LOW_POWER_SAMPLE_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Weight of each new power reading in the smoothed battery rate; the
+/// instantaneous figure jumps with every load spike
+const RATE_SMOOTHING: f32 = 0.15;
+
+/// Readings before a time estimate is shown to the minute
+const CONFIDENT_SAMPLES: u32 = 20;
+
+/// Smoothed deviation, as a fraction of the rate, above which a time
+/// estimate is only shown in hours
+const CONFIDENT_SPREAD: f32 = 0.15;
+
+/// Full charges a year assumed by the charge limit preview: one a day
+const CHARGES_PER_YEAR: f32 = 365.0;
+
+/// Charge limit points over which the wear of a cycle roughly halves; cycle
+/// life about doubles per 0.1 V less charge voltage, some 15% of capacity
+const WEAR_HALVING_PERCENT: f32 = 15.0;
+
+/// Samples kept for the CPU temperature trend line
+pub const TREND_SAMPLES: usize = 120;
+
+/// Cooling device types that slow the CPU down rather than spin a fan:
+/// ACPI passive cooling and thermald's idle injection
+const CPU_COOLING_TYPES: [&str; 2] = ["Processor", "intel_powerclamp"];
+
+/// Raw jiffy counters for a single CPU line of `/proc/stat`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub busy: u64,
+    pub total: u64,
+}
+
+/// Per-core utilization and frequency
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CoreSample {
+    /// Utilization over the last sample interval (0.0 - 1.0)
+    pub usage: f32,
+    /// Current scaling frequency in MHz
+    pub freq_mhz: Option<u32>,
+    /// Maximum hardware frequency in MHz
+    pub max_freq_mhz: Option<u32>,
+}
+
+/// RAM and swap usage from `/proc/meminfo` (values in KiB)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub total_kb: u64,
+    pub available_kb: u64,
+    pub swap_total_kb: u64,
+    pub swap_free_kb: u64,
+}
+
+impl MemoryUsage {
+    pub fn used_kb(&self) -> u64 {
+        self.total_kb.saturating_sub(self.available_kb)
+    }
+
+    pub fn swap_used_kb(&self) -> u64 {
+        self.swap_total_kb.saturating_sub(self.swap_free_kb)
+    }
+
+    /// Fraction of RAM in use (0.0 - 1.0)
+    pub fn used_ratio(&self) -> f32 {
+        ratio(self.used_kb(), self.total_kb)
+    }
+
+    /// Fraction of swap in use (0.0 - 1.0)
+    pub fn swap_used_ratio(&self) -> f32 {
+        ratio(self.swap_used_kb(), self.swap_total_kb)
+    }
+}
+
+/// A temperature reading from a hwmon sensor
+#[derive(Debug, Clone, PartialEq)]
+pub struct TempSensor {
+    /// hwmon chip name (e.g. `nvme`, `asusec`, `k10temp`)
+    pub chip: String,
+    /// Sensor label (`tempN_label`, or `tempN` when the driver provides none)
+    pub label: String,
+    /// Temperature in °C
+    pub celsius: f32,
+}
+
+impl TempSensor {
+    /// Stable identifier used for hiding sensors in config (`chip/label`)
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.chip, self.label)
+    }
+
+    /// Short human-readable name for compact display
+    pub fn display_name(&self) -> String {
+        match (self.chip.as_str(), self.label.as_str()) {
+            ("nvme", "Composite") => "NVMe".to_string(),
+            ("asusec", label) => format!("EC {}", label),
+            (chip, label) => format!("{} {}", chip, label),
+        }
+    }
+
+    /// Whether this sensor matches a hidden entry (full id or bare chip name)
+    pub fn is_hidden(&self, hidden: &[String]) -> bool {
+        let id = self.id();
+        hidden.iter().any(|h| *h == id || *h == self.chip)
+    }
+}
+
+/// A fan tachometer reading from a hwmon sensor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FanSensor {
+    pub chip: String,
+    /// Sensor label (`fanN_label`, e.g. `cpu_fan`, or `fanN`)
+    pub label: String,
+    pub rpm: u32,
+}
+
+/// Battery charge state from `/sys/class/power_supply`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatteryInfo {
+    /// Charge level (0-100)
+    pub capacity: u8,
+    /// Kernel status string (`Charging`, `Discharging`, `Full`, `Not charging`)
+    pub status: String,
+    /// Instantaneous power flow in watts
+    pub power_w: Option<f32>,
+    /// Stored energy in Wh
+    pub energy_wh: Option<f32>,
+    /// Stored energy when full, in Wh
+    pub energy_full_wh: Option<f32>,
+}
+
+impl BatteryInfo {
+    pub fn is_charging(&self) -> bool {
+        self.status == "Charging"
+    }
+
+    pub fn is_discharging(&self) -> bool {
+        self.status == "Discharging"
+    }
+}
+
+/// Exponentially smoothed battery power, for time-to-empty and
+/// time-to-full estimates. Starts over whenever the battery switches
+/// between charging and discharging.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BatteryRate {
+    charging: bool,
+    /// Smoothed power flow in watts
+    watts: f32,
+    /// Smoothed absolute deviation of readings from `watts`
+    spread: f32,
+    samples: u32,
+}
+
+impl BatteryRate {
+    /// Fold in a reading; a battery that is neither charging nor
+    /// discharging, or reports no power, resets the average
+    pub fn record(&mut self, battery: Option<&BatteryInfo>) {
+        let reading = battery.and_then(|battery| {
+            let watts = battery.power_w.filter(|w| *w > 0.0)?;
+            match (battery.is_charging(), battery.is_discharging()) {
+                (true, _) => Some((true, watts)),
+                (_, true) => Some((false, watts)),
+                _ => None,
+            }
+        });
+        let Some((charging, watts)) = reading else {
+            *self = Self::default();
+            return;
+        };
+
+        if self.samples == 0 || charging != self.charging {
+            *self = Self {
+                charging,
+                watts,
+                spread: 0.0,
+                samples: 1,
+            };
+            return;
+        }
+        let deviation = (watts - self.watts).abs();
+        self.watts += RATE_SMOOTHING * (watts - self.watts);
+        self.spread += RATE_SMOOTHING * (deviation - self.spread);
+        self.samples += 1;
+    }
+
+    /// Smoothed draw while discharging
+    pub fn discharge_watts(&self) -> Option<f32> {
+        (self.samples > 0 && !self.charging).then_some(self.watts)
+    }
+
+    /// Time until the battery is empty, or until it reaches `charge_limit`
+    /// percent while charging
+    pub fn estimate(&self, battery: &BatteryInfo, charge_limit: u8) -> Option<TimeEstimate> {
+        if self.samples == 0 || self.watts <= 0.0 {
+            return None;
+        }
+        let now = battery.energy_wh?;
+        let energy = if self.charging {
+            let limit = if charge_limit == 0 { 100 } else { charge_limit.min(100) };
+            battery.energy_full_wh? * limit as f32 / 100.0 - now
+        } else {
+            now
+        };
+        if energy <= 0.0 {
+            return None;
+        }
+
+        Some(TimeEstimate {
+            remaining: Duration::from_secs_f32(energy / self.watts * 3600.0),
+            to_full: self.charging,
+            confident: self.samples >= CONFIDENT_SAMPLES
+                && self.spread <= self.watts * CONFIDENT_SPREAD,
+        })
+    }
+}
+
+/// Smoothed battery time remaining
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeEstimate {
+    pub remaining: Duration,
+    /// Time to full (or the charge limit) rather than to empty
+    pub to_full: bool,
+    /// The rate has settled, so the estimate is good to the minute
+    pub confident: bool,
+}
+
+impl TimeEstimate {
+    /// `2 h 35 min` once the rate has settled, `~3 h` before that
+    pub fn label(&self) -> String {
+        let minutes = (self.remaining.as_secs() + 30) / 60;
+        if !self.confident {
+            return match (minutes + 30) / 60 {
+                0 => "<1 h".to_string(),
+                hours => format!("~{} h", hours),
+            };
+        }
+        match (minutes / 60, minutes % 60) {
+            (0, minutes) => format!("{} min", minutes),
+            (hours, 0) => format!("{} h", hours),
+            (hours, minutes) => format!("{} h {} min", hours, minutes),
+        }
+    }
+}
+
+/// Rough trade-off of a charge limit, shown while editing it: wear saved
+/// against capacity given up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitPreview {
+    /// Full-charge cycles' worth of wear avoided per year
+    pub cycles_saved: u32,
+    /// Energy left unused per charge, in Wh
+    pub energy_lost_wh: Option<f32>,
+    /// Runtime given up at the current draw
+    pub runtime_lost: Option<Duration>,
+}
+
+impl LimitPreview {
+    /// Estimate for `limit`, with capacity from `battery` and the draw from
+    /// `rate` when the battery is discharging
+    pub fn new(limit: u8, battery: Option<&BatteryInfo>, rate: &BatteryRate) -> Self {
+        let unused = (100 - limit.min(100)) as f32;
+        let wear = 0.5f32.powf(unused / WEAR_HALVING_PERCENT);
+        let energy_lost_wh = battery
+            .and_then(|battery| battery.energy_full_wh)
+            .map(|full| full * unused / 100.0);
+        let runtime_lost = energy_lost_wh
+            .zip(rate.discharge_watts())
+            .map(|(wh, watts)| Duration::from_secs_f32(wh / watts * 3600.0));
+        Self {
+            cycles_saved: (CHARGES_PER_YEAR * (1.0 - wear)).round() as u32,
+            energy_lost_wh,
+            runtime_lost,
+        }
+    }
+
+    /// `≈ +219 cycles/yr saved, ≈ 46 min less runtime`, with the energy
+    /// per charge instead while there's no draw to go by
+    pub fn label(&self) -> String {
+        let saved = format!("≈ +{} cycles/yr saved", self.cycles_saved);
+        match (self.runtime_lost, self.energy_lost_wh) {
+            (Some(runtime), _) => {
+                let runtime = TimeEstimate {
+                    remaining: runtime,
+                    to_full: false,
+                    confident: true,
+                };
+                format!("{}, ≈ {} less runtime", saved, runtime.label())
+            }
+            (None, Some(wh)) => format!("{}, ≈ {:.0} Wh less per charge", saved, wh),
+            (None, None) => saved,
+        }
+    }
+}
+
+/// Throttle indicators read in one sample
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThrottleReading {
+    /// Total of the Intel per-core and package throttle event counters
+    pub events: Option<u64>,
+    /// A CPU cooling device is engaged
+    pub cooling: bool,
+}
+
+/// One point of the temperature trend line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrendSample {
+    pub celsius: Option<f32>,
+    pub throttled: bool,
+}
+
+/// CPU temperature history with thermal throttling marked on it. A run of
+/// consecutive throttled samples counts as a single episode.
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleTracker {
+    pub trend: VecDeque<TrendSample>,
+    /// Episodes seen since hachi started
+    pub episodes: u32,
+    last_events: Option<u64>,
+    throttling: bool,
+}
+
+impl ThrottleTracker {
+    /// Fold in a sample
+    pub fn record(&mut self, celsius: Option<f32>, reading: ThrottleReading) {
+        // The counters only ever grow; the first reading is just a baseline
+        let counted = matches!(
+            (self.last_events, reading.events),
+            (Some(prev), Some(now)) if now > prev
+        );
+        self.last_events = reading.events;
+
+        let throttled = counted || reading.cooling;
+        if throttled && !self.throttling {
+            self.episodes += 1;
+        }
+        self.throttling = throttled;
+
+        if self.trend.len() == TREND_SAMPLES {
+            self.trend.pop_front();
+        }
+        self.trend.push_back(TrendSample { celsius, throttled });
+    }
+
+    /// Whether the latest sample was throttled
+    pub fn throttling(&self) -> bool {
+        self.throttling
+    }
+}
+
+/// Latest telemetry snapshot
+pub struct Telemetry {
+    pub cores: Vec<CoreSample>,
+    pub memory: MemoryUsage,
+    pub sensors: Vec<TempSensor>,
+    pub fans: Vec<FanSensor>,
+    pub battery: Option<BatteryInfo>,
+    pub battery_rate: BatteryRate,
+    pub throttle: ThrottleTracker,
+    /// Time since boot
+    pub uptime: Option<Duration>,
+    /// How often `poll` resamples
+    pub interval: Duration,
+    prev_times: Vec<CpuTimes>,
+    last_sample: Option<Instant>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        Self {
+            cores: Vec::new(),
+            memory: MemoryUsage::default(),
+            sensors: Vec::new(),
+            fans: Vec::new(),
+            battery: None,
+            battery_rate: BatteryRate::default(),
+            throttle: ThrottleTracker::default(),
+            uptime: None,
+            interval: SAMPLE_INTERVAL,
+            prev_times: Vec::new(),
+            last_sample: None,
+        }
+    }
+
+    /// Resample if the sample interval has elapsed, returning whether it did
+    pub fn poll(&mut self) -> bool {
+        let due = match self.last_sample {
+            Some(time) => time.elapsed() >= self.interval,
+            None => true,
+        };
+        if due {
+            self.sample();
+        }
+        due
+    }
+
+    /// Read all sources and update the snapshot
+    pub fn sample(&mut self) {
+        self.last_sample = Some(Instant::now());
+
+        let times = fs::read_to_string("/proc/stat")
+            .map(|contents| parse_proc_stat(&contents))
+            .unwrap_or_default();
+
+        self.cores = times
+            .iter()
+            .enumerate()
+            .map(|(i, now)| {
+                let usage = self
+                    .prev_times
+                    .get(i)
+                    .map(|prev| usage_between(prev, now))
+                    .unwrap_or(0.0);
+                CoreSample {
+                    usage,
+                    freq_mhz: read_cpufreq_mhz(i, "scaling_cur_freq"),
+                    max_freq_mhz: read_cpufreq_mhz(i, "cpuinfo_max_freq"),
+                }
+            })
+            .collect();
+
+        self.prev_times = times;
+
+        self.memory = fs::read_to_string("/proc/meminfo")
+            .map(|contents| parse_meminfo(&contents))
+            .unwrap_or_default();
+
+        (self.sensors, self.fans) = read_hwmon();
+        self.throttle.record(self.cpu_temp(), read_throttle());
+        self.battery = read_battery();
+        self.battery_rate.record(self.battery.as_ref());
+        self.uptime = fs::read_to_string("/proc/uptime")
+            .ok()
+            .and_then(|contents| parse_uptime(&contents));
+    }
+
+    /// Mean utilization across all cores (0.0 - 1.0)
+    pub fn average_usage(&self) -> f32 {
+        if self.cores.is_empty() {
+            return 0.0;
+        }
+        self.cores.iter().map(|c| c.usage).sum::<f32>() / self.cores.len() as f32
+    }
+
+    /// Sensors not hidden by the user's config
+    pub fn visible_sensors<'a>(
+        &'a self,
+        hidden: &'a [String],
+    ) -> impl Iterator<Item = &'a TempSensor> + 'a {
+        self.sensors.iter().filter(move |s| !s.is_hidden(hidden))
+    }
+
+    /// CPU package temperature in °C, from the first known CPU sensor
+    pub fn cpu_temp(&self) -> Option<f32> {
+        self.find_temp(&[
+            ("k10temp", "Tctl"),
+            ("coretemp", "Package id 0"),
+            ("zenpower", "Tdie"),
+            ("asusec", "CPU"),
+        ])
+    }
+
+    /// GPU temperature in °C, from the first known GPU sensor
+    pub fn gpu_temp(&self) -> Option<f32> {
+        self.find_temp(&[("amdgpu", "edge"), ("nouveau", "temp1"), ("asusec", "GPU")])
+    }
+
+    /// Skin temperature in °C as reported by the EC, falling back to the
+    /// ACPI thermal zone firmware uses for it on most models
+    pub fn skin_temp(&self) -> Option<f32> {
+        self.find_temp(&[("asusec", "Skin"), ("acpitz", "temp1")])
+    }
+
+    /// Temperature in °C from the sensor the fan curve is keyed on
+    pub fn fan_temp(&self, source: FanTempSource) -> Option<f32> {
+        match source {
+            FanTempSource::Cpu => self.cpu_temp(),
+            FanTempSource::Gpu => self.gpu_temp(),
+            FanTempSource::Skin => self.skin_temp(),
+        }
+    }
+
+    /// A fan reporting 0 RPM while the hottest CPU/GPU sensor exceeds `threshold` °C
+    pub fn stalled_fan(&self, threshold: f32) -> Option<(&FanSensor, f32)> {
+        let hottest = [self.cpu_temp(), self.gpu_temp()]
+            .into_iter()
+            .flatten()
+            .reduce(f32::max)?;
+        if hottest <= threshold {
+            return None;
+        }
+        self.fans.iter().find(|fan| fan.rpm == 0).map(|fan| (fan, hottest))
+    }
+
+    fn find_temp(&self, candidates: &[(&str, &str)]) -> Option<f32> {
+        candidates.iter().find_map(|(chip, label)| {
+            self.sensors
+                .iter()
+                .find(|s| s.chip == *chip && s.label == *label)
+                .map(|s| s.celsius)
+        })
+    }
+
+    /// Mean current frequency across cores that report one
+    pub fn average_freq_mhz(&self) -> Option<u32> {
+        let freqs: Vec<u32> = self.cores.iter().filter_map(|c| c.freq_mhz).collect();
+        if freqs.is_empty() {
+            None
+        } else {
+            Some(freqs.iter().sum::<u32>() / freqs.len() as u32)
+        }
+    }
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse the per-core `cpuN` lines of `/proc/stat`, skipping the aggregate line
+pub fn parse_proc_stat(contents: &str) -> Vec<CpuTimes> {
+    contents
+        .lines()
+        .filter(|line| {
+            line.strip_prefix("cpu")
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        })
+        .map(|line| {
+            let fields: Vec<u64> = line
+                .split_whitespace()
+                .skip(1)
+                .filter_map(|f| f.parse().ok())
+                .collect();
+            // user nice system idle iowait irq softirq steal (guest is already in user)
+            let total: u64 = fields.iter().take(8).sum();
+            let idle = fields.get(3).copied().unwrap_or(0) + fields.get(4).copied().unwrap_or(0);
+            CpuTimes {
+                busy: total.saturating_sub(idle),
+                total,
+            }
+        })
+        .collect()
+}
+
+/// Utilization between two counter snapshots (0.0 - 1.0)
+pub fn usage_between(prev: &CpuTimes, now: &CpuTimes) -> f32 {
+    let total = now.total.saturating_sub(prev.total);
+    if total == 0 {
+        return 0.0;
+    }
+    let busy = now.busy.saturating_sub(prev.busy);
+    (busy as f32 / total as f32).clamp(0.0, 1.0)
+}
+
+/// Seconds since boot from `/proc/uptime` (`12345.67 23456.78`, the second
+/// field being idle time)
+pub fn parse_uptime(contents: &str) -> Option<Duration> {
+    let seconds: f64 = contents.split_whitespace().next()?.parse().ok()?;
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Parse the RAM and swap fields of `/proc/meminfo`
+pub fn parse_meminfo(contents: &str) -> MemoryUsage {
+    let mut mem = MemoryUsage::default();
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Ok(value) = value.parse::<u64>() else {
+            continue;
+        };
+        match key {
+            "MemTotal:" => mem.total_kb = value,
+            "MemAvailable:" => mem.available_kb = value,
+            "SwapTotal:" => mem.swap_total_kb = value,
+            "SwapFree:" => mem.swap_free_kb = value,
+            _ => {}
+        }
+    }
+    mem
+}
+
+/// Collect every temperature and fan input exposed under `/sys/class/hwmon`
+pub fn read_hwmon() -> (Vec<TempSensor>, Vec<FanSensor>) {
+    let Ok(entries) = fs::read_dir("/sys/class/hwmon") else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let mut hwmons: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    hwmons.sort();
+
+    let mut sensors = Vec::new();
+    let mut fans = Vec::new();
+    for dir in hwmons {
+        let Some(chip) = read_trimmed(&dir.join("name")) else {
+            continue;
+        };
+
+        let Ok(files) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut inputs: Vec<String> = files
+            .flatten()
+            .filter_map(|f| f.file_name().into_string().ok())
+            .filter(|name| name.ends_with("_input"))
+            .collect();
+        inputs.sort();
+
+        for input in inputs {
+            let prefix = input.trim_end_matches("_input");
+            let Some(value) = read_trimmed(&dir.join(&input)).and_then(|v| v.parse::<i64>().ok())
+            else {
+                continue;
+            };
+            let label = read_trimmed(&dir.join(format!("{}_label", prefix)))
+                .unwrap_or_else(|| prefix.to_string());
+
+            if prefix.starts_with("temp") {
+                sensors.push(TempSensor {
+                    chip: chip.clone(),
+                    label,
+                    celsius: value as f32 / 1000.0,
+                });
+            } else if prefix.starts_with("fan") {
+                fans.push(FanSensor {
+                    chip: chip.clone(),
+                    label,
+                    rpm: value.max(0) as u32,
+                });
+            }
+        }
+    }
+    (sensors, fans)
+}
+
+/// Read the first battery under `/sys/class/power_supply`
+fn read_battery() -> Option<BatteryInfo> {
+    let dir = sysfs::battery_dir()?;
+
+    let read_num = |attr: &str| read_trimmed(&dir.join(attr)).and_then(|v| v.parse::<f64>().ok());
+
+    // Prefer power_now (µW), otherwise derive it from current (µA) and voltage (µV)
+    let power_w = read_num("power_now")
+        .map(|uw| uw / 1e6)
+        .or_else(|| Some(read_num("current_now")? * read_num("voltage_now")? / 1e12))
+        .map(|w| w.abs() as f32);
+
+    // Stored energy is in µWh, or in µAh on batteries that only report charge
+    let energy_wh = |energy: &str, charge: &str| {
+        read_num(energy)
+            .map(|uwh| uwh / 1e6)
+            .or_else(|| Some(read_num(charge)? * read_num("voltage_min_design")? / 1e12))
+            .map(|wh| wh as f32)
+    };
+
+    Some(BatteryInfo {
+        capacity: read_num("capacity").unwrap_or(0.0).clamp(0.0, 100.0) as u8,
+        status: read_trimmed(&dir.join("status")).unwrap_or_default(),
+        power_w,
+        energy_wh: energy_wh("energy_now", "charge_now"),
+        energy_full_wh: energy_wh("energy_full", "charge_full"),
+    })
+}
+
+/// Read the CPU throttle indicators. Intel exposes event counters per core;
+/// elsewhere an engaged processor cooling device is the only sign.
+fn read_throttle() -> ThrottleReading {
+    let counters = fs::read_dir("/sys/devices/system/cpu")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .flat_map(|entry| {
+            let dir = entry.path().join("thermal_throttle");
+            ["core_throttle_count", "package_throttle_count"].map(|name| dir.join(name))
+        })
+        .filter_map(|path| read_trimmed(&path)?.parse::<u64>().ok())
+        .collect::<Vec<_>>();
+
+    let cooling = fs::read_dir("/sys/class/thermal")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| {
+            read_trimmed(&dir.join("type"))
+                .is_some_and(|kind| CPU_COOLING_TYPES.contains(&kind.as_str()))
+        })
+        .any(|dir| read_trimmed(&dir.join("cur_state")).is_some_and(|s| cooling_engaged(&s)));
+
+    ThrottleReading {
+        events: (!counters.is_empty()).then(|| counters.iter().sum()),
+        cooling,
+    }
+}
+
+/// Whether a cooling device's `cur_state` holds the CPU back; an idle
+/// intel_powerclamp reports -1
+pub fn cooling_engaged(cur_state: &str) -> bool {
+    cur_state.parse::<i64>().is_ok_and(|state| state > 0)
+}
+
+fn ratio(part: u64, whole: u64) -> f32 {
+    if whole == 0 {
+        0.0
+    } else {
+        (part as f32 / whole as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// Read a cpufreq attribute (reported in kHz) as MHz
+fn read_cpufreq_mhz(core: usize, attr: &str) -> Option<u32> {
+    let path = format!("/sys/devices/system/cpu/cpu{}/cpufreq/{}", core, attr);
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| s.trim().parse::<u32>().ok())
+        .map(|khz| khz / 1000)
+}
@@ -1,5 +1,9 @@
+use proptest::prelude::*;
+
 use crate::daemon::{FanCurve, FanPoint, PowerProfile};
 
+mod fake_asusd;
+
 #[test]
 fn test_power_profile_cycle() {
     let mut profile = PowerProfile::Quiet;
@@ -46,3 +50,2561 @@ fn test_fan_point_validity() {
     assert_eq!(point.temp, 30);
     assert_eq!(point.speed, 0);
 }
+
+#[test]
+fn test_proc_stat_parsing() {
+    use crate::telemetry::{parse_proc_stat, usage_between};
+
+    let stat = "cpu  400 0 100 1500 0 0 0 0 0 0\n\
+                cpu0 200 0 50 700 50 0 0 0 0 0\n\
+                cpu1 200 0 50 800 0 0 0 0 0 0\n\
+                intr 12345\n";
+    let cores = parse_proc_stat(stat);
+    assert_eq!(cores.len(), 2); // Aggregate line skipped
+    assert_eq!(cores[0].total, 1000);
+    assert_eq!(cores[0].busy, 250); // idle + iowait excluded
+
+    let later = parse_proc_stat("cpu0 300 0 150 800 50 0 0 0 0 0\n");
+    assert!((usage_between(&cores[0], &later[0]) - 2.0 / 3.0).abs() < 1e-4);
+    assert_eq!(usage_between(&cores[0], &cores[0]), 0.0);
+}
+
+#[test]
+fn test_meminfo_parsing() {
+    use crate::telemetry::parse_meminfo;
+
+    let meminfo = "MemTotal:       16000000 kB\n\
+                   MemFree:         2000000 kB\n\
+                   MemAvailable:    4000000 kB\n\
+                   SwapTotal:       8000000 kB\n\
+                   SwapFree:        6000000 kB\n";
+    let mem = parse_meminfo(meminfo);
+    assert_eq!(mem.used_kb(), 12_000_000);
+    assert_eq!(mem.swap_used_kb(), 2_000_000);
+    assert!((mem.used_ratio() - 0.75).abs() < 1e-4);
+    assert!((mem.swap_used_ratio() - 0.25).abs() < 1e-4);
+
+    // No swap configured
+    assert_eq!(parse_meminfo("MemTotal: 100 kB\n").swap_used_ratio(), 0.0);
+}
+
+#[test]
+fn test_temp_sensor_hiding() {
+    use crate::telemetry::TempSensor;
+
+    let sensor = TempSensor {
+        chip: "nvme".to_string(),
+        label: "Composite".to_string(),
+        celsius: 41.0,
+    };
+    assert_eq!(sensor.id(), "nvme/Composite");
+    assert_eq!(sensor.display_name(), "NVMe");
+
+    assert!(!sensor.is_hidden(&[]));
+    assert!(sensor.is_hidden(&["nvme/Composite".to_string()]));
+    assert!(sensor.is_hidden(&["nvme".to_string()])); // Whole chip
+    assert!(!sensor.is_hidden(&["nvme/Sensor 1".to_string()]));
+}
+
+#[test]
+fn test_battery_time_estimate() {
+    use crate::telemetry::{BatteryInfo, BatteryRate, TimeEstimate};
+    use std::time::Duration;
+
+    let battery = |status: &str, watts| BatteryInfo {
+        capacity: 50,
+        status: status.to_string(),
+        power_w: Some(watts),
+        energy_wh: Some(40.0),
+        energy_full_wh: Some(80.0),
+    };
+    let mut rate = BatteryRate::default();
+
+    // One reading gives a rough estimate, a steady rate a precise one
+    let draining = battery("Discharging", 20.0);
+    rate.record(Some(&draining));
+    let estimate = rate.estimate(&draining, 80).unwrap();
+    assert_eq!(estimate.remaining, Duration::from_secs(2 * 3600));
+    assert!(!estimate.to_full && !estimate.confident);
+    assert_eq!(estimate.label(), "~2 h");
+    for _ in 0..20 {
+        rate.record(Some(&draining));
+    }
+    assert!(rate.estimate(&draining, 80).unwrap().confident);
+
+    // A jumpy load keeps it in hours
+    let mut jumpy = BatteryRate::default();
+    for i in 0..40 {
+        jumpy.record(Some(&battery("Discharging", if i % 2 == 0 { 10.0 } else { 30.0 })));
+    }
+    assert!(!jumpy.estimate(&draining, 80).unwrap().confident);
+
+    // Charging starts over and counts up to the charge limit: 24 Wh at 12 W
+    let charging = battery("Charging", 12.0);
+    rate.record(Some(&charging));
+    let estimate = rate.estimate(&charging, 80).unwrap();
+    assert_eq!(estimate.remaining, Duration::from_secs(2 * 3600));
+    assert!(estimate.to_full && !estimate.confident);
+    // Already past the limit
+    assert_eq!(rate.estimate(&charging, 50), None);
+
+    rate.record(Some(&battery("Full", 0.0)));
+    assert_eq!(rate.estimate(&draining, 80), None);
+
+    let label = |minutes: u64, confident| {
+        TimeEstimate {
+            remaining: Duration::from_secs(minutes * 60),
+            to_full: false,
+            confident,
+        }
+        .label()
+    };
+    assert_eq!(label(95, true), "1 h 35 min");
+    assert_eq!(label(40, true), "40 min");
+    assert_eq!(label(20, false), "<1 h");
+    assert_eq!(label(95, false), "~2 h");
+}
+
+#[test]
+fn test_charge_limit_preview() {
+    use crate::telemetry::{BatteryInfo, BatteryRate, LimitPreview};
+    use std::time::Duration;
+
+    let battery = |status: &str| BatteryInfo {
+        capacity: 50,
+        status: status.to_string(),
+        power_w: Some(16.0),
+        energy_wh: Some(40.0),
+        energy_full_wh: Some(80.0),
+    };
+    let mut rate = BatteryRate::default();
+    let draining = battery("Discharging");
+    rate.record(Some(&draining));
+
+    // 80% leaves 16 Wh unused: an hour at 16 W
+    let preview = LimitPreview::new(80, Some(&draining), &rate);
+    assert_eq!(preview.cycles_saved, 220);
+    assert_eq!(preview.runtime_lost, Some(Duration::from_secs(3600)));
+    assert_eq!(preview.label(), "≈ +220 cycles/yr saved, ≈ 1 h less runtime");
+
+    // Lower limits save more wear
+    assert!(LimitPreview::new(60, Some(&draining), &rate).cycles_saved > preview.cycles_saved);
+    assert_eq!(LimitPreview::new(100, Some(&draining), &rate).cycles_saved, 0);
+
+    // Without a draw to go by, the energy per charge
+    let charging = battery("Charging");
+    rate.record(Some(&charging));
+    let preview = LimitPreview::new(80, Some(&charging), &rate);
+    assert_eq!(preview.label(), "≈ +220 cycles/yr saved, ≈ 16 Wh less per charge");
+    assert_eq!(LimitPreview::new(80, None, &rate).label(), "≈ +220 cycles/yr saved");
+}
+
+#[test]
+fn test_color_picker() {
+    use crate::daemon::Rgb;
+    use crate::ui::picker::{Hsv, PickerRow, PickerState, SWATCHES};
+
+    let orange = Rgb::parse("#FF8000").unwrap();
+    assert_eq!(orange, Rgb::new(255, 128, 0));
+    assert_eq!(orange.to_string(), "#ff8000");
+    assert_eq!(Rgb::parse("ff8000"), Some(orange));
+    assert_eq!(Rgb::parse("#ff80"), None);
+    assert_eq!(serde_json::to_string(&orange).unwrap(), "\"#ff8000\"");
+
+    let hsv = Hsv::from_rgb(orange);
+    assert_eq!((hsv.hue, hsv.saturation, hsv.value), (30, 100, 100));
+    for color in SWATCHES {
+        assert_eq!(Hsv::from_rgb(color).to_rgb(), color);
+    }
+
+    // Hue wraps, saturation stops at the ends
+    let mut picker = PickerState::new(Rgb::new(255, 0, 0));
+    picker.adjust(-10);
+    assert_eq!(picker.hsv.hue, 350);
+    picker.select_row(true);
+    picker.adjust(25);
+    assert_eq!(picker.hsv.saturation, 100);
+    picker.adjust(-50);
+    assert_eq!(picker.color, Hsv { hue: 350, saturation: 50, value: 100 }.to_rgb());
+
+    // Hex digits only count on the hex row, and take effect once complete
+    assert!(!picker.type_hex('a'));
+    picker.select_row(true);
+    picker.select_row(true);
+    assert_eq!(picker.row, PickerRow::Hex);
+    for c in "00ff8".chars() {
+        assert!(picker.type_hex(c));
+    }
+    assert_eq!(picker.hex_input, "00ff8");
+    assert!(picker.type_hex('0'));
+    assert_eq!(picker.color, Rgb::new(0, 255, 128));
+    assert!(picker.hex_input.is_empty());
+
+    // Swatches step one at a time and stop at the last
+    picker.select_row(true);
+    picker.adjust(5);
+    picker.adjust(25);
+    assert_eq!(picker.color, SWATCHES[2]);
+    picker.select_row(true);
+    assert_eq!(picker.row, PickerRow::Swatches);
+}
+
+#[test]
+fn test_temperature_unit_conversion() {
+    use crate::config::TemperatureUnit;
+
+    assert_eq!(TemperatureUnit::Celsius.format(41.0), "41°C");
+    assert_eq!(TemperatureUnit::Fahrenheit.format(100.0), "212°F");
+    assert_eq!(TemperatureUnit::Fahrenheit.convert(30.0), 86.0);
+    assert_eq!(TemperatureUnit::Celsius.toggle(), TemperatureUnit::Fahrenheit);
+}
+
+#[test]
+fn test_session_temp_stats() {
+    use crate::stats::{format_duration, TempStats};
+    use std::time::Duration;
+
+    let mut stats = TempStats::default();
+    assert_eq!(stats.average(), None);
+    for temp in [50.0, 70.0, 60.0] {
+        stats.record(temp);
+    }
+    assert_eq!(stats.min, 50.0);
+    assert_eq!(stats.max, 70.0);
+    assert_eq!(stats.average(), Some(60.0));
+
+    assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+    assert_eq!(format_duration(Duration::from_secs(195)), "3m 15s");
+    assert_eq!(format_duration(Duration::from_secs(3720)), "1h 02m");
+}
+
+#[test]
+fn test_telemetry_record_csv() {
+    use crate::recorder::TelemetryRecord;
+
+    let record = TelemetryRecord {
+        timestamp: 1700000000.5,
+        profile: Some("Balanced"),
+        cpu_load: 0.25,
+        cpu_freq_mhz: Some(3200),
+        cpu_temp: Some(55.0),
+        gpu_temp: None,
+        mem_used: 0.5,
+        battery_percent: Some(80),
+        battery_power_w: None,
+    };
+    assert_eq!(
+        record.to_csv(),
+        "1700000000.500,Balanced,0.250,3200,55,,0.500,80,"
+    );
+}
+
+#[test]
+fn test_fan_stall_detection() {
+    use crate::telemetry::{FanSensor, Telemetry, TempSensor};
+
+    let mut telemetry = Telemetry::new();
+    telemetry.sensors = vec![TempSensor {
+        chip: "k10temp".to_string(),
+        label: "Tctl".to_string(),
+        celsius: 85.0,
+    }];
+    telemetry.fans = vec![FanSensor {
+        chip: "asus".to_string(),
+        label: "cpu_fan".to_string(),
+        rpm: 0,
+    }];
+
+    let (fan, temp) = telemetry.stalled_fan(75.0).expect("stall expected");
+    assert_eq!(fan.label, "cpu_fan");
+    assert_eq!(temp, 85.0);
+
+    // Below threshold, 0 RPM is normal (fan stop)
+    assert!(telemetry.stalled_fan(90.0).is_none());
+
+    telemetry.fans[0].rpm = 2400;
+    assert!(telemetry.stalled_fan(75.0).is_none());
+}
+
+#[test]
+fn test_sysfs_choice_parsing() {
+    use crate::sysfs::parse_choices;
+
+    let (choices, active) = parse_choices("[auto] inhibit-charge force-discharge\n");
+    assert_eq!(choices, vec!["auto", "inhibit-charge", "force-discharge"]);
+    assert_eq!(active.as_deref(), Some("auto"));
+
+    let (_, active) = parse_choices("auto [inhibit-charge]");
+    assert_eq!(active.as_deref(), Some("inhibit-charge"));
+}
+
+#[test]
+fn test_kbd_idle_timeout_format() {
+    use crate::ui::format_timeout;
+
+    assert_eq!(format_timeout(0), "never");
+    assert_eq!(format_timeout(45), "45s");
+    assert_eq!(format_timeout(120), "2m");
+    assert_eq!(format_timeout(150), "2m 30s");
+}
+
+#[test]
+fn test_backlight_percent() {
+    use crate::sysfs::Backlight;
+
+    let backlight = Backlight {
+        device: "amdgpu_bl1".to_string(),
+        brightness: 128,
+        max_brightness: 255,
+    };
+    assert_eq!(backlight.percent(), 50);
+    assert_eq!(backlight.raw_for_percent(100), 255);
+    assert_eq!(backlight.raw_for_percent(150), 255);
+    assert_eq!(backlight.raw_for_percent(20), 51);
+
+    // A 5% step on a 0-10 panel still moves a whole unit either way
+    let coarse = Backlight {
+        device: "intel_backlight".to_string(),
+        brightness: 5,
+        max_brightness: 10,
+    };
+    assert_eq!(coarse.stepped(5), 6);
+    assert_eq!(coarse.stepped(-5), 4);
+    assert_eq!(Backlight { brightness: 1, ..coarse.clone() }.stepped(-5), 1);
+    assert_eq!(Backlight { brightness: 10, ..coarse }.stepped(5), 10);
+}
+
+#[test]
+fn test_armoury_attribute_validation() {
+    use crate::armoury::Attribute;
+
+    let ppt = Attribute {
+        name: "ppt_pl1_spl".to_string(),
+        current: "25".to_string(),
+        min: Some(5),
+        max: Some(30),
+        possible: Vec::new(),
+    };
+    assert!(ppt.accepts("15"));
+    assert!(!ppt.accepts("45"));
+    assert!(!ppt.accepts("fast"));
+    assert_eq!(ppt.clamp(2), 5);
+    assert_eq!(ppt.clamp(45), 30);
+
+    let mux = Attribute {
+        name: "gpu_mux_mode".to_string(),
+        current: "1".to_string(),
+        min: None,
+        max: None,
+        possible: vec!["0".to_string(), "1".to_string()],
+    };
+    assert!(mux.accepts("0"));
+    assert!(!mux.accepts("2"));
+}
+
+#[test]
+fn test_elevation_allowlist() {
+    use crate::helper::{is_allowed, is_valid_value};
+    use std::path::Path;
+
+    assert!(is_allowed(Path::new("/sys/firmware/acpi/platform_profile")));
+    assert!(is_allowed(Path::new("/sys/class/power_supply/BAT0/charge_control_end_threshold")));
+    assert!(is_allowed(Path::new(
+        "/sys/class/firmware-attributes/asus-armoury/attributes/ppt_pl1_spl/current_value"
+    )));
+
+    assert!(!is_allowed(Path::new("/etc/shadow")));
+    assert!(!is_allowed(Path::new("/sys/class/power_supply/BAT0/../../../../etc/shadow")));
+    assert!(!is_allowed(Path::new("/sys/class/power_supply/BAT0/extra/charge_behaviour")));
+    assert!(!is_allowed(Path::new("sys/firmware/acpi/platform_profile")));
+
+    assert!(is_valid_value("inhibit-charge"));
+    assert!(!is_valid_value("80\nmore"));
+    assert!(!is_valid_value(""));
+}
+
+#[test]
+fn test_hardware_state_apply() {
+    use crate::daemon::{DaemonSignal, HardwareState, HardwareUpdate};
+
+    let mut state = HardwareState::default();
+    state.apply(&HardwareUpdate::PowerProfileChanged(PowerProfile::Quiet));
+    state.apply(&HardwareUpdate::External(DaemonSignal::ChargeLimit(80)));
+    state.apply(&HardwareUpdate::TdpChanged(15));
+    assert_eq!(state.power_profile, PowerProfile::Quiet);
+    assert_eq!(state.charge_limit, 80);
+    assert_eq!(state.tdp, Some(15));
+
+    // A full refresh replaces everything
+    state.apply(&HardwareUpdate::StateRefresh(HardwareState::default()));
+    assert_eq!(state.charge_limit, 0);
+    assert_eq!(state.tdp, None);
+}
+
+#[test]
+fn test_hardware_state_serde() {
+    use crate::daemon::HardwareState;
+
+    let state = HardwareState {
+        power_profile: PowerProfile::Performance,
+        charge_limit: 80,
+        fan_curve: FanCurve::default_curve(),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&state).unwrap();
+    assert!(json.contains("\"power_profile\":\"performance\""));
+
+    let parsed: HardwareState = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.charge_limit, 80);
+    assert_eq!(parsed.fan_curve.cpu_curve, state.fan_curve.cpu_curve);
+
+    // Missing fields fall back to defaults, so partial presets load
+    let partial: HardwareState = serde_json::from_str(r#"{"power_profile":"quiet"}"#).unwrap();
+    assert_eq!(partial.power_profile, PowerProfile::Quiet);
+    assert_eq!(partial.tdp, None);
+}
+
+#[test]
+fn test_superseded_intents_dropped() {
+    use crate::daemon::{supersede, HardwareIntent};
+    use crate::sysfs::Peripheral;
+
+    let batch = vec![
+        (1, HardwareIntent::SetPowerProfile(PowerProfile::Quiet)),
+        (2, HardwareIntent::SetPeripheral(Peripheral::FnLock, true)),
+        (3, HardwareIntent::SetPeripheral(Peripheral::Touchpad, false)),
+        (4, HardwareIntent::SetPowerProfile(PowerProfile::Performance)),
+        (5, HardwareIntent::RefreshState),
+        (6, HardwareIntent::RefreshState),
+    ];
+    let generations: Vec<_> = supersede(batch).into_iter().map(|(g, _)| g).collect();
+    // Different peripherals are different slots; the older profile write goes
+    assert_eq!(generations, vec![2, 3, 4, 5]);
+
+    // A queued hold and release of the sleep inhibitor both run, in order,
+    // leaving it released
+    let batch = vec![
+        (1, HardwareIntent::InhibitSleep(Some("Tune run in progress"))),
+        (2, HardwareIntent::InhibitSleep(None)),
+    ];
+    let generations: Vec<_> = supersede(batch).into_iter().map(|(g, _)| g).collect();
+    assert_eq!(generations, vec![1, 2]);
+}
+
+#[test]
+fn test_layout_config_parsing() {
+    use crate::app::PanelId;
+    use crate::config::Config;
+
+    let config: Config = toml::from_str(
+        r#"
+        [layout]
+        left = ["power", "fan-curve"]
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.layout.left, Some(vec![PanelId::Power, PanelId::FanCurve]));
+    assert_eq!(config.layout.right, None);
+
+    assert!(toml::from_str::<Config>("[layout]\nleft = [\"clock\"]").is_err());
+}
+
+#[test]
+fn test_ui_session_roundtrip() {
+    use crate::app::{FocusedPanel, Tab};
+    use crate::session::UiSession;
+
+    let session = UiSession {
+        tab: Tab::Panels,
+        focused: FocusedPanel::FanCurve,
+        sakura_enabled: false,
+        ..UiSession::default()
+    };
+    let toml = toml::to_string(&session).unwrap();
+    assert_eq!(toml::from_str::<UiSession>(&toml).unwrap(), session);
+
+    // Missing fields fall back to a fresh session
+    let partial: UiSession = toml::from_str("focused = \"lid\"").unwrap();
+    assert_eq!(partial.focused, FocusedPanel::Lid);
+    assert_eq!(partial.tab, Tab::Dashboard);
+    assert!(partial.sakura_enabled);
+}
+
+#[test]
+fn test_cli_args_parsing() {
+    use crate::cli::Args;
+    use std::path::PathBuf;
+
+    let parse = |args: &[&str]| Args::parse(args.iter().map(|a| a.to_string()));
+
+    let args = parse(&["--config", "/tmp/a.toml", "--profile-dir=/tmp/profiles"]).unwrap();
+    assert_eq!(args.config, Some(PathBuf::from("/tmp/a.toml")));
+    assert_eq!(args.profile_dir, Some(PathBuf::from("/tmp/profiles")));
+    assert_eq!(parse(&[]).unwrap(), Args::default());
+    assert!(parse(&["--read-only"]).unwrap().read_only);
+    assert!(parse(&["--plain"]).unwrap().plain);
+    assert!(parse(&["--debug-console"]).unwrap().debug_console);
+    assert!(parse(&["diagnose", "--read-only"]).unwrap().diagnose);
+
+    assert!(parse(&["--config"]).is_err());
+    assert!(parse(&["--config="]).is_err());
+    assert!(parse(&["--bogus"]).is_err());
+}
+
+#[test]
+fn test_diagnose_bundle() {
+    use crate::daemon::{AsusdVersion, AuraState, HardwareState};
+    use crate::diagnose::{Bundle, Scrubber};
+
+    let scrubber = Scrubber {
+        home: Some("/home/mei".to_string()),
+        user: Some("mei".to_string()),
+        host: Some("zephyrus".to_string()),
+    };
+    assert_eq!(
+        scrubber.scrub("can't write /home/mei/.config/hachi/config.toml"),
+        "can't write ~/.config/hachi/config.toml"
+    );
+    assert_eq!(scrubber.scrub("mei@zephyrus: access denied"), "<user>@<host>: access denied");
+    // Only whole words, so short names don't mangle the text around them
+    let short = Scrubber {
+        user: Some("me".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(short.scrub("timed out for me"), "timed out for <user>");
+
+    let state = HardwareState {
+        connected: true,
+        aura: Some(AuraState::default()),
+        asusd_version: Some(AsusdVersion::new(6, 1, 12)),
+        ..Default::default()
+    };
+    let json = Bundle::new(&state, &[], Vec::new()).to_json().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["asusd"], "6.1.12");
+    assert_eq!(json["capabilities"]["keyboard_lighting"], true);
+    assert_eq!(json["capabilities"]["slash"], false);
+    assert_eq!(json["state"]["connected"], true);
+}
+
+#[test]
+fn test_disconnect_reason_classification() {
+    use crate::daemon::DisconnectReason as Reason;
+    use zbus::fdo::Error as Fdo;
+
+    let classify = |e: Fdo| Reason::from_error(&zbus::Error::from(e));
+
+    assert_eq!(classify(Fdo::ServiceUnknown(String::new())), Some(Reason::AsusdMissing));
+    assert_eq!(classify(Fdo::UnknownInterface(String::new())), Some(Reason::InterfaceMissing));
+    assert_eq!(classify(Fdo::AccessDenied(String::new())), Some(Reason::PermissionDenied));
+    assert_eq!(classify(Fdo::InvalidArgs(String::new())), None);
+    assert_eq!(
+        Reason::from_error(&zbus::Error::Address("unix:path=/nope".to_string())),
+        Some(Reason::NoSystemBus)
+    );
+}
+
+#[test]
+fn test_transient_errors() {
+    use crate::error::HachiError;
+    use zbus::fdo::Error as Fdo;
+
+    let dbus = |e: Fdo| HachiError::DbusConnection(zbus::Error::from(e));
+
+    // asusd restarting or slow to answer: worth retrying
+    assert!(dbus(Fdo::ServiceUnknown(String::new())).is_transient());
+    assert!(dbus(Fdo::NoReply(String::new())).is_transient());
+    // Rejected values and policy denials won't change on their own
+    assert!(!dbus(Fdo::InvalidArgs(String::new())).is_transient());
+    assert!(!dbus(Fdo::AccessDenied(String::new())).is_transient());
+    assert!(!HachiError::BatteryLimitOutOfRange(5).is_transient());
+}
+
+#[test]
+fn test_polkit_denial_detection() {
+    use crate::error::{AuthDenial, HachiError};
+    use zbus::fdo::Error as Fdo;
+
+    let dbus = |e: Fdo| HachiError::DbusConnection(zbus::Error::from(e));
+
+    assert_eq!(
+        dbus(Fdo::InteractiveAuthorizationRequired(String::new())).auth_denial(),
+        Some(AuthDenial::AuthenticationRequired)
+    );
+    assert_eq!(
+        dbus(Fdo::AccessDenied(String::new())).auth_denial(),
+        Some(AuthDenial::NotAuthorized)
+    );
+    assert_eq!(dbus(Fdo::InvalidArgs(String::new())).auth_denial(), None);
+    assert_eq!(HachiError::InvalidFanCurve(String::new()).auth_denial(), None);
+}
+
+#[test]
+fn test_safe_render_downgrade() {
+    use crate::ui::compat::{ascii_fallback, to_indexed};
+    use ratatui::style::Color;
+
+    assert_eq!(to_indexed(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+    assert_eq!(to_indexed(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+    assert_eq!(to_indexed(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+    assert_eq!(to_indexed(Color::Reset), Color::Reset);
+
+    assert_eq!(ascii_fallback('━'), '-');
+    assert_eq!(ascii_fallback('┏'), '+');
+    assert_eq!(ascii_fallback('█'), '#');
+    assert_eq!(ascii_fallback('⁶'), '6');
+}
+
+#[test]
+fn test_status_bar_tooltip() {
+    use crate::app::Severity;
+    use crate::ui::StatusBar;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let line = |bar: StatusBar| {
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        (0..area.width).map(|x| buf[(x, 0)].symbol()).collect::<String>()
+    };
+
+    let tooltip = Some("Click to apply Quiet profile");
+    let info = line(StatusBar::new(true).message("Connected", Severity::Info).tooltip(tooltip));
+    assert!(info.contains("Click to apply Quiet profile"));
+
+    let error = line(StatusBar::new(true).message("Error: boom", Severity::Error).tooltip(tooltip));
+    assert!(error.contains("Error: boom"));
+    assert!(!error.contains("Click to apply"));
+}
+
+#[test]
+fn test_fan_graph_point_at_column() {
+    use crate::ui::FanCurveGraph;
+    use ratatui::layout::Rect;
+
+    let curve = FanCurve::default_curve();
+    let area = Rect::new(0, 0, 77, 20);
+    // Graph starts at column 7 (border + axis labels) and spans 68 columns
+    assert_eq!(FanCurveGraph::point_at(&curve, area, 0), Some(0));
+    assert_eq!(FanCurveGraph::point_at(&curve, area, 76), Some(7));
+    assert_eq!(FanCurveGraph::point_at(&curve, Rect::new(0, 0, 20, 5), 3), None);
+}
+
+#[test]
+fn test_clipboard_export() {
+    use crate::clipboard::{osc52, summary};
+    use crate::config::TemperatureUnit;
+    use crate::daemon::HardwareState;
+    use crate::telemetry::Telemetry;
+
+    assert_eq!(osc52("hachi"), "\x1b]52;c;aGFjaGk=\x07");
+    assert_eq!(osc52("ab"), "\x1b]52;c;YWI=\x07");
+    assert_eq!(osc52(""), "\x1b]52;c;\x07");
+
+    let state = HardwareState {
+        charge_limit: 80,
+        fan_curve: FanCurve::default_curve(),
+        ..Default::default()
+    };
+    let text = summary(Some("ROG Zephyrus G14"), &state, &Telemetry::new(), TemperatureUnit::Celsius);
+    assert!(text.starts_with("hachi · ROG Zephyrus G14\n"));
+    assert!(text.contains("Charge limit 80%"));
+    assert!(text.contains("30°C 0%"));
+}
+
+#[test]
+fn test_fan_curve_svg_export() {
+    use crate::config::TemperatureUnit;
+    use crate::export::fan_curve_svg;
+
+    let curve = FanCurve::default_curve();
+    let svg = fan_curve_svg(&curve, PowerProfile::Balanced, TemperatureUnit::Celsius);
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains("Balanced · fan curve (default)"));
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert_eq!(svg.matches("<circle").count(), curve.cpu_curve.len() + curve.gpu_curve.len());
+    // 30°C / 0% lands on the bottom-left corner of the plot area
+    assert!(svg.contains(r#"<circle cx="60.0" cy="340.0""#));
+}
+
+#[test]
+fn test_preset_roundtrip() {
+    use crate::daemon::{AuraState, HardwareState, KbdBrightness, Rgb};
+    use crate::preset::{valid_name, Preset};
+
+    let mut state = HardwareState {
+        power_profile: PowerProfile::Quiet,
+        charge_limit: 60,
+        fan_curve: FanCurve::default_curve(),
+        ..Default::default()
+    };
+    let preset = Preset::from_state("travel", &state);
+    let parsed = Preset::parse("travel", &preset.to_toml().unwrap()).unwrap();
+    assert_eq!(parsed, preset);
+    assert_eq!(parsed.kbd_idle_timeout, None);
+    assert_eq!(parsed.kbd_color, None);
+
+    // Keyboard lighting is part of the bundle
+    state.aura = Some(AuraState {
+        brightness: Some(KbdBrightness::Low),
+        idle_timeout: None,
+        color: Some(Rgb::new(255, 0, 128)),
+    });
+    let preset = Preset::from_state("desk", &state);
+    let parsed = Preset::parse("desk", &preset.to_toml().unwrap()).unwrap();
+    assert_eq!(parsed.kbd_brightness, Some(KbdBrightness::Low));
+    assert_eq!(parsed.kbd_color, Some(Rgb::new(255, 0, 128)));
+
+    assert!(Preset::parse("broken", "charge_limit = 60").is_err());
+
+    assert!(valid_name("desk"));
+    assert!(!valid_name("  "));
+    assert!(!valid_name("../escape"));
+    assert!(!valid_name(".hidden"));
+}
+
+#[test]
+fn test_process_rules() {
+    use crate::automation::{active_rule, matches};
+    use crate::config::Config;
+
+    let config: Config = toml::from_str(
+        r#"
+        [[automation.processes]]
+        match = ["blender"]
+
+        [[automation.processes]]
+        match = ["steam_app_*"]
+        profile = "balanced"
+        preset = "gaming"
+        "#,
+    )
+    .unwrap();
+    let rules = &config.automation.processes;
+    assert_eq!(rules[0].profile, PowerProfile::Performance);
+    assert_eq!(rules[1].preset.as_deref(), Some("gaming"));
+
+    assert!(matches("steam_app_*", "steam_app_1091500"));
+    assert!(!matches("blender", "blender-thumbnailer"));
+
+    let running = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    assert_eq!(active_rule(rules, &running(&["bash", "steam_app_42"])), Some(1));
+    assert_eq!(active_rule(rules, &running(&["steam_app_42", "blender"])), Some(0));
+    assert_eq!(active_rule(rules, &running(&["bash"])), None);
+}
+
+#[test]
+fn test_idle_quiet_config() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::automation::idle_duration;
+    use crate::config::Config;
+
+    let config: Config = toml::from_str("[automation]\nidle_quiet_minutes = 10\n").unwrap();
+    assert_eq!(config.automation.idle_quiet_minutes, Some(10));
+    assert!(config.automation.processes.is_empty());
+
+    let now = UNIX_EPOCH + Duration::from_secs(1_000);
+    assert_eq!(idle_duration(400_000_000, now), Duration::from_secs(600));
+    // A hint from the future (clock skew) is not idle at all
+    assert_eq!(idle_duration(2_000_000_000, now), Duration::ZERO);
+}
+
+#[test]
+fn test_event_rules() {
+    use crate::automation::{active_event, describe, Environment};
+    use crate::config::Config;
+    use crate::daemon::{GpuMode, KbdBrightness};
+
+    let config: Config = toml::from_str(
+        r#"
+        [[automation.events]]
+        docked = true
+        external_power = true
+        profile = "performance"
+        gpu = "discrete"
+
+        [[automation.events]]
+        lid_closed = true
+        profile = "quiet"
+        keyboard = "off"
+        "#,
+    )
+    .unwrap();
+    let rules = &config.automation.events;
+    assert_eq!(rules[0].gpu, Some(GpuMode::Discrete));
+    assert_eq!(rules[0].keyboard, None);
+    assert_eq!(rules[1].keyboard, Some(KbdBrightness::Off));
+    assert_eq!(describe(&rules[0]), "docked, on AC");
+
+    let mut env = Environment::default();
+    assert_eq!(active_event(rules, &env), None);
+    env.lid_closed = true;
+    assert_eq!(active_event(rules, &env), Some(1));
+    env.docked = true;
+    assert_eq!(active_event(rules, &env), Some(0));
+    env.external_power = false;
+    assert_eq!(active_event(rules, &env), Some(1));
+}
+
+#[test]
+fn test_message_history() {
+    use crate::app::{History, MessagesView, Severity};
+
+    let mut history = History::default();
+    for i in 0..105 {
+        history.push(Severity::Info, &format!("Status {}", i));
+    }
+    history.push(Severity::Error, "Error: Permission denied");
+
+    let all = history.search("");
+    assert_eq!(all.len(), 100);
+    assert_eq!(all[0].text, "Error: Permission denied");
+    assert_eq!(all[99].text, "Status 6");
+
+    let errors = history.search("permission");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].line().ends_with(" error Error: Permission denied"));
+
+    // Debug lines stay hidden until asked for; `e` leaves only failures
+    history.push(Severity::Debug, "Refreshing state...");
+    let mut view = MessagesView::default();
+    assert_eq!(view.found(&history)[0].text, "Error: Permission denied");
+    view.least = Severity::Debug;
+    assert_eq!(view.found(&history)[0].text, "Refreshing state...");
+    view.least = Severity::Error;
+    assert_eq!(view.found(&history).len(), 1);
+}
+
+#[test]
+fn test_profile_power_limits() {
+    use crate::daemon::{HardwareState, HardwareUpdate, PowerLimits};
+    use crate::session::UiSession;
+
+    let mut state = HardwareState::default();
+    assert_eq!(state.power_limits(), None);
+    let limits = PowerLimits {
+        sustained: 65,
+        boost: Some(80),
+    };
+    state.apply(&HardwareUpdate::PowerLimitsChanged(PowerProfile::Performance, limits));
+    assert_eq!(state.power_limits(), Some(limits));
+
+    let mut session = UiSession::default();
+    session.profile_limits.set(PowerProfile::Performance, limits);
+    let restored: UiSession = toml::from_str(&toml::to_string(&session).unwrap()).unwrap();
+    assert_eq!(restored.profile_limits.get(PowerProfile::Performance), Some(limits));
+    assert_eq!(restored.profile_limits.get(PowerProfile::Quiet), None);
+}
+
+#[test]
+fn test_profile_details() {
+    use crate::daemon::PowerLimits;
+    use crate::session::UiSession;
+    use crate::ui::profile_details;
+
+    let choices: Vec<String> = ["low-power", "balanced", "performance"].map(String::from).into();
+    let firmware = PowerProfile::Quiet.firmware_name(&choices);
+    assert_eq!(firmware, Some("low-power"));
+    let limits = PowerLimits {
+        sustained: 35,
+        boost: None,
+    };
+    assert_eq!(
+        profile_details(PowerProfile::Quiet, firmware, Some(limits), Some(2900)).as_deref(),
+        Some("low-power · 35W sustained · fans ≤2900 rpm")
+    );
+    // A firmware name matching the profile's adds nothing
+    let balanced = PowerProfile::Balanced.firmware_name(&choices);
+    assert_eq!(profile_details(PowerProfile::Balanced, balanced, None, None), None);
+
+    // Sessions from before fan speeds were learned still load
+    let old: UiSession = toml::from_str("[profile_limits.quiet]\nsustained = 35\n").unwrap();
+    assert_eq!(old.profile_limits.get(PowerProfile::Quiet), Some(limits));
+    assert_eq!(old.profile_fans.get(PowerProfile::Quiet), None);
+}
+
+#[test]
+fn test_status_bar_hints() {
+    use crate::ui::StatusBar;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let line = |width: u16| {
+        let hints = [("enter", "apply"), ("n", "new"), ("?", "help"), ("q", "quit")];
+        let area = Rect::new(0, 0, width, 1);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(true).hints(&hints).render(area, &mut buf);
+        (0..area.width).map(|x| buf[(x, 0)].symbol()).collect::<String>()
+    };
+
+    let wide = line(120);
+    assert!(wide.contains("enter: apply  n: new  ?: help  q: quit"));
+
+    // Only what fits in half the bar, least important dropped first
+    let narrow = line(64);
+    assert!(narrow.contains("enter: apply  n: new  ?: help"));
+    assert!(!narrow.contains("q: quit"));
+}
+
+#[test]
+fn test_focus_ring() {
+    use crate::app::{FocusRing, FocusedPanel, PanelId};
+
+    // Layout order, skipping display-only panels
+    let columns = vec![
+        vec![PanelId::Power, PanelId::Telemetry],
+        vec![PanelId::FanCurve, PanelId::Presets],
+    ];
+    let ring = FocusRing::new(&columns, &[]);
+    assert_eq!(
+        ring.panels(),
+        [FocusedPanel::PowerProfile, FocusedPanel::FanCurve, FocusedPanel::Presets]
+    );
+
+    let step = |current, forward, wrap| ring.clone().wrap(wrap).step(current, forward);
+    assert_eq!(step(FocusedPanel::PowerProfile, true, true), Some(FocusedPanel::FanCurve));
+    assert_eq!(step(FocusedPanel::Presets, true, true), Some(FocusedPanel::PowerProfile));
+    assert_eq!(step(FocusedPanel::PowerProfile, false, true), Some(FocusedPanel::Presets));
+    assert_eq!(step(FocusedPanel::Presets, true, false), Some(FocusedPanel::Presets));
+    assert_eq!(step(FocusedPanel::PowerProfile, false, false), Some(FocusedPanel::PowerProfile));
+    // A hidden panel hands focus to the first one
+    assert_eq!(step(FocusedPanel::Tdp, true, true), Some(FocusedPanel::PowerProfile));
+
+    // A configured order goes first; panels it names that aren't shown are skipped
+    let ring = FocusRing::new(&columns, &[PanelId::Presets, PanelId::Lid, PanelId::Power]);
+    assert_eq!(
+        ring.panels(),
+        [FocusedPanel::Presets, FocusedPanel::PowerProfile, FocusedPanel::FanCurve]
+    );
+    assert!(!ring.contains(FocusedPanel::Lid));
+    assert_eq!(ring.step(FocusedPanel::FanCurve, true), Some(FocusedPanel::Presets));
+    assert_eq!(FocusRing::new(&[], &[]).step(FocusedPanel::Battery, true), None);
+}
+
+#[test]
+fn test_fan_hysteresis() {
+    use crate::config::Config;
+
+    let mut curve = FanCurve::default_curve();
+    assert!(curve.validate().is_ok());
+
+    // Heating follows the breakpoints
+    assert_eq!(curve.target_speed(69.0, None, 3), 20);
+    assert_eq!(curve.target_speed(70.0, Some(20), 3), 35);
+    // Cooling holds the speed until 3°C below the 70°C breakpoint
+    assert_eq!(curve.target_speed(68.0, Some(35), 3), 35);
+    assert_eq!(curve.target_speed(66.5, Some(35), 3), 20);
+    assert_eq!(curve.target_speed(69.5, Some(35), 0), 20);
+
+    let config: Config = toml::from_str("[ui]\nfan_hysteresis = 4").unwrap();
+    assert_eq!(config.ui.fan_hysteresis, 4);
+    assert_eq!(Config::default().ui.fan_hysteresis, 0);
+    assert!(toml::from_str::<Config>("[ui]\nfan_hysteresis = 11").is_err());
+
+    curve.cpu_curve[3].temp = 45;
+    curve.cpu_curve[2].temp = 50;
+    assert!(curve.validate().is_err());
+}
+
+#[tokio::test]
+async fn test_fan_steps() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, EditMode, FocusedPanel, Tab};
+    use crate::config::Config;
+    use crate::daemon::{DaemonHandle, FanSteps};
+
+    let steps = FanSteps { speed_step: 8, temp_step: 5 };
+    assert_eq!(steps.snap_speed(3), 0);
+    assert_eq!(steps.snap_speed(4), 8);
+    // 100% isn't a step: the top one is
+    assert_eq!(steps.snap_speed(100), 96);
+    assert_eq!(steps.snap_temp(42), 40);
+    assert_eq!(steps.snap_temp(43), 45);
+    assert!(FanSteps::default().takes_any());
+
+    let snapped = FanCurve::default_curve().snapped(steps);
+    let speeds: Vec<u8> = snapped.cpu_curve.iter().map(|point| point.speed).collect();
+    assert_eq!(speeds, [0, 8, 8, 24, 32, 56, 64, 96]);
+    assert!(snapped.validate().is_ok());
+
+    // 41 and 42°C both snap to 40; the second moves up a step
+    let mut close = FanCurve::default_curve();
+    close.cpu_curve[0].temp = 41;
+    close.cpu_curve[1].temp = 42;
+    let snapped = close.snapped(steps);
+    let temps: Vec<u8> = snapped.cpu_curve.iter().map(|point| point.temp).collect();
+    assert_eq!(temps[..3], [40, 45, 50]);
+    assert!(snapped.validate().is_ok());
+
+    let config: Config = toml::from_str("[fan]\nspeed_step = 5").unwrap();
+    assert_eq!(config.fan, FanSteps { speed_step: 5, temp_step: 1 });
+
+    // Edits move a whole step at a time and land on one
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, config);
+    app.health = None;
+    app.tab = Tab::Panels;
+    app.focused = FocusedPanel::FanCurve;
+    app.state.fan_curve = FanCurve::default_curve();
+    app.state.fan_curve.cpu_curve[0].speed = 3;
+    for code in [KeyCode::Enter, KeyCode::Char('k')] {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+    assert_eq!(app.edit_mode, EditMode::FanCurve { point_index: 0 });
+    assert_eq!(app.state.fan_curve.cpu_curve[0].speed, 10);
+}
+
+#[tokio::test]
+async fn test_count_prefix() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, CountPrefix, EditMode, FocusedPanel, Tab};
+    use crate::config::Config;
+    use crate::daemon::DaemonHandle;
+
+    let mut count = CountPrefix::default();
+    assert!(count.push(KeyCode::Char('2')));
+    assert!(count.push(KeyCode::Char('0')));
+    assert!(!count.push(KeyCode::Char('l')));
+    assert!(!count.push(KeyCode::Left));
+    assert_eq!(count.take(), Some(20));
+    assert_eq!(count.take(), None);
+
+    // Long digit runs stop at the cap instead of overflowing
+    for _ in 0..12 {
+        assert!(count.push(KeyCode::Char('9')));
+    }
+    assert_eq!(count.take(), Some(9999));
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    app.health = None;
+    app.tab = Tab::Panels;
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+    app.focused = FocusedPanel::Battery;
+    app.state.charge_limit = 60;
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.edit_mode, EditMode::Battery);
+    for code in [KeyCode::Char('2'), KeyCode::Char('0'), KeyCode::Char('l')] {
+        press(&mut app, code);
+    }
+    assert_eq!(app.state.charge_limit, 80);
+    // The count is used up: the next step is the default one
+    press(&mut app, KeyCode::Char('h'));
+    assert_eq!(app.state.charge_limit, 75);
+    press(&mut app, KeyCode::Esc);
+
+    // On a list the count moves that many rows, stopping at the end
+    app.focused = FocusedPanel::PowerProfile;
+    app.selected_profile = 0;
+    for code in [KeyCode::Char('3'), KeyCode::Char('j')] {
+        press(&mut app, code);
+    }
+    assert_eq!(app.focused, FocusedPanel::PowerProfile);
+    assert_eq!(app.selected_profile, 2);
+    for code in [KeyCode::Char('2'), KeyCode::Char('k')] {
+        press(&mut app, code);
+    }
+    assert_eq!(app.selected_profile, 0);
+
+    // Esc drops the count; a lone digit left alone is a panel number
+    for code in [KeyCode::Char('3'), KeyCode::Esc] {
+        press(&mut app, code);
+    }
+    app.tick();
+    assert_eq!(app.focused, FocusedPanel::PowerProfile);
+    press(&mut app, KeyCode::Char('3'));
+    assert_eq!(app.focused, FocusedPanel::PowerProfile);
+    tokio::time::sleep(std::time::Duration::from_millis(650)).await;
+    app.tick();
+    assert_eq!(app.focused, FocusedPanel::FanCurve);
+
+    // ...and so is one followed by any other key
+    app.focused = FocusedPanel::Presets;
+    for code in [KeyCode::Char('1'), KeyCode::Char('J')] {
+        press(&mut app, code);
+    }
+    assert_eq!(app.focused, FocusedPanel::PowerProfile);
+}
+
+#[tokio::test]
+async fn test_curve_copy() {
+    use std::time::Duration;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, CurveTarget, EditMode, FocusedPanel, Tab};
+    use crate::config::Config;
+    use crate::daemon::{CurveData, DaemonHandle, Fan};
+    use fake_asusd::{serve, written_curves, Platform, TestBus};
+
+    let curve = FanCurve::default_curve();
+    let copied = curve.cpu_to_gpu(-10);
+    assert_eq!(copied.cpu_curve, curve.cpu_curve);
+    let speeds: Vec<u8> = copied.gpu_curve.iter().map(|point| point.speed).collect();
+    assert_eq!(speeds, [0, 0, 0, 10, 25, 45, 55, 90]);
+    assert_eq!(curve.shifted(10).cpu_curve[7].speed, 100);
+
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let asusd = serve(&bus, Platform::default()).await.unwrap();
+    let daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+    let mut app = App::new(daemon, Config::default());
+    app.health = None;
+    app.tab = Tab::Panels;
+    app.focused = FocusedPanel::FanCurve;
+    async fn settle(app: &mut App, done: impl Fn(&App) -> bool) {
+        let waiting = async {
+            while !done(app) {
+                app.process_updates();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("update from the actor");
+    }
+    // Shown at some point: asusd's startup signals may replace it right away
+    let status = |text: &'static str| {
+        move |app: &App| app.history.messages().any(|message| message.text == text)
+    };
+    settle(&mut app, |app| app.state.fan_curve_control).await;
+    let press = |app: &mut App, keys: &str| {
+        for c in keys.chars() {
+            let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+    };
+
+    // CPU → GPU, 10 points slower
+    press(&mut app, "Cjj");
+    let gpu = CurveTarget::Gpu;
+    assert_eq!(app.edit_mode, EditMode::CurveCopy { target: gpu, offset: -10 });
+    assert_eq!(gpu.describe(PowerProfile::Balanced, -10), "CPU → GPU, speeds -10%");
+    press(&mut app, "\n");
+    assert_eq!(app.state.fan_curve.gpu_curve, copied.gpu_curve);
+    settle(&mut app, status("Fan curve updated")).await;
+
+    // Balanced → Quiet goes straight into asusd's Quiet curves
+    press(&mut app, "Clj\n");
+    settle(&mut app, status("Quiet fan curve written, used once active")).await;
+    let quiet = copied.shifted(-5);
+    let written = written_curves(&asusd, PowerProfile::Quiet.to_u32()).await;
+    assert!(written.contains(&CurveData::new(Fan::Cpu, &quiet.cpu_curve, false)));
+    assert_eq!(app.state.fan_curve.cpu_curve, copied.cpu_curve);
+
+    // ...and is what the graph shows once Quiet is active
+    app.focused = FocusedPanel::PowerProfile;
+    press(&mut app, "k\n");
+    settle(&mut app, |app| app.state.fan_curve.cpu_curve == quiet.cpu_curve).await;
+    assert_eq!(app.state.fan_curve.gpu_curve, quiet.gpu_curve);
+}
+
+#[test]
+fn test_full_blast() {
+    use std::time::Duration;
+
+    use crate::ui::StatusBar;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let curve = FanCurve::default_curve();
+    let blast = curve.full_blast(PowerProfile::Balanced);
+    assert!(blast.enabled);
+    assert!(blast.enablement.any(PowerProfile::Balanced));
+    assert!(!blast.enablement.any(PowerProfile::Quiet));
+    assert!(blast.validate().is_ok());
+    assert!(blast.cpu_curve.iter().chain(&blast.gpu_curve).all(|point| point.speed == 100));
+    let temps = |points: &[FanPoint]| points.iter().map(|point| point.temp).collect::<Vec<_>>();
+    assert_eq!(temps(&blast.cpu_curve), temps(&curve.cpu_curve));
+
+    let area = Rect::new(0, 0, 120, 1);
+    let mut buf = Buffer::empty(area);
+    StatusBar::new(true)
+        .full_blast(Some(Duration::from_secs(272)))
+        .render(area, &mut buf);
+    let line = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect::<String>();
+    assert!(line.contains("FULL BLAST 4:32"));
+}
+
+#[test]
+fn test_fan_temp_source() {
+    use crate::config::{Config, FanTempSource};
+    use crate::telemetry::{Telemetry, TempSensor};
+
+    let sensor = |chip: &str, label: &str, celsius: f32| TempSensor {
+        chip: chip.to_string(),
+        label: label.to_string(),
+        celsius,
+    };
+    let mut telemetry = Telemetry::new();
+    telemetry.sensors = vec![
+        sensor("k10temp", "Tctl", 82.0),
+        sensor("amdgpu", "edge", 64.0),
+        sensor("acpitz", "temp1", 45.0),
+    ];
+    assert_eq!(telemetry.fan_temp(FanTempSource::Cpu), Some(82.0));
+    assert_eq!(telemetry.fan_temp(FanTempSource::Gpu), Some(64.0));
+    assert_eq!(telemetry.fan_temp(FanTempSource::Skin), Some(45.0));
+
+    // The EC's own skin sensor wins over the thermal zone
+    telemetry.sensors.push(sensor("asusec", "Skin", 41.0));
+    assert_eq!(telemetry.fan_temp(FanTempSource::Skin), Some(41.0));
+
+    assert_eq!(FanTempSource::Skin.cycle_next(), FanTempSource::Cpu);
+    let config: Config = toml::from_str("[ui]\nfan_temp_source = \"skin\"").unwrap();
+    assert_eq!(config.ui.fan_temp_source, FanTempSource::Skin);
+    assert_eq!(Config::default().ui.fan_temp_source, FanTempSource::Cpu);
+}
+
+#[test]
+fn test_quiet_hours() {
+    use crate::automation::{active_quiet_hours, in_window};
+    use crate::config::{ClockTime, Config};
+
+    let at = |s: &str| ClockTime::parse(s).unwrap();
+    assert_eq!(at("7:05").to_string(), "07:05");
+    assert!(ClockTime::parse("24:00").is_none());
+    assert!(ClockTime::parse("23:5").is_none());
+
+    // Wraps past midnight; the end is exclusive
+    assert!(in_window(at("23:00"), at("07:00"), at("23:30")));
+    assert!(in_window(at("23:00"), at("07:00"), at("03:00")));
+    assert!(!in_window(at("23:00"), at("07:00"), at("07:00")));
+    assert!(!in_window(at("23:00"), at("07:00"), at("12:00")));
+    assert!(in_window(at("12:00"), at("14:00"), at("13:59")));
+
+    let config: Config = toml::from_str(
+        "[[automation.quiet_hours]]\nstart = \"23:00\"\nend = \"07:00\"",
+    )
+    .unwrap();
+    let rules = &config.automation.quiet_hours;
+    assert_eq!(rules[0].fan_cap, 40);
+    assert_eq!(rules[0].profile, PowerProfile::Quiet);
+    assert_eq!(active_quiet_hours(rules, at("01:00")), Some(0));
+    assert_eq!(active_quiet_hours(rules, at("09:00")), None);
+    let invalid = "[[automation.quiet_hours]]\nstart = \"late\"\nend = \"07:00\"";
+    assert!(toml::from_str::<Config>(invalid).is_err());
+
+    let capped = FanCurve::default_curve().capped(rules[0].fan_cap, PowerProfile::Quiet);
+    assert!(capped.enabled);
+    assert!(capped.validate().is_ok());
+    assert!(capped.cpu_curve.iter().chain(&capped.gpu_curve).all(|point| point.speed <= 40));
+}
+
+#[test]
+fn test_tune_trace() {
+    use std::time::Duration;
+
+    use crate::tune::{Trace, TuneRun, TuneSample, Tuning};
+
+    let sample = |secs: f32, celsius: f32| TuneSample {
+        secs,
+        celsius,
+        fan_rpm: Some(celsius as u32 * 50),
+    };
+    let trace = Trace {
+        samples: vec![
+            sample(0.0, 50.0),
+            sample(10.0, 88.0),
+            sample(20.0, 91.0),
+            sample(30.0, 70.0),
+            sample(45.0, 54.0),
+        ],
+        load_secs: Some(20.0),
+    };
+    assert_eq!(trace.peak(), Some(91.0));
+    assert_eq!(trace.peak_rpm(), Some(4550));
+    // Back within 5°C of the starting 50°C at 45 s, 25 s after the load
+    assert_eq!(trace.recovery_secs(), Some(25.0));
+
+    // Ending the load early kills the command and starts the cooldown
+    let mut tuning = Tuning::default();
+    let mut run = TuneRun::start("sleep 30").unwrap();
+    run.record(50.0, None);
+    assert!(run.loading());
+    assert!(!run.poll(Duration::from_secs(60)));
+    run.end_load();
+    assert!(run.poll(Duration::ZERO));
+    tuning.run = Some(run);
+    assert!(tuning.traces().0.is_none());
+
+    tuning.finish();
+    tuning.run = Some(TuneRun::start("true").unwrap());
+    tuning.finish();
+    let (before, after) = tuning.traces();
+    assert_eq!(before.unwrap().samples.len(), 1);
+    assert!(after.unwrap().samples.is_empty());
+}
+
+#[test]
+fn test_panel_help() {
+    use crate::app::{PanelId, REGISTRY};
+    use crate::ui::PanelHelp;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    // Every panel that takes keys documents them
+    for spec in REGISTRY.iter().filter(|spec| spec.focus.is_some()) {
+        assert!(!spec.help.is_empty(), "{} has no help", spec.title);
+    }
+
+    let fan = REGISTRY.iter().find(|spec| spec.id == PanelId::FanCurve).unwrap();
+    let area = Rect::new(0, 0, 60, fan.help.len() as u16 + 4);
+    let mut buf = Buffer::empty(area);
+    PanelHelp::new(fan.title, fan.help).render(area, &mut buf);
+    let row = |y: u16| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>();
+    assert!(row(0).contains("help · fan curve"));
+    assert!(row(2).contains("Enter") && row(2).contains("Edit the curve points"));
+}
+
+#[test]
+fn test_which_key_overlay() {
+    use crate::ui::WhichKey;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let keys: Vec<(String, String)> = [("a", "3 keys"), ("b", "12 keys"), ("esc", "cancel")]
+        .iter()
+        .map(|(key, action)| (key.to_string(), action.to_string()))
+        .collect();
+    let which_key = WhichKey::new("@  replay macro", &keys);
+    // Entries are 3 + 7 + 6 wide: all three fit on one row at 60 columns,
+    // one per row at 20
+    assert_eq!(which_key.height(60), 3);
+    assert_eq!(which_key.height(20), 5);
+
+    let area = Rect::new(0, 0, 60, 3);
+    let mut buf = Buffer::empty(area);
+    WhichKey::new("@  replay macro", &keys).render(area, &mut buf);
+    let row = |y: u16| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>();
+    assert!(row(0).contains("@  replay macro"));
+    assert!(row(1).contains("a   → 3 keys"));
+    assert!(row(1).contains("esc → cancel"));
+}
+
+#[test]
+fn test_refresh_spinner() {
+    use std::time::Duration;
+
+    use crate::ui::StatusBar;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let render = |elapsed: Option<Duration>| {
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(true).refreshing(elapsed).render(area, &mut buf);
+        (0..area.width).map(|x| buf[(x, 0)].symbol()).collect::<String>()
+    };
+    assert!(!render(None).contains("refreshing"));
+    // The spinner advances a frame every 100 ms
+    assert!(render(Some(Duration::ZERO)).contains("⠋ refreshing"));
+    assert!(render(Some(Duration::from_millis(250))).contains("⠹ refreshing"));
+}
+
+#[test]
+fn test_health_checks() {
+    use std::time::Instant;
+
+    use crate::health::{local_checks, Check, CheckStatus, HealthView};
+
+    let statuses = |checks: Vec<Check>| checks.iter().map(|c| c.status).collect::<Vec<_>>();
+    assert_eq!(
+        statuses(local_checks(4, Some("truecolor"), false)),
+        [CheckStatus::Pass, CheckStatus::Pass]
+    );
+    // No sensors and no truecolor are warnings, and safe rendering
+    // doesn't need truecolor at all
+    assert_eq!(
+        statuses(local_checks(0, None, false)),
+        [CheckStatus::Warn, CheckStatus::Warn]
+    );
+    assert_eq!(statuses(local_checks(4, None, true))[1], CheckStatus::Pass);
+
+    let mut view = HealthView::running();
+    assert!(!view.all_pass());
+    view.checks = local_checks(4, Some("24bit"), false);
+    view.finished = Some(Instant::now());
+    assert!(view.all_pass());
+    view.checks.push(Check::fail("asusd", "not running"));
+    assert!(!view.all_pass());
+}
+
+#[test]
+fn test_asusd_version() {
+    use crate::daemon::AsusdVersion;
+
+    assert_eq!(AsusdVersion::parse("6.1.12"), Some(AsusdVersion::new(6, 1, 12)));
+    assert_eq!(AsusdVersion::parse("v6.1.12-1"), Some(AsusdVersion::new(6, 1, 12)));
+    assert_eq!(AsusdVersion::parse("6.1"), Some(AsusdVersion::new(6, 1, 0)));
+    assert_eq!(AsusdVersion::parse("six"), None);
+
+    assert_eq!(AsusdVersion::new(6, 1, 12).untested_panels(), None);
+    // Older releases only affect panels added since; newer ones all of them
+    assert_eq!(
+        AsusdVersion::new(6, 0, 9).untested_panels(),
+        Some(vec!["Lighting", "Lid lighting"])
+    );
+    assert_eq!(AsusdVersion::new(6, 2, 0).untested_panels().map(|p| p.len()), Some(4));
+    let warning = AsusdVersion::new(6, 2, 0).compatibility_warning().unwrap();
+    assert!(warning.starts_with("asusd 6.2.0 is newer than hachi supports (6.1.0 to 6.1.x)"));
+}
+
+#[test]
+fn test_rollback() {
+    use crate::daemon::{HardwareIntent, HardwareState, HardwareUpdate, Rollback};
+
+    let mut state = HardwareState {
+        charge_limit: 80,
+        ..Default::default()
+    };
+    let confirmed = Rollback::confirmed(&HardwareIntent::SetChargeLimit(60), &state);
+    assert_eq!(confirmed, Some(Rollback::ChargeLimit(80)));
+    // Only settings the UI updates optimistically have anything to undo
+    assert_eq!(Rollback::confirmed(&HardwareIntent::SetTdp(25), &state), None);
+
+    // The optimistic value is replaced by the confirmed one
+    state.charge_limit = 60;
+    state.apply(&HardwareUpdate::RolledBack(confirmed.unwrap()));
+    assert_eq!(state.charge_limit, 80);
+}
+
+#[test]
+fn test_startup_config() {
+    use crate::config::Config;
+
+    let config: Config =
+        toml::from_str("[startup]\nprofile = \"quiet\"\ncharge_limit = 80\n").unwrap();
+    assert_eq!(config.startup.profile, Some(PowerProfile::Quiet));
+    assert_eq!(config.startup.charge_limit, Some(80));
+    // Nothing is applied unless configured
+    let config: Config = toml::from_str("").unwrap();
+    assert_eq!(config.startup.profile, None);
+    assert_eq!(config.startup.charge_limit, None);
+}
+
+#[test]
+fn test_perf_overlay() {
+    use std::time::Duration;
+
+    use crate::ui::{PerfOverlay, PerfStats};
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    let stats = PerfStats {
+        frame: Duration::from_micros(2500),
+        frame_max: Duration::from_millis(12),
+        effects: 2,
+        particles: 45,
+        dbus_latency: None,
+        queued_updates: 3,
+        queued_intents: 1,
+    };
+    let area = Rect::new(0, 0, PerfOverlay::WIDTH, PerfOverlay::HEIGHT);
+    let mut buf = Buffer::empty(area);
+    PerfOverlay::new(&stats).render(area, &mut buf);
+    let row = |y: u16| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>();
+    assert!(row(1).contains("2.5 ms (max 12.0 ms)"));
+    // No asusd call yet
+    assert!(row(4).contains("asusd     -"));
+    assert!(row(5).contains("3 updates, 1 intents"));
+}
+
+#[tokio::test]
+async fn test_low_power_rendering() {
+    use crate::app::App;
+    use crate::config::Config;
+    use crate::daemon::DaemonHandle;
+    use crate::telemetry::{LOW_POWER_SAMPLE_INTERVAL, SAMPLE_INTERVAL};
+
+    // No asusd needed: the state is set by hand
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    app.tick();
+    assert!(!app.low_power);
+    let full = app.frame_duration();
+
+    app.state.power_profile = PowerProfile::Quiet;
+    app.tick();
+    assert!(app.low_power);
+    assert_eq!(app.frame_duration(), full * 2);
+    assert_eq!(app.telemetry.interval, LOW_POWER_SAMPLE_INTERVAL);
+
+    // Switched off in the config, Quiet renders as usual
+    app.config.ui.follow_power_profile = Some(false);
+    app.tick();
+    assert!(!app.low_power);
+    assert_eq!(app.frame_duration(), full);
+    assert_eq!(app.telemetry.interval, SAMPLE_INTERVAL);
+}
+
+#[tokio::test]
+async fn test_blade_follows_battery() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use crate::app::{App, BladeEffect, Tab};
+    use crate::config::Config;
+    use crate::daemon::DaemonHandle;
+    use crate::telemetry::BatteryInfo;
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    app.tab = Tab::Panels;
+    // Sample telemetry before faking the battery so it isn't overwritten
+    app.tick();
+    let mut draw = |app: &mut App| {
+        let frame = terminal.draw(|frame| app.render(frame)).unwrap();
+        frame.buffer.content().iter().map(|cell| cell.symbol()).collect::<String>()
+    };
+    draw(&mut app);
+    let battery = |status: &str, capacity| BatteryInfo {
+        capacity,
+        status: status.to_string(),
+        power_w: None,
+        energy_wh: None,
+        energy_full_wh: None,
+    };
+
+    app.telemetry.battery = Some(battery("Charging", 60));
+    app.tick();
+    assert_eq!(app.blade_effect, Some(BladeEffect::Charging));
+    assert!(draw(&mut app).contains('⚡'));
+
+    app.telemetry.battery = Some(battery("Discharging", 15));
+    app.tick();
+    assert_eq!(app.blade_effect, Some(BladeEffect::Low));
+    assert!(!draw(&mut app).contains('⚡'));
+
+    app.telemetry.battery = Some(battery("Discharging", 50));
+    app.tick();
+    assert_eq!(app.blade_effect, None);
+
+    // Off the panels tab there's no blade to animate
+    app.telemetry.battery = Some(battery("Charging", 60));
+    app.tab = Tab::Dashboard;
+    app.tick();
+    assert_eq!(app.blade_effect, None);
+}
+
+#[tokio::test]
+async fn test_lighting_follows_profile() {
+    use crate::app::App;
+    use crate::config::Config;
+    use crate::daemon::{AuraState, DaemonHandle, KbdBrightness, Rgb};
+
+    let config: Config = toml::from_str(
+        "[lighting.performance]\ncolor = \"#ff0000\"\nbrightness = \"high\"\n\
+         [lighting.quiet]\nbrightness = \"low\"\n",
+    )
+    .unwrap();
+    let performance = config.lighting.for_profile(PowerProfile::Performance).unwrap();
+    assert_eq!(performance.color, Some(Rgb::new(255, 0, 0)));
+    assert!(config.lighting.for_profile(PowerProfile::Balanced).is_none());
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, config);
+    let status = |app: &App| app.status_message.as_ref().map(|(text, ..)| text.clone());
+    app.state.connected = true;
+    app.state.aura = Some(AuraState {
+        brightness: Some(KbdBrightness::Med),
+        color: Some(Rgb::new(255, 255, 255)),
+        ..AuraState::default()
+    });
+
+    // Nothing bound to Balanced
+    app.tick();
+    assert_eq!(status(&app), None);
+
+    app.state.power_profile = PowerProfile::Performance;
+    app.tick();
+    assert_eq!(status(&app).unwrap(), "Performance lighting: #ff0000, brightness High");
+
+    // Applied once per change, and only what differs
+    app.status_message = None;
+    app.tick();
+    assert_eq!(status(&app), None);
+    app.state.power_profile = PowerProfile::Quiet;
+    app.state.aura.as_mut().unwrap().brightness = Some(KbdBrightness::Low);
+    app.tick();
+    assert!(!status(&app).is_some_and(|text| text.contains("lighting")));
+}
+
+#[tokio::test]
+async fn test_reactive_lighting() {
+    use std::time::Duration;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, FocusedPanel, Tab};
+    use crate::config::{Config, ReactiveLighting};
+    use crate::daemon::{AuraState, DaemonHandle, Rgb};
+
+    let reactive = ReactiveLighting::default();
+    assert_eq!(reactive.color(20.0), Rgb::new(0, 0, 255));
+    assert_eq!(reactive.color(67.5), Rgb::new(0, 255, 0));
+    assert_eq!(reactive.color(105.0), Rgb::new(255, 0, 0));
+    // Moves in hue steps, not with every tenth of a degree
+    assert_eq!(reactive.color(60.0), reactive.color(60.4));
+    // Every write lands in the keyboard's flash, so short intervals are raised
+    let eager = ReactiveLighting { interval_secs: 5, ..reactive };
+    assert_eq!(eager.interval(), Duration::from_secs(ReactiveLighting::MIN_INTERVAL_SECS));
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    app.health = None;
+    app.tab = Tab::Panels;
+    app.focused = FocusedPanel::Lighting;
+    app.state.aura = Some(AuraState::default());
+
+    app.handle_key(key(KeyCode::Char('t')));
+    assert!(app.reactive_lighting);
+    app.handle_key(key(KeyCode::Char('t')));
+    assert!(!app.reactive_lighting);
+
+    // Picking a color by hand ends it
+    app.handle_key(key(KeyCode::Char('t')));
+    app.handle_key(key(KeyCode::Char('p')));
+    app.handle_key(key(KeyCode::Enter));
+    assert!(!app.reactive_lighting);
+}
+
+#[tokio::test]
+async fn test_debug_console() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, Tab};
+    use crate::config::Config;
+    use crate::console::{RawAction, RawArg, RawCall};
+    use crate::daemon::{DaemonHandle, HardwareIntent};
+
+    let call = RawCall::parse("set platform ChargeControlEndThreshold y 80").unwrap();
+    assert_eq!(call.interface, "xyz.ljones.Platform");
+    let threshold = "ChargeControlEndThreshold".to_string();
+    assert_eq!(call.action, RawAction::Set(threshold, RawArg::Byte(80)));
+    assert!(call.writes());
+    let call = RawCall::parse("call xyz.ljones.Aura Rename s \"desk light\"").unwrap();
+    let args = vec![RawArg::Str("desk light".to_string())];
+    assert_eq!(call.action, RawAction::Call("Rename".to_string(), args));
+    assert!(!RawCall::parse("get aura Brightness").unwrap().writes());
+    // Raw writes have no slot, but read-only mode still drops them
+    assert!(HardwareIntent::RawCall(call).writes());
+    let read = RawCall::parse("get aura Brightness").unwrap();
+    assert!(!HardwareIntent::RawCall(read).writes());
+    assert!(RawCall::parse("set platform X y 300").is_err());
+    assert!(RawCall::parse("call platform Next ub 1").is_err());
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    app.health = None;
+    app.tab = Tab::Panels;
+    let press = |app: &mut App, code| app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+
+    // Only with --debug-console
+    press(&mut app, KeyCode::Char('`'));
+    assert!(app.console.is_none());
+    app.debug_console = true;
+    app.read_only = true;
+    press(&mut app, KeyCode::Char('`'));
+    for c in "set platform PlatformProfile u 1".chars() {
+        press(&mut app, KeyCode::Char(c));
+    }
+    press(&mut app, KeyCode::Enter);
+    let console = app.console.as_ref().unwrap();
+    assert!(console.output.last().unwrap().starts_with("error: read-only"));
+
+    // Up brings the command back; q is typed rather than quitting
+    press(&mut app, KeyCode::Up);
+    press(&mut app, KeyCode::Char('q'));
+    assert!(!app.should_quit);
+    assert_eq!(app.console.as_ref().unwrap().input, "set platform PlatformProfile u 1q");
+    press(&mut app, KeyCode::Esc);
+    assert!(app.console.is_none());
+}
+
+#[tokio::test]
+async fn test_plain_mode() {
+    use ratatui::{backend::TestBackend, style::Color, Terminal};
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, FocusedPanel, Tab};
+    use crate::config::Config;
+    use crate::daemon::{AuraState, DaemonHandle, KbdBrightness};
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    app.set_plain();
+    app.health = None;
+    app.state.connected = true;
+    app.state.power_profile = PowerProfile::Performance;
+    app.state.aura = Some(AuraState {
+        brightness: Some(KbdBrightness::High),
+        ..AuraState::default()
+    });
+    app.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE));
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let frame = terminal.draw(|frame| app.render(frame)).unwrap();
+    let rows: Vec<String> = (0..30)
+        .map(|y| (0..100).map(|x| frame.buffer[(x, y)].symbol()).collect::<String>())
+        .collect();
+    let text = rows.join("\n");
+
+    assert!(rows[0].starts_with("hachi: connected to asusd"), "{}", rows[0]);
+    assert!(text.contains("> Power profile: Performance active"), "{}", text);
+    assert!(text.contains("  Lighting: keyboard brightness High"), "{}", text);
+    assert!(text.contains("Keys: "), "{}", text);
+    // State changes end up as text lines at the bottom
+    assert!(rows.iter().any(|row| row.contains("info  Sakura")), "{}", text);
+    // No borders, art or color
+    assert!(!text.contains(['│', '─', '╭', '┃']), "{}", text);
+    let cells = frame.buffer.content();
+    assert!(cells.iter().all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+
+    // Popups read as text too, with plain words for the picker row
+    let mut popup_text = |app: &mut App| {
+        let frame = terminal.draw(|frame| app.render(frame)).unwrap();
+        (0..30)
+            .map(|y| (0..100).map(|x| frame.buffer[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    app.tab = Tab::Panels;
+    app.focused = FocusedPanel::Lighting;
+    for code in [KeyCode::Char('p'), KeyCode::Char('j')] {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+    let text = popup_text(&mut app);
+    assert!(text.contains("editing saturation"), "{}", text);
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Char('T'), KeyModifiers::NONE));
+    let text = popup_text(&mut app);
+    assert!(text.starts_with("Tune, idle, load: "), "{}", text);
+    assert!(text.contains("No runs yet"), "{}", text);
+}
+
+#[test]
+fn test_clock_widget() {
+    use std::time::Duration;
+
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    use crate::telemetry::parse_uptime;
+    use crate::ui::Clock;
+
+    assert_eq!(parse_uptime("4530.25 17000.10\n"), Some(Duration::from_millis(4_530_250)));
+    assert_eq!(parse_uptime(""), None);
+
+    let render = |width| {
+        let area = Rect::new(0, 0, width, 1);
+        let mut buf = Buffer::empty(area);
+        Clock::new("14:32")
+            .uptime(Some(Duration::from_secs(4530)))
+            .power(Some(true), Some(Duration::from_secs(600)))
+            .render(area, &mut buf);
+        (0..width).map(|x| buf[(x, 0)].symbol()).collect::<String>()
+    };
+    assert!(render(100).ends_with("on battery 10m 00s · up 1h 15m · 14:32 "));
+    // Narrow rows keep the time first, then the uptime
+    assert_eq!(render(40).trim_start(), "up 1h 15m · 14:32 ");
+    assert_eq!(render(20).trim_start(), "14:32 ");
+}
+
+#[tokio::test]
+async fn test_profile_rules() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, EditMode};
+    use crate::config::Config;
+    use crate::daemon::{DaemonHandle, GpuMode};
+
+    let config: Config = toml::from_str(
+        "[automation.profiles.quiet]\ncommand = \"true\"\ngpu = \"hybrid\"\n",
+    )
+    .unwrap();
+    let quiet = config.automation.profiles.for_profile(PowerProfile::Quiet).unwrap();
+    assert_eq!(quiet.gpu, Some(GpuMode::Hybrid));
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, config);
+    app.health = None;
+    app.state.connected = true;
+    app.state.gpu_mode = Some(GpuMode::Discrete);
+
+    // The profile found on launch runs nothing
+    app.state.power_profile = PowerProfile::Quiet;
+    app.tick();
+    assert_eq!(app.edit_mode, EditMode::None);
+
+    app.state.power_profile = PowerProfile::Balanced;
+    app.tick();
+    app.state.power_profile = PowerProfile::Quiet;
+    app.tick();
+    assert_eq!(app.edit_mode, EditMode::GpuSwitch(GpuMode::Hybrid));
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert_eq!(app.edit_mode, EditMode::None);
+
+    // Already in the offered mode: no prompt
+    app.state.gpu_mode = Some(GpuMode::Hybrid);
+    app.state.power_profile = PowerProfile::Balanced;
+    app.tick();
+    app.state.power_profile = PowerProfile::Quiet;
+    app.tick();
+    assert_eq!(app.edit_mode, EditMode::None);
+}
+
+#[tokio::test]
+async fn test_scenarios() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, Tab};
+    use crate::config::Config;
+    use crate::daemon::DaemonHandle;
+
+    let config: Config = toml::from_str(
+        "[[scenarios]]\nname = \"Meeting\"\nprofile = \"quiet\"\n\n\
+         [[scenarios]]\nname = \"Desk\"\nprofile = \"balanced\"\ncharge_limit = 5\n",
+    )
+    .unwrap();
+    // A built-in keeps its key when replaced, the user's own follow
+    let names: Vec<_> = config.scenarios().into_iter().map(|s| s.name).collect();
+    assert_eq!(names, ["Travel", "Desk", "Meeting"]);
+
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, config);
+    app.health = None;
+    app.tab = Tab::Panels;
+    app.sakura_enabled = true;
+    app.state.power_profile = PowerProfile::Performance;
+    let press = |app: &mut App, n| app.handle_key(KeyEvent::new(KeyCode::F(n), KeyModifiers::NONE));
+    let status = |app: &App| app.status_message.as_ref().map(|(text, ..)| text.clone());
+
+    press(&mut app, 5);
+    assert_eq!(app.state.power_profile, PowerProfile::Quiet);
+    assert!(!app.sakura_enabled);
+    assert_eq!(
+        status(&app).as_deref(),
+        Some("Travel: Quiet, charge limit 100%, sakura off")
+    );
+
+    // Out-of-range limits are clamped
+    press(&mut app, 6);
+    assert_eq!(app.state.power_profile, PowerProfile::Balanced);
+    assert_eq!(status(&app).as_deref(), Some("Desk: Balanced, charge limit 20%"));
+
+    press(&mut app, 8);
+    assert_eq!(status(&app).as_deref(), Some("No scenario on F8"));
+}
+
+/// Valid curves: 1-8 points at rising temperatures, speeds up to 100%
+fn fan_points() -> impl Strategy<Value = Vec<FanPoint>> {
+    use proptest::collection::{btree_set, vec};
+
+    btree_set(0u8..=120, 1..=8).prop_flat_map(|temps| {
+        let len = temps.len();
+        vec(0u8..=100, len).prop_map(move |speeds| {
+            temps.iter().zip(speeds).map(|(&temp, speed)| FanPoint { temp, speed }).collect()
+        })
+    })
+}
+
+fn curve_of(points: Vec<FanPoint>) -> FanCurve {
+    FanCurve {
+        cpu_curve: points.clone(),
+        gpu_curve: points,
+        enabled: true,
+        enablement: Default::default(),
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_valid_fan_curves_validate(points in fan_points()) {
+        prop_assert!(curve_of(points).validate().is_ok());
+    }
+
+    #[test]
+    fn test_fan_speed_over_100_rejected(
+        points in fan_points(),
+        index in any::<prop::sample::Index>(),
+        speed in 101u8..,
+    ) {
+        let mut curve = curve_of(points);
+        let i = index.index(curve.gpu_curve.len());
+        curve.gpu_curve[i].speed = speed;
+        prop_assert!(curve.validate().is_err());
+    }
+
+    #[test]
+    fn test_target_speed_from_curve(
+        points in fan_points(),
+        hysteresis in 0u8..=10,
+        celsius in -20.0f32..150.0,
+        previous in prop::option::of(0u8..=100),
+    ) {
+        let curve = curve_of(points);
+        let speed = curve.target_speed(celsius, previous, hysteresis);
+        prop_assert!(speed <= 100);
+        // Always a breakpoint's speed, the speed held through the
+        // hysteresis band, or off below the first breakpoint
+        let known = curve.cpu_curve.iter().any(|point| point.speed == speed);
+        prop_assert!(known || Some(speed) == previous || speed == 0);
+    }
+
+    #[test]
+    fn test_catmull_rom_passes_through_points(
+        points in prop::array::uniform4((-500.0f32..500.0, -500.0f32..500.0)),
+    ) {
+        use crate::ui::catmull_rom;
+
+        let [p0, p1, p2, p3] = points;
+        let start = catmull_rom(p0, p1, p2, p3, 0.0);
+        let end = catmull_rom(p0, p1, p2, p3, 1.0);
+        prop_assert!((start.0 - p1.0).abs() < 1e-3 && (start.1 - p1.1).abs() < 1e-3);
+        prop_assert!((end.0 - p2.0).abs() < 1e-2 && (end.1 - p2.1).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_spline_stays_in_graph(points in fan_points(), width in 1u16..120, height in 1u16..40) {
+        use crate::ui::spline_points;
+        use ratatui::layout::Rect;
+
+        // Mapped the way the fan curve graph maps them, 30-100°C across
+        let area = Rect::new(4, 2, width, height);
+        let screen: Vec<(f32, f32)> = points
+            .iter()
+            .map(|point| {
+                let x_ratio = point.temp.saturating_sub(30) as f32 / 70.0;
+                let y_ratio = 1.0 - point.speed as f32 / 100.0;
+                (4.0 + width as f32 * x_ratio, 2.0 + height as f32 * y_ratio)
+            })
+            .collect();
+        for (x, y) in spline_points(&screen, &area) {
+            prop_assert!(x >= area.x as f32 && x < area.right() as f32);
+            prop_assert!(y >= area.y as f32 && y < area.bottom() as f32);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_actor_against_fake_asusd() {
+    use crate::daemon::{AsusdVersion, DaemonHandle, HardwareUpdate, Rollback};
+    use fake_asusd::{expect_update, platform, serve, Platform, TestBus, MIN_CHARGE_LIMIT};
+
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let asusd = serve(&bus, Platform::default()).await.unwrap();
+    let mut daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+
+    let update = expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::StateRefresh(_))).await;
+    let HardwareUpdate::StateRefresh(state) = update else {
+        unreachable!()
+    };
+    assert!(state.connected);
+    assert_eq!(state.charge_limit, 80);
+    assert_eq!(state.asusd_version, Some(AsusdVersion::new(6, 1, 12)));
+
+    // Intent → D-Bus write → confirmation
+    daemon.set_power_profile(PowerProfile::Performance);
+    let performance = |u: &HardwareUpdate| {
+        matches!(u, HardwareUpdate::PowerProfileChanged(PowerProfile::Performance))
+    };
+    expect_update(&mut daemon, performance).await;
+    assert_eq!(platform(&asusd).await.0, PowerProfile::Performance.to_u32());
+    daemon.set_charge_limit(60);
+    expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::ChargeLimitChanged(60))).await;
+    assert_eq!(platform(&asusd).await.1, 60);
+
+    // A rejected write is reported and rolled back to the confirmed value
+    daemon.set_charge_limit(MIN_CHARGE_LIMIT - 10);
+    expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::Error(_))).await;
+    let update = expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::RolledBack(_))).await;
+    assert!(matches!(update, HardwareUpdate::RolledBack(Rollback::ChargeLimit(60))));
+    assert_eq!(platform(&asusd).await.1, 60);
+}
+
+#[tokio::test]
+async fn test_fan_curve_toggle() {
+    use std::time::Duration;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, EditMode, FocusedPanel, Tab};
+    use crate::config::Config;
+    use crate::daemon::DaemonHandle;
+    use fake_asusd::{fan_curves, serve, Platform, TestBus};
+
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let asusd = serve(&bus, Platform::default()).await.unwrap();
+    let daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+    let mut app = App::new(daemon, Config::default());
+    app.health = None;
+    app.tab = Tab::Panels;
+    app.focused = FocusedPanel::FanCurve;
+
+    // The indicator moves only once asusd has taken the change
+    async fn toggle(app: &mut App, enabled: bool) {
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_ne!(app.state.fan_curve.enabled, enabled);
+        let waiting = async {
+            while app.state.fan_curve.enabled != enabled {
+                app.process_updates();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("confirmation from the actor");
+    }
+    toggle(&mut app, true).await;
+    let balanced = PowerProfile::Balanced.to_u32();
+    let on = vec![(balanced, "CPU".to_string()), (balanced, "GPU".to_string())];
+    assert_eq!(fan_curves(&asusd).await, on);
+    assert_eq!(app.edit_mode, EditMode::None);
+    toggle(&mut app, false).await;
+    assert!(fan_curves(&asusd).await.is_empty());
+
+    // A refresh reads the switch back from asusd rather than resetting it
+    toggle(&mut app, true).await;
+    app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+    app.state.fan_curve.enabled = false;
+    let waiting = async {
+        while !app.state.fan_curve.enabled {
+            app.process_updates();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), waiting)
+        .await
+        .expect("refresh from the actor");
+    toggle(&mut app, false).await;
+    assert!(fan_curves(&asusd).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_full_blast_reaches_asusd() {
+    use std::time::Duration;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::app::{App, FocusedPanel, Tab};
+    use crate::config::Config;
+    use crate::daemon::DaemonHandle;
+    use fake_asusd::{serve, written_curves, Platform, TestBus};
+
+    // Without asusd's fan curves there is nothing to pin, so no countdown starts
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    app.health = None;
+    app.tab = Tab::Panels;
+    app.handle_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::NONE));
+    let status = app.status_message.as_ref().map(|(text, ..)| text.clone());
+    assert!(status.is_some_and(|text| text.starts_with("Full blast unsupported")));
+
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let asusd = serve(&bus, Platform::default()).await.unwrap();
+    let daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+    let mut app = App::new(daemon, Config::default());
+    app.health = None;
+    app.tab = Tab::Panels;
+    async fn settle(app: &mut App, done: impl Fn(&App) -> bool) {
+        let waiting = async {
+            while !done(app) {
+                app.process_updates();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("update from the actor");
+    }
+    settle(&mut app, |app| app.state.fan_curve_control).await;
+
+    // Both fans of the active profile are written at full duty and switched on
+    app.handle_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::NONE));
+    let status = app.status_message.as_ref().map(|(text, ..)| text.clone());
+    assert!(status.is_some_and(|text| text.starts_with("Full blast: fans at 100%")));
+    // asusd's startup signals may replace the status line right away
+    settle(&mut app, |app| {
+        app.history.messages().any(|message| message.text == "Fan curve updated")
+    })
+    .await;
+    let curves = written_curves(&asusd, PowerProfile::Balanced.to_u32()).await;
+    assert_eq!(curves.len(), 2);
+    assert!(curves.iter().all(|data| data.enabled && data.pwm.iter().all(|&pwm| pwm == 255)));
+    assert!(app.state.fan_curve.cpu_curve.iter().all(|point| point.speed == 100));
+
+    // Switching profile mid-burst: ending it gives Balanced its curve back
+    // and leaves Performance's alone
+    app.focused = FocusedPanel::PowerProfile;
+    app.selected_profile = PowerProfile::Performance.ui_index();
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    settle(&mut app, |app| app.state.power_profile == PowerProfile::Performance).await;
+    app.handle_key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::NONE));
+    let restored = async {
+        loop {
+            let curves = written_curves(&asusd, PowerProfile::Balanced.to_u32()).await;
+            if curves.iter().all(|data| data.pwm.iter().any(|&pwm| pwm < 255)) {
+                break;
+            }
+            app.process_updates();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), restored)
+        .await
+        .expect("Balanced curve restored");
+    assert!(written_curves(&asusd, PowerProfile::Performance.to_u32()).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_process_rule_preset() {
+    use std::time::Duration;
+
+    use crate::app::App;
+    use crate::config::Config;
+    use crate::daemon::DaemonHandle;
+    use crate::preset::Preset;
+    use fake_asusd::{platform, serve, written_curves, Platform, TestBus};
+
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let asusd = serve(&bus, Platform::default()).await.unwrap();
+    // The test binary itself is the program the rule waits for
+    let config: Config = toml::from_str(
+        "[[automation.processes]]\nmatch = [\"hachi-*\"]\npreset = \"gaming\"",
+    )
+    .unwrap();
+    let daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+    let mut app = App::new(daemon, config);
+    app.health = None;
+    let mut preset = Preset::from_state("gaming", &app.state);
+    preset.fan_curve = FanCurve::default_curve();
+    for point in &mut preset.fan_curve.cpu_curve {
+        point.speed = point.speed.max(60);
+    }
+    app.presets = vec![preset];
+
+    async fn settle(app: &mut App, done: impl Fn(&App) -> bool) {
+        let waiting = async {
+            while !done(app) {
+                app.process_updates();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("update from the actor");
+    }
+    settle(&mut app, |app| app.state.fan_curve_control).await;
+    let performance = PowerProfile::Performance.to_u32();
+    let preset_written = |curves: &[crate::daemon::CurveData]| {
+        curves.iter().any(|data| data.fan == "CPU" && data.pwm.iter().all(|&pwm| pwm >= 153))
+    };
+
+    // The preset lands in the rule's profile once that profile's curve loads
+    app.tick();
+    let applied = async {
+        while !preset_written(&written_curves(&asusd, performance).await) {
+            app.process_updates();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), applied).await.expect("preset written");
+    assert_eq!(platform(&asusd).await.0, performance);
+    assert!(written_curves(&asusd, PowerProfile::Balanced.to_u32()).await.is_empty());
+
+    // Once the program is gone the profile's own curve and the old profile return
+    app.config.automation.processes[0].processes = vec!["no-such-program".to_string()];
+    tokio::time::sleep(Duration::from_millis(5100)).await;
+    app.tick();
+    let restored = async {
+        while preset_written(&written_curves(&asusd, performance).await)
+            || platform(&asusd).await.0 != PowerProfile::Balanced.to_u32()
+        {
+            app.process_updates();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), restored).await.expect("rule undone");
+    assert!(written_curves(&asusd, PowerProfile::Balanced.to_u32()).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_fan_enablement_matrix() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    use crate::app::{App, EditMode, FocusedPanel, Tab};
+    use crate::config::Config;
+    use crate::daemon::{DaemonHandle, Fan, HardwareState, HardwareUpdate};
+    use crate::ui::FanCurveGraph;
+    use fake_asusd::{
+        expect_update, fan_curves, serve, serve_with_fans, FanCurves, Platform, TestBus,
+    };
+
+    // Only the active profile's switches move the ●/○ indicator
+    let mut state = HardwareState::default();
+    let gpu_on = HardwareUpdate::FanEnabledChanged(PowerProfile::Performance, Fan::Gpu, true);
+    state.apply(&gpu_on);
+    assert!(state.fan_curve.enablement.get(PowerProfile::Performance, Fan::Gpu));
+    assert!(!state.fan_curve.enablement.get(PowerProfile::Performance, Fan::Cpu));
+    assert!(!state.fan_curve.enabled);
+    state.apply(&HardwareUpdate::FanEnabledChanged(PowerProfile::Balanced, Fan::Cpu, true));
+    assert!(state.fan_curve.enabled);
+    state.apply(&HardwareUpdate::FanCurveEnabledChanged(false));
+    assert!(!state.fan_curve.enablement.any(PowerProfile::Balanced));
+    assert!(state.fan_curve.enablement.any(PowerProfile::Performance));
+
+    // Drawn as a grid in the graph's corner, the chosen cell as a diamond
+    let mut buf = Buffer::empty(Rect::new(0, 0, 60, 16));
+    FanCurveGraph::new(&state.fan_curve)
+        .selected_switch(Some((PowerProfile::Quiet, Fan::Cpu)))
+        .render(buf.area, &mut buf);
+    let rows: Vec<String> = (0..16)
+        .map(|y| (0..60).map(|x| buf[(x, y)].symbol()).collect::<String>())
+        .collect();
+    let header = rows.iter().position(|row| row.contains("Q B P")).unwrap();
+    assert!(rows[header + 1].contains("CPU ◇ ○ ○"), "{}", rows.join("\n"));
+    assert!(rows[header + 2].contains("GPU ○ ○ ●"), "{}", rows.join("\n"));
+
+    // m opens the matrix on the active profile; l and j move to Performance's GPU fan
+    let daemon = DaemonHandle::spawn_on(Some("unix:path=/nonexistent".to_string()));
+    let mut app = App::new(daemon, Config::default());
+    app.health = None;
+    app.tab = Tab::Panels;
+    app.focused = FocusedPanel::FanCurve;
+    for code in [KeyCode::Char('m'), KeyCode::Char('l'), KeyCode::Char('l'), KeyCode::Char('j')] {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+    let cell = EditMode::FanEnablement { profile: PowerProfile::Performance, fan: Fan::Gpu };
+    assert_eq!(app.edit_mode, cell);
+    app.read_only = true;
+    app.edit_mode = EditMode::None;
+    app.handle_key(KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE));
+    assert_eq!(app.edit_mode, EditMode::None);
+
+    // One fan in one profile reaches asusd, and comes back confirmed
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let asusd = serve(&bus, Platform::default()).await.unwrap();
+    let mut daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+    expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::StateRefresh(_))).await;
+    daemon.set_fan_enabled(PowerProfile::Performance, Fan::Gpu, true);
+    let confirmed = |u: &HardwareUpdate| {
+        matches!(u, HardwareUpdate::FanEnabledChanged(PowerProfile::Performance, Fan::Gpu, true))
+    };
+    expect_update(&mut daemon, confirmed).await;
+    let performance = PowerProfile::Performance.to_u32();
+    assert_eq!(fan_curves(&asusd).await, vec![(performance, "GPU".to_string())]);
+    assert!(daemon.state().fan_curve.enablement.get(PowerProfile::Performance, Fan::Gpu));
+
+    // The grid starts from what asusd already has switched on, in every profile
+    drop(asusd);
+    let Some(bus) = TestBus::start() else {
+        return;
+    };
+    let quiet = PowerProfile::Quiet.to_u32();
+    let fans = FanCurves {
+        enabled: vec![(quiet, "CPU".to_string()), (performance, "GPU".to_string())],
+        ..Default::default()
+    };
+    let _asusd = serve_with_fans(&bus, Platform::default(), fans).await.unwrap();
+    let mut daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+    let update = expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::StateRefresh(_))).await;
+    let HardwareUpdate::StateRefresh(state) = update else {
+        unreachable!();
+    };
+    let enablement = state.fan_curve.enablement;
+    let on: Vec<_> = PowerProfile::ALL
+        .into_iter()
+        .flat_map(|profile| Fan::ALL.map(|fan| (profile, fan)))
+        .filter(|&(profile, fan)| enablement.get(profile, fan))
+        .collect();
+    assert_eq!(on, [(PowerProfile::Quiet, Fan::Cpu), (PowerProfile::Performance, Fan::Gpu)]);
+    // Balanced is active and has neither fan on
+    assert!(!state.fan_curve.enabled);
+}
+
+#[tokio::test]
+async fn test_actor_reconnects_to_fake_asusd() {
+    use crate::daemon::{DaemonHandle, DisconnectReason, HardwareUpdate};
+    use fake_asusd::{expect_update, serve, Platform, TestBus};
+
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let asusd = serve(&bus, Platform::default()).await.unwrap();
+    let mut daemon = DaemonHandle::spawn_on(Some(bus.address.clone()));
+    expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::StateRefresh(_))).await;
+
+    // asusd stops: the next refresh notices
+    drop(asusd);
+    daemon.refresh();
+    let update = expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::Disconnected(_))).await;
+    assert!(matches!(update, HardwareUpdate::Disconnected(DisconnectReason::AsusdMissing)));
+    assert!(!daemon.state().connected);
+
+    // ...and picks it up again once it's back
+    let platform = Platform {
+        charge_limit: 70,
+        ..Default::default()
+    };
+    let _asusd = serve(&bus, platform).await.unwrap();
+    daemon.refresh();
+    expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::Connected)).await;
+    let update = expect_update(&mut daemon, |u| matches!(u, HardwareUpdate::StateRefresh(_))).await;
+    let HardwareUpdate::StateRefresh(state) = update else {
+        unreachable!()
+    };
+    assert!(state.connected);
+    assert_eq!(state.charge_limit, 70);
+}
+
+/// Any key, with or without a modifier, except those that write outside
+/// the test's config directory: `y` (clipboard), `e` (fan curve export),
+/// `B` (bug report) and Ctrl+R (telemetry recording)
+fn key_event() -> impl Strategy<Value = crossterm::event::KeyEvent> {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    let code = prop_oneof![
+        8 => prop::char::range(' ', '~').prop_map(KeyCode::Char),
+        2 => prop::sample::select(vec![
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::Left,
+            KeyCode::Right,
+            KeyCode::Enter,
+            KeyCode::Esc,
+            KeyCode::Tab,
+            KeyCode::BackTab,
+            KeyCode::Home,
+            KeyCode::End,
+            KeyCode::PageUp,
+            KeyCode::PageDown,
+            KeyCode::Backspace,
+            KeyCode::Delete,
+        ]),
+        1 => (1u8..=12).prop_map(KeyCode::F),
+    ];
+    let modifiers = prop::sample::select(vec![
+        KeyModifiers::NONE,
+        KeyModifiers::NONE,
+        KeyModifiers::SHIFT,
+        KeyModifiers::CONTROL,
+        KeyModifiers::ALT,
+    ]);
+    (code, modifiers)
+        .prop_filter("writes outside the test directory", |(code, modifiers)| match code {
+            KeyCode::Char('y' | 'e' | 'B') => false,
+            KeyCode::Char('r') => !modifiers.contains(KeyModifiers::CONTROL),
+            _ => true,
+        })
+        .prop_map(|(code, modifiers)| KeyEvent::new(code, modifiers))
+}
+
+/// What must hold after every key, whatever was pressed before it
+fn check_app_invariants(app: &crate::app::App) -> Result<(), TestCaseError> {
+    use crate::app::EditMode;
+    use crate::daemon::CHARGE_LIMIT_RANGE;
+
+    prop_assert!(app.selected_profile <= 2, "profile {}", app.selected_profile);
+    let limit = app.state.charge_limit;
+    prop_assert!(
+        (CHARGE_LIMIT_RANGE.min..=CHARGE_LIMIT_RANGE.max).contains(&limit),
+        "charge limit {}",
+        limit
+    );
+    prop_assert!(app.presets.is_empty() || app.selected_preset < app.presets.len());
+
+    if app.edit_mode != EditMode::None {
+        prop_assert!(!app.show_help, "{:?} under the help popup", app.edit_mode);
+    }
+    // Presets are files, not hardware settings, so they stay editable
+    let hardware_edit = !matches!(
+        app.edit_mode,
+        EditMode::None | EditMode::PresetName { .. } | EditMode::PresetDelete
+    );
+    prop_assert!(!(app.read_only && hardware_edit), "{:?} while read-only", app.edit_mode);
+    match app.edit_mode {
+        EditMode::FanCurve { point_index } => {
+            let points = app.state.fan_curve.cpu_curve.len();
+            prop_assert!(point_index < points, "point {} of {}", point_index, points);
+        }
+        EditMode::PresetName { rename: true } | EditMode::PresetDelete => {
+            prop_assert!(app.selected_preset < app.presets.len(), "{:?}", app.edit_mode);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[test]
+fn test_random_keys_keep_app_consistent() {
+    use std::time::{Duration, Instant};
+
+    use crate::app::App;
+    use crate::config::{self, Config};
+    use crate::daemon::DaemonHandle;
+    use fake_asusd::{serve, Platform, TestBus};
+    use proptest::test_runner::{Config as ProptestConfig, TestRunner};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _runtime = runtime.enter();
+    let Some(bus) = TestBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping");
+        return;
+    };
+    let _asusd = runtime.block_on(serve(&bus, Platform::default())).unwrap();
+
+    // Header, unit and layout keys save the config, preset keys write presets
+    let dir = std::env::temp_dir().join(format!("hachi-test-keys-{}", std::process::id()));
+    config::set_config_path(dir.join("config.toml"));
+    config::set_profile_dir(dir.join("profiles"));
+    let mut config = Config::default();
+    config.tune.command = "true".to_string();
+
+    let mut runner = TestRunner::new(ProptestConfig::with_cases(32));
+    let keys = (prop::collection::vec(key_event(), 1..400), any::<bool>());
+    let result = runner.run(&keys, |(keys, read_only)| {
+        let daemon = DaemonHandle::spawn_on(Some(bus.address.clone())).read_only(read_only);
+        let mut app = App::new(daemon, config.clone());
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+
+        // Start from asusd's values: the charge limit reads 0 until the
+        // first refresh after connecting
+        let connecting = Instant::now();
+        while app.state.charge_limit == 0 && connecting.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(5));
+            app.process_updates();
+        }
+        prop_assert!(app.state.connected);
+
+        for (i, key) in keys.into_iter().enumerate() {
+            app.handle_key(key);
+            // The main loop would exit; keep going to reach more states
+            app.should_quit = false;
+            check_app_invariants(&app)?;
+
+            // Let confirmations, errors and rollbacks arrive in between,
+            // and draw what the keys led to
+            if i % 8 == 7 {
+                std::thread::sleep(Duration::from_millis(1));
+                app.process_updates();
+                app.tick();
+                terminal.draw(|frame| app.render(frame)).unwrap();
+                check_app_invariants(&app)?;
+            }
+        }
+        Ok(())
+    });
+    let _ = std::fs::remove_dir_all(&dir);
+    result.unwrap();
+}
+
+#[tokio::test]
+async fn test_full_intent_queue() {
+    use std::os::unix::net::UnixListener;
+    use std::time::Duration;
+
+    use crate::daemon::{DaemonHandle, HardwareUpdate, Rollback};
+
+    // A bus that takes the connection and never answers keeps the actor
+    // stuck connecting, so nothing drains the 32-intent queue
+    let path = std::env::temp_dir().join(format!("hachi-test-stalled-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let _listener = UnixListener::bind(&path).unwrap();
+    let mut daemon = DaemonHandle::spawn_on(Some(format!("unix:path={}", path.display())));
+
+    for _ in 0..32 {
+        daemon.refresh();
+    }
+    assert_eq!(daemon.take_dropped(), 0);
+    daemon.refresh();
+    assert_eq!(daemon.take_dropped(), 1);
+
+    // Writes wait for room before they're dropped; a newer write to the
+    // same setting replaces one still waiting
+    daemon.set_charge_limit(60);
+    daemon.set_charge_limit(70);
+    daemon.set_power_profile(PowerProfile::Quiet);
+    assert_eq!(daemon.take_dropped(), 0);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(daemon.take_dropped(), 2);
+
+    // ...and the UI gets the confirmed values back
+    let updates: Vec<_> = std::iter::from_fn(|| daemon.try_recv()).collect();
+    let rollbacks: Vec<_> = updates
+        .iter()
+        .filter_map(|update| match update {
+            HardwareUpdate::RolledBack(rollback) => Some(rollback),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(rollbacks.len(), 2);
+    assert!(rollbacks.iter().any(|rollback| matches!(rollback, Rollback::ChargeLimit(_))));
+    assert!(rollbacks.iter().any(|rollback| matches!(rollback, Rollback::PowerProfile(_))));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_throttle_episodes() {
+    use crate::telemetry::{cooling_engaged, ThrottleReading, ThrottleTracker, TREND_SAMPLES};
+
+    let counter = |events| ThrottleReading {
+        events: Some(events),
+        cooling: false,
+    };
+    let mut tracker = ThrottleTracker::default();
+
+    // The first counter value is only a baseline, however large
+    tracker.record(Some(70.0), counter(40));
+    assert_eq!(tracker.episodes, 0);
+    assert!(!tracker.throttling());
+
+    // Consecutive rises are one episode
+    tracker.record(Some(95.0), counter(42));
+    tracker.record(Some(97.0), counter(45));
+    assert_eq!(tracker.episodes, 1);
+    assert!(tracker.throttling());
+
+    tracker.record(Some(80.0), counter(45));
+    assert!(!tracker.throttling());
+
+    // An engaged cooling device counts without any counters
+    tracker.record(Some(96.0), ThrottleReading { events: None, cooling: true });
+    assert_eq!(tracker.episodes, 2);
+
+    let marked: Vec<bool> = tracker.trend.iter().map(|sample| sample.throttled).collect();
+    assert_eq!(marked, [false, true, true, false, true]);
+
+    for _ in 0..TREND_SAMPLES {
+        tracker.record(None, ThrottleReading::default());
+    }
+    assert_eq!(tracker.trend.len(), TREND_SAMPLES);
+    assert_eq!(tracker.episodes, 2);
+
+    // intel_powerclamp idles at -1
+    assert!(!cooling_engaged("-1"));
+    assert!(!cooling_engaged("0"));
+    assert!(cooling_engaged("3"));
+    assert!(!cooling_engaged(""));
+}
@@ -0,0 +1,255 @@
+//! A private D-Bus bus with a fake asusd on it, so tests can run the
+//! hardware actor end to end: intent → D-Bus call → update
+
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+use zbus::{connection, fdo, interface, Connection};
+
+use crate::daemon::{CurveData, DaemonHandle, Fan, FanCurve, HardwareUpdate};
+
+/// Lowest charge limit the fake accepts, so tests can provoke a failed write
+pub const MIN_CHARGE_LIMIT: u8 = 40;
+
+/// A `dbus-daemon` of our own, stopped on drop
+pub struct TestBus {
+    daemon: Child,
+    config: PathBuf,
+    pub address: String,
+}
+
+impl TestBus {
+    /// Start a bus that lets anyone own any name, or `None` when
+    /// `dbus-daemon` isn't installed
+    pub fn start() -> Option<Self> {
+        let config = std::env::temp_dir().join(format!(
+            "hachi-test-bus-{}-{:?}.conf",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(
+            &config,
+            r#"<!DOCTYPE busconfig PUBLIC "-//freedesktop//DTD D-Bus Bus Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/busconfig.dtd">
+<busconfig>
+  <type>session</type>
+  <listen>unix:tmpdir=/tmp</listen>
+  <policy context="default">
+    <allow send_destination="*" eavesdrop="true"/>
+    <allow eavesdrop="true"/>
+    <allow own="*"/>
+  </policy>
+</busconfig>
+"#,
+        )
+        .ok()?;
+
+        let mut daemon = Command::new("dbus-daemon")
+            .arg(format!("--config-file={}", config.display()))
+            .args(["--nofork", "--print-address"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let mut address = String::new();
+        BufReader::new(daemon.stdout.take()?)
+            .read_line(&mut address)
+            .ok()?;
+        Some(Self {
+            daemon,
+            config,
+            address: address.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for TestBus {
+    fn drop(&mut self) {
+        let _ = self.daemon.kill();
+        let _ = self.daemon.wait();
+        let _ = std::fs::remove_file(&self.config);
+    }
+}
+
+/// asusd's Platform interface, holding its values in memory
+pub struct Platform {
+    pub profile: u32,
+    pub charge_limit: u8,
+    pub version: String,
+}
+
+impl Default for Platform {
+    fn default() -> Self {
+        Self {
+            profile: 0,
+            charge_limit: 80,
+            version: "6.1.12".to_string(),
+        }
+    }
+}
+
+#[interface(name = "xyz.ljones.Platform")]
+impl Platform {
+    #[zbus(property)]
+    fn platform_profile(&self) -> u32 {
+        self.profile
+    }
+
+    #[zbus(property)]
+    fn set_platform_profile(&mut self, profile: u32) {
+        self.profile = profile;
+    }
+
+    #[zbus(property)]
+    fn charge_control_end_threshold(&self) -> u8 {
+        self.charge_limit
+    }
+
+    #[zbus(property)]
+    fn set_charge_control_end_threshold(&mut self, limit: u8) -> fdo::Result<()> {
+        if limit < MIN_CHARGE_LIMIT {
+            return Err(fdo::Error::InvalidArgs(format!(
+                "charge limit {} too low",
+                limit
+            )));
+        }
+        self.charge_limit = limit;
+        Ok(())
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> String {
+        self.version.clone()
+    }
+
+    fn next_platform_profile(&mut self) {
+        self.profile = (self.profile + 1) % 2;
+    }
+}
+
+/// asusd's FanCurves interface: the profile and fan of each custom curve
+/// that is on, and the curves written so far
+#[derive(Default)]
+pub struct FanCurves {
+    pub enabled: Vec<(u32, String)>,
+    pub curves: Vec<(u32, CurveData)>,
+}
+
+#[interface(name = "xyz.ljones.FanCurves")]
+impl FanCurves {
+    /// Curves written for `profile`, the default curve for the others
+    fn fan_curve_data(&self, profile: u32) -> Vec<CurveData> {
+        let default = FanCurve::default_curve();
+        Fan::ALL
+            .map(|fan| {
+                let key = (profile, fan.as_str());
+                let enabled = self.enabled.iter().any(|(p, f)| (*p, f.as_str()) == key);
+                let written = self.curves.iter().find(|(p, data)| (*p, data.fan.as_str()) == key);
+                match written {
+                    Some((_, data)) => CurveData { enabled, ..data.clone() },
+                    None => CurveData::new(fan, default.points(fan), enabled),
+                }
+            })
+            .to_vec()
+    }
+
+    fn set_fan_curve(&mut self, profile: u32, curve: CurveData) -> fdo::Result<()> {
+        if curve.pwm.len() != 8 || curve.temp.len() != 8 {
+            return Err(fdo::Error::InvalidArgs("curves have eight points".to_string()));
+        }
+        self.set_profile_fan_curve_enabled(profile, &curve.fan.clone(), curve.enabled);
+        self.curves.retain(|(p, data)| (*p, data.fan.as_str()) != (profile, curve.fan.as_str()));
+        self.curves.push((profile, curve));
+        Ok(())
+    }
+
+    fn set_fan_curves_enabled(&mut self, profile: u32, enabled: bool) {
+        for fan in ["CPU", "GPU"] {
+            self.set_profile_fan_curve_enabled(profile, fan, enabled);
+        }
+    }
+
+    fn set_profile_fan_curve_enabled(&mut self, profile: u32, fan: &str, enabled: bool) {
+        self.enabled.retain(|(p, f)| (*p, f.as_str()) != (profile, fan));
+        if enabled {
+            self.enabled.push((profile, fan.to_string()));
+        }
+    }
+}
+
+/// Claim asusd's name and export `platform`; asusd goes away when the
+/// returned connection is dropped
+pub async fn serve(bus: &TestBus, platform: Platform) -> zbus::Result<Connection> {
+    serve_with_fans(bus, platform, FanCurves::default()).await
+}
+
+/// `serve`, starting from the given fan curve state
+pub async fn serve_with_fans(
+    bus: &TestBus,
+    platform: Platform,
+    fans: FanCurves,
+) -> zbus::Result<Connection> {
+    connection::Builder::address(bus.address.as_str())?
+        .name("xyz.ljones.Asusd")?
+        .serve_at("/", fdo::ObjectManager)?
+        .serve_at("/xyz/ljones", platform)?
+        .serve_at("/xyz/ljones", fans)?
+        .build()
+        .await
+}
+
+/// The fake's current values
+pub async fn platform(conn: &Connection) -> (u32, u8) {
+    let iface = conn
+        .object_server()
+        .interface::<_, Platform>("/xyz/ljones")
+        .await
+        .expect("Platform is served");
+    let platform = iface.get().await;
+    (platform.profile, platform.charge_limit)
+}
+
+/// Profiles and fans the fake has custom curves switched on for
+pub async fn fan_curves(conn: &Connection) -> Vec<(u32, String)> {
+    let iface = conn
+        .object_server()
+        .interface::<_, FanCurves>("/xyz/ljones")
+        .await
+        .expect("FanCurves is served");
+    let enabled = iface.get().await.enabled.clone();
+    enabled
+}
+
+/// Curves the fake holds for `profile`, as written
+pub async fn written_curves(conn: &Connection, profile: u32) -> Vec<CurveData> {
+    let iface = conn
+        .object_server()
+        .interface::<_, FanCurves>("/xyz/ljones")
+        .await
+        .expect("FanCurves is served");
+    let curves = iface.get().await.curves.clone();
+    curves.into_iter().filter(|(p, _)| *p == profile).map(|(_, data)| data).collect()
+}
+
+/// Wait up to 5 s for an update matching `wanted`, skipping the others
+pub async fn expect_update(
+    daemon: &mut DaemonHandle,
+    wanted: impl Fn(&HardwareUpdate) -> bool,
+) -> HardwareUpdate {
+    let waiting = async {
+        loop {
+            while let Some(update) = daemon.try_recv() {
+                if wanted(&update) {
+                    return update;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), waiting)
+        .await
+        .expect("update from the actor")
+}
@@ -0,0 +1,188 @@
+//! Stress runs for fan curve tuning: launch a load command, record the
+//! temperature and fan response until it exits plus a cooldown, and keep
+//! the previous run to compare against
+
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::TemperatureUnit;
+use crate::error::{HachiError, Result};
+
+/// Within this many °C of the starting temperature counts as cooled down
+const RECOVERED_WITHIN: f32 = 5.0;
+
+/// One telemetry reading during a run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TuneSample {
+    /// Seconds since the run started
+    pub secs: f32,
+    pub celsius: f32,
+    /// Fastest fan, when the fans report speed
+    pub fan_rpm: Option<u32>,
+}
+
+/// Temperatures and fan speeds over one run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace {
+    pub samples: Vec<TuneSample>,
+    /// When the load command exited, in seconds since the start
+    pub load_secs: Option<f32>,
+}
+
+impl Trace {
+    pub fn peak(&self) -> Option<f32> {
+        self.samples.iter().map(|s| s.celsius).reduce(f32::max)
+    }
+
+    pub fn peak_rpm(&self) -> Option<u32> {
+        self.samples.iter().filter_map(|s| s.fan_rpm).max()
+    }
+
+    /// Seconds from the end of the load until the temperature was back
+    /// within a few degrees of where it started
+    pub fn recovery_secs(&self) -> Option<f32> {
+        let load_secs = self.load_secs?;
+        let baseline = self.samples.first()?.celsius + RECOVERED_WITHIN;
+        self.samples
+            .iter()
+            .find(|s| s.secs >= load_secs && s.celsius <= baseline)
+            .map(|s| s.secs - load_secs)
+    }
+
+    pub fn duration_secs(&self) -> f32 {
+        self.samples.last().map_or(0.0, |s| s.secs)
+    }
+
+    /// `peak 91°C`, `4800 rpm`, `cooled in 24s`: whichever are known
+    pub fn summary(&self, unit: TemperatureUnit) -> Vec<String> {
+        let mut parts = Vec::new();
+        if let Some(peak) = self.peak() {
+            parts.push(format!("peak {}", unit.format(peak)));
+        }
+        if let Some(rpm) = self.peak_rpm() {
+            parts.push(format!("{} rpm", rpm));
+        }
+        match (self.load_secs, self.recovery_secs()) {
+            (Some(_), Some(secs)) => parts.push(format!("cooled in {:.0}s", secs)),
+            (Some(_), None) => parts.push("still cooling".to_string()),
+            (None, _) => {}
+        }
+        parts
+    }
+}
+
+/// A run in progress
+pub struct TuneRun {
+    child: Option<Child>,
+    started: Instant,
+    /// When the load command exited and the cooldown began
+    cooldown_from: Option<Instant>,
+    pub trace: Trace,
+}
+
+impl TuneRun {
+    /// Start the load command through `sh -c`, in its own process group so
+    /// stopping it also stops the workers it forks
+    pub fn start(command: &str) -> Result<Self> {
+        let child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .process_group(0)
+            .spawn()
+            .map_err(|e| HachiError::Config(format!("could not run {:?}: {}", command, e)))?;
+        Ok(Self {
+            child: Some(child),
+            started: Instant::now(),
+            cooldown_from: None,
+            trace: Trace::default(),
+        })
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Whether the load command is still running
+    pub fn loading(&self) -> bool {
+        self.cooldown_from.is_none()
+    }
+
+    pub fn record(&mut self, celsius: f32, fan_rpm: Option<u32>) {
+        self.trace.samples.push(TuneSample {
+            secs: self.started.elapsed().as_secs_f32(),
+            celsius,
+            fan_rpm,
+        });
+    }
+
+    /// Notice the load command exiting; true once the cooldown after it is
+    /// over and the run is complete
+    pub fn poll(&mut self, cooldown: Duration) -> bool {
+        if let Some(child) = &mut self.child {
+            if !matches!(child.try_wait(), Ok(None)) {
+                self.end_load();
+            }
+        }
+        self.cooldown_from.is_some_and(|from| from.elapsed() >= cooldown)
+    }
+
+    /// Stop the load early and go straight to the cooldown
+    pub fn end_load(&mut self) {
+        self.kill();
+        if self.cooldown_from.is_none() {
+            self.cooldown_from = Some(Instant::now());
+            self.trace.load_secs = Some(self.started.elapsed().as_secs_f32());
+        }
+    }
+
+    fn kill(&mut self) {
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        if matches!(child.try_wait(), Ok(None)) {
+            // SIGKILL so the wait below can't hang on a command ignoring TERM.
+            // SAFETY: signals the process group the child leads, nothing else
+            unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) };
+        }
+        let _ = child.wait();
+    }
+}
+
+impl Drop for TuneRun {
+    /// Never leave the stress command running after hachi exits
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// The run in progress and the last two finished ones
+#[derive(Default)]
+pub struct Tuning {
+    pub run: Option<TuneRun>,
+    pub before: Option<Trace>,
+    pub after: Option<Trace>,
+}
+
+impl Tuning {
+    /// Keep the finished run as the latest, the previous one becoming the
+    /// baseline
+    pub fn finish(&mut self) -> Option<&Trace> {
+        let run = self.run.take()?;
+        self.before = self.after.take();
+        self.after = Some(run.trace.clone());
+        self.after.as_ref()
+    }
+
+    /// Baseline and latest trace to plot; a run in progress is drawn live
+    /// against the last finished one
+    pub fn traces(&self) -> (Option<&Trace>, Option<&Trace>) {
+        match &self.run {
+            Some(run) => (self.after.as_ref(), Some(&run.trace)),
+            None => (self.before.as_ref(), self.after.as_ref()),
+        }
+    }
+}
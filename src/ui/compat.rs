@@ -0,0 +1,111 @@
+//! Safe rendering for remote sessions and the Linux console: the finished
+//! frame is rewritten to the xterm 256-color palette and plain ASCII, since
+//! truecolor, box drawing and Nerd Font glyphs can't be counted on over SSH
+//! or in the kernel's VT font
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+/// Whether hachi is running over SSH
+pub fn remote_session() -> bool {
+    ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// Whether the terminal can't be trusted with the full look: an SSH session
+/// or the Linux virtual console (`TERM=linux`)
+pub fn needs_safe_render() -> bool {
+    remote_session() || std::env::var_os("TERM").is_some_and(|term| term == "linux")
+}
+
+/// Rewrite every cell in `area` to 256-color indices and ASCII symbols
+pub fn downgrade(buf: &mut Buffer, area: Rect) {
+    let area = area.intersection(buf.area);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            cell.fg = to_indexed(cell.fg);
+            cell.bg = to_indexed(cell.bg);
+            if !cell.symbol().is_ascii() {
+                let ascii = cell.symbol().chars().next().map_or(' ', ascii_fallback);
+                cell.set_char(ascii);
+            }
+        }
+    }
+}
+
+/// Nearest xterm-256 color: the 6x6x6 cube or the 24-step grey ramp,
+/// whichever is closer
+pub fn to_indexed(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    let cube_index = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (v - 35) / 40,
+    };
+    let cube_level = |i: u8| if i == 0 { 0 } else { 55 + i * 40 };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = (cube_level(ri), cube_level(gi), cube_level(bi));
+
+    let average = (r as u16 + g as u16 + b as u16) / 3;
+    let grey_index = ((average.max(8) - 8 + 5) / 10).min(23) as u8;
+    let grey_level = 8 + grey_index * 10;
+
+    let distance = |(r2, g2, b2): (u8, u8, u8)| {
+        [(r, r2), (g, g2), (b, b2)]
+            .iter()
+            .map(|&(a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+    if distance((grey_level, grey_level, grey_level)) < distance(cube) {
+        Color::Indexed(232 + grey_index)
+    } else {
+        Color::Indexed(16 + 36 * ri + 6 * gi + bi)
+    }
+}
+
+/// ASCII stand-in for a non-ASCII glyph
+pub fn ascii_fallback(c: char) -> char {
+    match c {
+        // Box drawing
+        '─' | '━' | '═' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' | '╴' | '╶' | '╸' | '╺' | '▔' | '▁' => {
+            '-'
+        }
+        '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' | '╵' | '╷' | '╹' | '╻' | '▏' | '▕' => {
+            '|'
+        }
+        '╱' => '/',
+        '╲' => '\\',
+        '\u{2500}'..='\u{257F}' => '+',
+        // Shades and blocks
+        '░' => '.',
+        '▒' => ':',
+        '\u{2580}'..='\u{259F}' => '#',
+        // Braille dots (graphs and art)
+        '\u{2800}' => ' ',
+        '\u{2801}'..='\u{28FF}' => '.',
+        // Markers and arrows
+        '●' | '◉' | '◆' | '■' | '▮' | '❀' | '✿' | '❁' | '✾' | '❃' | '✤' => {
+            '*'
+        }
+        '○' | '◇' | '□' | '▯' | '◠' | '°' => 'o',
+        '▶' | '▷' | '►' | '▸' | '→' => '>',
+        '◀' | '◁' | '◄' | '←' => '<',
+        '▲' | '↑' | '◢' | '◣' => '^',
+        '▼' | '↓' => 'v',
+        '·' | '…' => '.',
+        '⟳' => '~',
+        '⚠' => '!',
+        '⚡' => '+',
+        // Superscript digits in panel titles
+        '⁰' => '0',
+        '¹' => '1',
+        '²' => '2',
+        '³' => '3',
+        '⁴'..='⁹' => char::from_digit(c as u32 - '⁰' as u32, 10).unwrap_or('?'),
+        _ => '?',
+    }
+}
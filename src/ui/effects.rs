@@ -9,9 +9,6 @@ use tachyonfx::{fx, Duration as FxDuration, Effect, Shader};
 
 use super::theme::colors;
 
-/// Sakura petal characters for particle effects
-const SAKURA_CHARS: [char; 6] = ['❀', '✿', '❁', '✾', '❃', '✤'];
-
 /// Create a cyberpunk glitch effect for mode transitions
 pub fn glitch_burst(duration_ms: u32) -> Effect {
     let quarter = duration_ms / 4;
@@ -81,6 +78,11 @@ pub fn power_surge(profile_color: Color) -> Effect {
     ])
 }
 
+/// Sweep a profile's color across the header, revealing the title behind it
+pub fn header_sweep(profile_color: Color) -> Effect {
+    fx::sweep_in(fx::Direction::LeftToRight, 15u16, 0u16, profile_color, 600u32)
+}
+
 /// Create charging animation for battery
 pub fn battery_charge_pulse(level: u8) -> Effect {
     let color = match level {
@@ -93,6 +95,23 @@ pub fn battery_charge_pulse(level: u8) -> Effect {
     fx::ping_pong(fx::fade_to_fg(color, 800u32))
 }
 
+/// Energy running along the katana blade toward the tip while charging:
+/// a lit front sweeps over the dimmed blade, again and again
+pub fn blade_energy_flow() -> Effect {
+    fx::repeating(fx::sweep_in(
+        fx::Direction::LeftToRight,
+        6u16,
+        0u16,
+        colors::STEEL_GRAY,
+        1200u32,
+    ))
+}
+
+/// Slow red pulse on the blade while the battery runs low
+pub fn blade_low_pulse() -> Effect {
+    fx::repeating(fx::ping_pong(fx::fade_to_fg(colors::RONIN_RED, 700u32)))
+}
+
 /// Create a "data stream" effect for fan curves
 pub fn data_stream() -> Effect {
     fx::sweep_in(
@@ -155,17 +174,42 @@ impl EffectManager {
         !self.effects.is_empty()
     }
 
+    /// Number of effects running
+    pub fn count(&self) -> usize {
+        self.effects.len()
+    }
+
     /// Trigger a glitch effect on profile change
     pub fn trigger_profile_glitch(&mut self, area: Rect, profile_color: Color) {
         self.add("profile_glitch", power_surge(profile_color), area);
         self.add("scan", scan_line(300), area);
     }
 
+    /// Trigger the header sweep announcing a new power profile
+    pub fn trigger_header_sweep(&mut self, area: Rect, profile_color: Color) {
+        self.add("header_sweep", header_sweep(profile_color), area);
+    }
+
     /// Trigger battery update effect
     pub fn trigger_battery_update(&mut self, area: Rect, level: u8) {
         self.add("battery_pulse", battery_charge_pulse(level), area);
     }
 
+    /// Run energy along the katana blade until `stop_blade` is called
+    pub fn trigger_blade_charging(&mut self, area: Rect) {
+        self.add("blade", blade_energy_flow(), area);
+    }
+
+    /// Pulse the katana blade red until `stop_blade` is called
+    pub fn trigger_blade_low(&mut self, area: Rect) {
+        self.add("blade", blade_low_pulse(), area);
+    }
+
+    /// End the blade's charging or low-battery animation
+    pub fn stop_blade(&mut self) {
+        self.remove("blade");
+    }
+
     /// Trigger border glow animation for focused panel
     pub fn trigger_border_glow(&mut self, name: &str, area: Rect, color: Color) {
         self.add(name, border_glow(color, 800), area);
@@ -182,96 +226,3 @@ impl Default for EffectManager {
         Self::new()
     }
 }
-
-/// Custom shader for rendering sakura particles in background
-pub struct SakuraShader {
-    particles: Vec<SakuraParticle>,
-    width: u16,
-    height: u16,
-}
-
-struct SakuraParticle {
-    x: f32,
-    y: f32,
-    char_idx: usize,
-    speed: f32,
-    drift: f32,
-    alpha: f32,
-}
-
-impl SakuraShader {
-    pub fn new(width: u16, height: u16, density: usize) -> Self {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-
-        let particles = (0..density)
-            .map(|_| SakuraParticle {
-                x: rng.gen_range(0.0..width as f32),
-                y: rng.gen_range(0.0..height as f32),
-                char_idx: rng.gen_range(0..SAKURA_CHARS.len()),
-                speed: rng.gen_range(0.1..0.4),
-                drift: rng.gen_range(-0.2..0.2),
-                alpha: rng.gen_range(0.3..1.0),
-            })
-            .collect();
-
-        Self {
-            particles,
-            width,
-            height,
-        }
-    }
-
-    /// Update particle positions
-    pub fn update(&mut self, delta: Duration) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let dt = delta.as_secs_f32();
-
-        for particle in &mut self.particles {
-            // Move down with drift
-            particle.y += particle.speed * dt * 10.0;
-            particle.x += particle.drift * dt * 5.0;
-
-            // Wrap around screen
-            if particle.y > self.height as f32 {
-                particle.y = -1.0;
-                particle.x = rng.gen_range(0.0..self.width as f32);
-                particle.alpha = rng.gen_range(0.3..1.0);
-            }
-            if particle.x < 0.0 {
-                particle.x = self.width as f32 - 1.0;
-            } else if particle.x >= self.width as f32 {
-                particle.x = 0.0;
-            }
-        }
-    }
-
-    /// Render particles to buffer
-    pub fn render(&self, buf: &mut Buffer, area: Rect) {
-        for particle in &self.particles {
-            let x = area.x + particle.x as u16;
-            let y = area.y + particle.y as u16;
-
-            if x < area.right() && y < area.bottom() && x >= area.x && y >= area.y {
-                let ch = SAKURA_CHARS[particle.char_idx];
-                // Vary pink based on alpha
-                let intensity = (particle.alpha * 255.0) as u8;
-                let color = Color::Rgb(255, intensity / 3, intensity / 2);
-
-                if let Some(cell) = buf.cell_mut((x, y)) {
-                    // Only render on empty/background cells
-                    if cell.symbol() == " " {
-                        cell.set_char(ch).set_fg(color);
-                    }
-                }
-            }
-        }
-    }
-
-    /// Resize the shader area
-    pub fn resize(&mut self, width: u16, height: u16) {
-        self.width = width;
-        self.height = height;
-    }
-}
@@ -1,8 +1,23 @@
+pub mod compat;
+#[cfg(feature = "effects")]
 pub mod effects;
 pub mod header_art;
+pub mod picker;
+#[cfg(feature = "particles")]
+mod sakura;
+#[cfg(not(all(feature = "effects", feature = "particles")))]
+mod stubs;
 pub mod theme;
 pub mod widgets;
 
-pub use effects::{EffectManager, SakuraShader};
+#[cfg(feature = "effects")]
+pub use effects::EffectManager;
+#[cfg(feature = "particles")]
+pub use sakura::SakuraShader;
+#[cfg(not(feature = "effects"))]
+pub use stubs::EffectManager;
+#[cfg(not(feature = "particles"))]
+pub use stubs::SakuraShader;
+pub use picker::{ColorPicker, PickerState};
 pub use theme::colors;
 pub use widgets::*;
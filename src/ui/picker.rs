@@ -0,0 +1,369 @@
+//! Color picker: hue, saturation and value sliders drawn as gradient bars,
+//! a hex field and a row of swatches
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Widget},
+};
+
+use crate::daemon::Rgb;
+use crate::ui::theme::{colors, styles};
+
+/// Ready-made colors on the swatch row
+pub const SWATCHES: [Rgb; 8] = [
+    Rgb::new(255, 0, 0),
+    Rgb::new(255, 128, 0),
+    Rgb::new(255, 255, 0),
+    Rgb::new(0, 255, 0),
+    Rgb::new(0, 255, 255),
+    Rgb::new(0, 0, 255),
+    Rgb::new(128, 0, 255),
+    Rgb::new(255, 255, 255),
+];
+
+/// Width of the row labels, e.g. `▸ Hue   `
+const LABEL_WIDTH: u16 = 8;
+
+/// Width of the value after each slider, e.g. ` 240°`
+const VALUE_WIDTH: u16 = 6;
+
+/// A color as hue in degrees and saturation and value in percent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hsv {
+    pub hue: u16,
+    pub saturation: u8,
+    pub value: u8,
+}
+
+impl Hsv {
+    pub fn from_rgb(rgb: Rgb) -> Self {
+        let [r, g, b] = [rgb.r, rgb.g, rgb.b].map(|c| c as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+        Self {
+            hue: hue.round() as u16 % 360,
+            saturation: (saturation * 100.0).round() as u8,
+            value: (max * 100.0).round() as u8,
+        }
+    }
+
+    pub fn to_rgb(self) -> Rgb {
+        let value = self.value.min(100) as f32 / 100.0;
+        let chroma = value * self.saturation.min(100) as f32 / 100.0;
+        let sector = (self.hue % 360) as f32 / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let channel = |c: f32| ((c + value - chroma) * 255.0).round() as u8;
+        Rgb::new(channel(r), channel(g), channel(b))
+    }
+}
+
+/// Rows of the picker, top to bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerRow {
+    Hue,
+    Saturation,
+    Value,
+    Hex,
+    Swatches,
+}
+
+impl PickerRow {
+    pub const ALL: [PickerRow; 5] = [
+        Self::Hue,
+        Self::Saturation,
+        Self::Value,
+        Self::Hex,
+        Self::Swatches,
+    ];
+
+    /// Full name for plain text, e.g. `saturation`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Hue => "hue",
+            Self::Saturation => "saturation",
+            Self::Value => "value",
+            Self::Hex => "hex",
+            Self::Swatches => "presets",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Hue => "Hue",
+            Self::Saturation => "Sat",
+            Self::Value => "Value",
+            Self::Hex => "Hex",
+            Self::Swatches => "Preset",
+        }
+    }
+}
+
+/// What the picker holds between frames
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickerState {
+    /// The color being picked: exact when typed or taken from a swatch,
+    /// otherwise derived from `hsv`
+    pub color: Rgb,
+    pub hsv: Hsv,
+    pub row: PickerRow,
+    /// Hex digits typed so far on the hex row
+    pub hex_input: String,
+    /// Swatch last taken
+    pub swatch: usize,
+}
+
+impl PickerState {
+    pub fn new(color: Rgb) -> Self {
+        Self {
+            color,
+            hsv: Hsv::from_rgb(color),
+            row: PickerRow::Hue,
+            hex_input: String::new(),
+            swatch: SWATCHES.iter().position(|&s| s == color).unwrap_or(0),
+        }
+    }
+
+    /// Move to the row above or below, dropping a half-typed hex value
+    pub fn select_row(&mut self, down: bool) {
+        let index = PickerRow::ALL.iter().position(|&row| row == self.row).unwrap_or(0);
+        let index = if down {
+            (index + 1).min(PickerRow::ALL.len() - 1)
+        } else {
+            index.saturating_sub(1)
+        };
+        self.row = PickerRow::ALL[index];
+        self.hex_input.clear();
+    }
+
+    /// Move the selected slider by `delta` (hue wraps around), or step
+    /// through the swatches
+    pub fn adjust(&mut self, delta: i16) {
+        let percent = |value: u8| (value as i16 + delta).clamp(0, 100) as u8;
+        let mut hsv = self.hsv;
+        match self.row {
+            PickerRow::Hue => hsv.hue = (hsv.hue as i16 + delta).rem_euclid(360) as u16,
+            PickerRow::Saturation => hsv.saturation = percent(hsv.saturation),
+            PickerRow::Value => hsv.value = percent(hsv.value),
+            PickerRow::Hex => return,
+            PickerRow::Swatches => {
+                let last = SWATCHES.len() as i16 - 1;
+                self.swatch = (self.swatch as i16 + delta.signum()).clamp(0, last) as usize;
+                self.set_color(SWATCHES[self.swatch]);
+                return;
+            }
+        }
+        self.hsv = hsv;
+        self.color = hsv.to_rgb();
+    }
+
+    /// Add a digit to the hex field, taking the color once all six are in.
+    /// Returns whether `c` was accepted.
+    pub fn type_hex(&mut self, c: char) -> bool {
+        if self.row != PickerRow::Hex || !c.is_ascii_hexdigit() {
+            return false;
+        }
+        self.hex_input.push(c.to_ascii_lowercase());
+        if self.hex_input.len() == 6 {
+            if let Some(color) = Rgb::parse(&self.hex_input) {
+                self.set_color(color);
+            }
+            self.hex_input.clear();
+        }
+        true
+    }
+
+    pub fn backspace(&mut self) {
+        self.hex_input.pop();
+    }
+
+    fn set_color(&mut self, color: Rgb) {
+        self.color = color;
+        self.hsv = Hsv::from_rgb(color);
+    }
+}
+
+fn tui(color: Rgb) -> Color {
+    Color::Rgb(color.r, color.g, color.b)
+}
+
+/// Picker popup for a `PickerState`
+pub struct ColorPicker<'a> {
+    state: &'a PickerState,
+    title: &'a str,
+}
+
+impl<'a> ColorPicker<'a> {
+    /// Rows the popup needs, borders included
+    pub const HEIGHT: u16 = 11;
+
+    pub fn new(state: &'a PickerState) -> Self {
+        Self {
+            state,
+            title: "color",
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// `▸ Hue   ` with the marker on the selected row
+    fn label(&self, row: PickerRow) -> Span<'static> {
+        let selected = row == self.state.row;
+        let text = format!("{} {:6}", if selected { "▸" } else { " " }, row.label());
+        let style = if selected {
+            styles::text_highlight()
+        } else {
+            styles::text()
+        };
+        Span::styled(text, style)
+    }
+
+    /// A slider as a gradient bar, with a marker at `position` (0.0-1.0)
+    fn slider(
+        &self,
+        row: PickerRow,
+        area: Rect,
+        buf: &mut Buffer,
+        position: f32,
+        value: String,
+        color_at: impl Fn(f32) -> Rgb,
+    ) {
+        buf.set_line(area.x, area.y, &Line::from(self.label(row)), LABEL_WIDTH);
+        let width = area.width.saturating_sub(LABEL_WIDTH + VALUE_WIDTH);
+        if width < 2 {
+            return;
+        }
+
+        let marker = (position * (width - 1) as f32).round() as u16;
+        for i in 0..width {
+            let color = color_at(i as f32 / (width - 1) as f32);
+            let Some(cell) = buf.cell_mut((area.x + LABEL_WIDTH + i, area.y)) else {
+                continue;
+            };
+            if i == marker {
+                let hsv = Hsv::from_rgb(color);
+                let contrast = if hsv.value > 60 && hsv.saturation < 60 {
+                    Color::Black
+                } else {
+                    Color::White
+                };
+                cell.set_char('◆').set_fg(contrast).set_bg(tui(color));
+            } else {
+                cell.set_char('█').set_fg(tui(color));
+            }
+        }
+        let value = Span::styled(format!(" {:>5}", value), styles::text_dim());
+        buf.set_span(area.x + LABEL_WIDTH + width, area.y, &value, VALUE_WIDTH);
+    }
+}
+
+impl Widget for ColorPicker<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let hint = Span::styled(" j/k row · h/l adjust · Enter apply ", styles::text_dim());
+        let block = Block::default()
+            .title(self.title)
+            .title_style(styles::title())
+            .title_bottom(Line::from(hint).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+        let inner = block.inner(area).inner(Margin::new(1, 1));
+        block.render(area, buf);
+        if inner.height < 7 || inner.width < LABEL_WIDTH + VALUE_WIDTH + 2 {
+            return;
+        }
+        let row = |n: u16| Rect {
+            y: inner.y + n,
+            height: 1,
+            ..inner
+        };
+
+        let state = self.state;
+        let preview = Line::from(vec![
+            Span::styled("  ██████████ ", Style::default().fg(tui(state.color))),
+            Span::styled(state.color.to_string(), styles::text()),
+        ]);
+        buf.set_line(inner.x, inner.y, &preview, inner.width);
+
+        let hsv = state.hsv;
+        self.slider(
+            PickerRow::Hue,
+            row(2),
+            buf,
+            hsv.hue as f32 / 359.0,
+            format!("{}°", hsv.hue),
+            |at| {
+                let hue = (at * 359.0).round() as u16;
+                Hsv { hue, ..hsv }.to_rgb()
+            },
+        );
+        self.slider(
+            PickerRow::Saturation,
+            row(3),
+            buf,
+            hsv.saturation as f32 / 100.0,
+            format!("{}%", hsv.saturation),
+            |at| {
+                let saturation = (at * 100.0).round() as u8;
+                Hsv { saturation, ..hsv }.to_rgb()
+            },
+        );
+        self.slider(
+            PickerRow::Value,
+            row(4),
+            buf,
+            hsv.value as f32 / 100.0,
+            format!("{}%", hsv.value),
+            |at| {
+                let value = (at * 100.0).round() as u8;
+                Hsv { value, ..hsv }.to_rgb()
+            },
+        );
+
+        // Typed digits with a cursor, or the current color
+        let mut hex = vec![self.label(PickerRow::Hex)];
+        if state.row == PickerRow::Hex {
+            hex.push(Span::styled(format!("#{}", state.hex_input), styles::text_highlight()));
+            hex.push(Span::styled("_", styles::text_highlight()));
+        } else {
+            hex.push(Span::styled(state.color.to_string(), styles::text()));
+        }
+        buf.set_line(inner.x, inner.y + 5, &Line::from(hex), inner.width);
+
+        let mut swatches = vec![self.label(PickerRow::Swatches)];
+        for (i, &swatch) in SWATCHES.iter().enumerate() {
+            let taken = state.row == PickerRow::Swatches && i == state.swatch;
+            let (open, close) = if taken { ("[", "]") } else { (" ", " ") };
+            swatches.push(Span::styled(open, styles::text_highlight()));
+            swatches.push(Span::styled("██", Style::default().fg(tui(swatch))));
+            swatches.push(Span::styled(close, styles::text_highlight()));
+        }
+        buf.set_line(inner.x, inner.y + 6, &Line::from(swatches), inner.width);
+    }
+}
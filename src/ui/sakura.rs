@@ -0,0 +1,122 @@
+//! Sakura petals drifting behind the panels (the `particles` feature)
+
+use std::time::Duration;
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+use super::theme::colors;
+
+/// Sakura petal characters for particle effects
+const SAKURA_CHARS: [char; 6] = ['❀', '✿', '❁', '✾', '❃', '✤'];
+
+/// Custom shader for rendering sakura particles in background
+pub struct SakuraShader {
+    particles: Vec<SakuraParticle>,
+    width: u16,
+    height: u16,
+}
+
+struct SakuraParticle {
+    x: f32,
+    y: f32,
+    char_idx: usize,
+    speed: f32,
+    drift: f32,
+    alpha: f32,
+}
+
+impl SakuraParticle {
+    /// Cell the particle is drawn in
+    fn cell(&self) -> (i32, i32) {
+        (self.x.floor() as i32, self.y.floor() as i32)
+    }
+}
+
+impl SakuraShader {
+    pub fn new(width: u16, height: u16, density: usize) -> Self {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+
+        let particles = (0..density)
+            .map(|_| SakuraParticle {
+                x: rng.gen_range(0.0..width as f32),
+                y: rng.gen_range(0.0..height as f32),
+                char_idx: rng.gen_range(0..SAKURA_CHARS.len()),
+                speed: rng.gen_range(0.1..0.4),
+                drift: rng.gen_range(-0.2..0.2),
+                alpha: rng.gen_range(0.3..1.0),
+            })
+            .collect();
+
+        Self {
+            particles,
+            width,
+            height,
+        }
+    }
+
+    pub fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Update particle positions. Returns whether any particle moved to a
+    /// different cell; sub-cell movement doesn't change the screen.
+    pub fn update(&mut self, delta: Duration) -> bool {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let dt = delta.as_secs_f32();
+        let mut moved = false;
+
+        for particle in &mut self.particles {
+            let cell = particle.cell();
+            // Move down with drift
+            particle.y += particle.speed * dt * 10.0;
+            particle.x += particle.drift * dt * 5.0;
+
+            // Wrap around screen
+            if particle.y > self.height as f32 {
+                particle.y = -1.0;
+                particle.x = rng.gen_range(0.0..self.width as f32);
+                particle.alpha = rng.gen_range(0.3..1.0);
+            }
+            if particle.x < 0.0 {
+                particle.x = self.width as f32 - 1.0;
+            } else if particle.x >= self.width as f32 {
+                particle.x = 0.0;
+            }
+
+            moved |= particle.cell() != cell;
+        }
+        moved
+    }
+
+    /// Render particles into background cells of an already drawn buffer.
+    /// Cells holding widget content or a non-background fill count as
+    /// opaque, so particles pass behind them.
+    pub fn render(&self, buf: &mut Buffer, area: Rect) {
+        for particle in &self.particles {
+            let x = area.x + particle.x as u16;
+            let y = area.y + particle.y as u16;
+
+            if x < area.right() && y < area.bottom() && x >= area.x && y >= area.y {
+                let ch = SAKURA_CHARS[particle.char_idx];
+                // Vary pink based on alpha
+                let intensity = (particle.alpha * 255.0) as u8;
+                let color = Color::Rgb(255, intensity / 3, intensity / 2);
+
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    // Only render on empty/background cells
+                    if cell.symbol() == " " && cell.bg == colors::VOID_BLACK {
+                        cell.set_char(ch).set_fg(color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resize the shader area
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.width = width;
+        self.height = height;
+    }
+}
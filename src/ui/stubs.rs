@@ -0,0 +1,82 @@
+//! Stand-ins for the visual extras left out of a lean build: the same API
+//! as `EffectManager` and `SakuraShader`, drawing nothing
+
+#[cfg(not(feature = "effects"))]
+pub use no_effects::EffectManager;
+#[cfg(not(feature = "particles"))]
+pub use no_particles::SakuraShader;
+
+#[cfg(not(feature = "effects"))]
+mod no_effects {
+    use std::time::Duration;
+
+    use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+    /// Built without the `effects` feature: effects are never started
+    #[derive(Default)]
+    pub struct EffectManager;
+
+    impl EffectManager {
+        pub fn new() -> Self {
+            Self
+        }
+
+        pub fn remove(&mut self, _name: &str) {}
+
+        pub fn clear(&mut self) {}
+
+        pub fn process(&mut self, _duration: Duration, _buf: &mut Buffer, _area: Rect) {}
+
+        pub fn has_active_effects(&self) -> bool {
+            false
+        }
+
+        pub fn count(&self) -> usize {
+            0
+        }
+
+        pub fn trigger_profile_glitch(&mut self, _area: Rect, _profile_color: Color) {}
+
+        pub fn trigger_header_sweep(&mut self, _area: Rect, _profile_color: Color) {}
+
+        pub fn trigger_battery_update(&mut self, _area: Rect, _level: u8) {}
+
+        pub fn trigger_blade_charging(&mut self, _area: Rect) {}
+
+        pub fn trigger_blade_low(&mut self, _area: Rect) {}
+
+        pub fn stop_blade(&mut self) {}
+
+        pub fn trigger_border_glow(&mut self, _name: &str, _area: Rect, _color: Color) {}
+
+        pub fn trigger_border_cycle(&mut self, _name: &str, _area: Rect) {}
+    }
+}
+
+#[cfg(not(feature = "particles"))]
+mod no_particles {
+    use std::time::Duration;
+
+    use ratatui::{buffer::Buffer, layout::Rect};
+
+    /// Built without the `particles` feature: no petals
+    pub struct SakuraShader;
+
+    impl SakuraShader {
+        pub fn new(_width: u16, _height: u16, _density: usize) -> Self {
+            Self
+        }
+
+        pub fn particle_count(&self) -> usize {
+            0
+        }
+
+        pub fn update(&mut self, _delta: Duration) -> bool {
+            false
+        }
+
+        pub fn render(&self, _buf: &mut Buffer, _area: Rect) {}
+
+        pub fn resize(&mut self, _width: u16, _height: u16) {}
+    }
+}
@@ -86,6 +86,18 @@ pub mod styles {
             .add_modifier(Modifier::BOLD)
     }
 
+    /// Border style (hover) - panel under the mouse cursor
+    pub fn border_hover() -> Style {
+        Style::default().fg(GHOST_WHITE)
+    }
+
+    /// Border style (alert) - hardware problem needs attention
+    pub fn border_alert() -> Style {
+        Style::default()
+            .fg(RONIN_RED)
+            .add_modifier(Modifier::BOLD)
+    }
+
     /// Background style
     pub fn background() -> Style {
         Style::default().bg(VOID_BLACK)
@@ -184,6 +196,14 @@ pub const BEE_LOGO: &str = r#"      ╱◠◠◠◠╲
     ◢╱      ╲◣
    ◢╱        ╲◣"#;
 
+/// Oni mask, an alternative header logo
+pub const ONI_MASK: &str = r#" ▲          ▲
+ ██▄▄▄▄▄▄▄▄██
+ █  ◣    ◢  █
+ █   ▄▄▄▄   █
+ ▀█ ▼▼▼▼▼▼ █▀
+   ▀▀▀▀▀▀▀▀"#;
+
 /// Compact Bee for smaller displays
 pub const BEE_COMPACT: &str = r#"
   ┏━━━━━━━━━┓
@@ -219,6 +239,16 @@ pub fn charge_level_style(level: u8) -> Style {
     }
 }
 
+/// Get the appropriate style for a load ratio (0.0 - 1.0)
+pub fn load_level_style(load: f32) -> Style {
+    match (load.clamp(0.0, 1.0) * 100.0) as u8 {
+        0..=25 => Style::default().fg(colors::NEON_CYAN),
+        26..=50 => Style::default().fg(colors::BALANCE_BLUE),
+        51..=75 => Style::default().fg(colors::EMBER_ORANGE),
+        _ => Style::default().fg(colors::RONIN_RED),
+    }
+}
+
 /// Get profile-specific color
 pub fn profile_color(profile: &crate::daemon::PowerProfile) -> Color {
     match profile {
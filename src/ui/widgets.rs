@@ -1,14 +1,35 @@
+use std::time::Duration;
+
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style, Stylize},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Paragraph, Widget},
+    widgets::{
+        Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Widget,
+    },
 };
 
-use crate::daemon::{FanCurve, PowerProfile};
+use crate::app::{Message, Severity};
+use crate::config::{FanTempSource, HeaderArt, TemperatureUnit};
+use crate::console::Console;
+use crate::daemon::{
+    AuraState, Backend, DisconnectReason, Fan, FanCurve, HardwareState, PowerLimits,
+    PowerProfile, SlashState, ValueRange,
+};
+use crate::health::{Check, CheckStatus};
+use crate::stats::format_duration;
+use crate::sysfs::Backlight;
+use crate::telemetry::{LimitPreview, Telemetry, TimeEstimate};
+use crate::tune::Trace;
 use crate::ui::header_art::HACHI_BIG_TEXT;
-use crate::ui::theme::{colors, profile_styles, styles};
+use crate::ui::theme::{
+    colors, load_level_style, profile_color, profile_styles, styles, BEE_LOGO, HACHI_BANNER, ONI_MASK,
+};
+
+/// Eighth-block glyphs for compact vertical bars, lowest to highest
+const BAR_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
 /// Power profile selector widget
 pub struct PowerProfileSelector<'a> {
@@ -16,6 +37,40 @@ pub struct PowerProfileSelector<'a> {
     selected: usize,
     focused: bool,
     title: &'a str,
+    /// Power limits each profile applies (in `PowerProfile::ALL` order),
+    /// shown instead of the generic description once known
+    limits: [Option<PowerLimits>; 3],
+    /// Fastest fan speed seen under each profile, in RPM
+    fans: [Option<u32>; 3],
+    /// The firmware's names for the profiles
+    firmware: [Option<&'a str>; 3],
+}
+
+/// What this laptop does under a profile, from its firmware name, power
+/// limits and loudest fan speed seen, e.g. `low-power · 35W sustained ·
+/// fans ≤2900 rpm`; `None` until something is known
+pub fn profile_details(
+    profile: PowerProfile,
+    firmware: Option<&str>,
+    limits: Option<PowerLimits>,
+    fan_peak: Option<u32>,
+) -> Option<String> {
+    let mut parts = Vec::new();
+    // Only names that say more than the profile's own
+    if let Some(name) = firmware.filter(|name| !name.eq_ignore_ascii_case(profile.as_str())) {
+        parts.push(name.to_string());
+    }
+    match limits {
+        Some(PowerLimits { sustained, boost: Some(boost) }) => {
+            parts.push(format!("{}W boost · {}W sustained", boost, sustained))
+        }
+        Some(limits) => parts.push(format!("{}W sustained", limits.sustained)),
+        None => {}
+    }
+    if let Some(rpm) = fan_peak {
+        parts.push(format!("fans ≤{} rpm", rpm));
+    }
+    (!parts.is_empty()).then(|| parts.join(" · "))
 }
 
 impl<'a> PowerProfileSelector<'a> {
@@ -25,9 +80,27 @@ impl<'a> PowerProfileSelector<'a> {
             selected: current.to_u8() as usize,
             focused: false,
             title: " Power Profile ",
+            limits: [None; 3],
+            fans: [None; 3],
+            firmware: [None; 3],
         }
     }
 
+    pub fn limits(mut self, limits: [Option<PowerLimits>; 3]) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn fans(mut self, fans: [Option<u32>; 3]) -> Self {
+        self.fans = fans;
+        self
+    }
+
+    pub fn firmware(mut self, firmware: [Option<&'a str>; 3]) -> Self {
+        self.firmware = firmware;
+        self
+    }
+
     pub fn selected(mut self, selected: usize) -> Self {
         self.selected = selected;
         self
@@ -121,10 +194,12 @@ impl Widget for PowerProfileSelector<'_> {
                 power_indicator,
             ]);
 
+            let desc = profile_details(*profile, self.firmware[i], self.limits[i], self.fans[i])
+                .unwrap_or_else(|| desc.to_string());
             let desc_line = Line::from(vec![
                 Span::raw("      "),
                 Span::styled(
-                    *desc,
+                    desc,
                     if is_active {
                         profile_style
                     } else {
@@ -148,6 +223,10 @@ impl Widget for PowerProfileSelector<'_> {
 /// Battery Katana widget - sword-shaped battery indicator
 pub struct BatteryKatana {
     charge_limit: u8,
+    bypass: Option<bool>,
+    charging: bool,
+    estimate: Option<TimeEstimate>,
+    preview: Option<LimitPreview>,
     focused: bool,
     editing: bool,
 }
@@ -156,11 +235,50 @@ impl BatteryKatana {
     pub fn new(charge_limit: u8) -> Self {
         Self {
             charge_limit,
+            bypass: None,
+            charging: false,
+            estimate: None,
+            preview: None,
             focused: false,
             editing: false,
         }
     }
 
+    /// Blade row (body and tip) inside a panel rendered at `area`, for
+    /// effects that run along it
+    pub fn blade_area(area: Rect) -> Option<Rect> {
+        let inner = area.inner(Margin::new(1, 1));
+        if inner.height < 3 || inner.width < 20 {
+            return None;
+        }
+        Some(Rect::new(inner.x + 4, inner.y + 1, inner.width - 5, 1))
+    }
+
+    /// Show the lightning glyph while the battery is charging
+    pub fn charging(mut self, charging: bool) -> Self {
+        self.charging = charging;
+        self
+    }
+
+    /// Time to empty or full, shown under the blade
+    pub fn estimate(mut self, estimate: Option<TimeEstimate>) -> Self {
+        self.estimate = estimate;
+        self
+    }
+
+    /// What the limit being edited trades off, shown under the blade in
+    /// place of the time estimate
+    pub fn preview(mut self, preview: Option<LimitPreview>) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Bypass charging state (`None` hides the toggle)
+    pub fn bypass(mut self, bypass: Option<bool>) -> Self {
+        self.bypass = bypass;
+        self
+    }
+
     pub fn focused(mut self, focused: bool) -> Self {
         self.focused = focused;
         self
@@ -222,6 +340,13 @@ impl Widget for BatteryKatana {
                 Span::styled(format!("{}%", self.charge_limit), limit_style),
             ])
         };
+        let label = if self.charging {
+            let mut spans = label.spans;
+            spans.push(Span::styled("  ⚡", Style::default().fg(colors::EMBER_ORANGE).bold()));
+            Line::from(spans)
+        } else {
+            label
+        };
         buf.set_line(chunks[0].x, chunks[0].y, &label, chunks[0].width);
 
         // Katana blade visualization with enhanced graphics
@@ -247,12 +372,50 @@ impl Widget for BatteryKatana {
 
         buf.set_line(chunks[1].x, chunks[1].y, &blade_line, chunks[1].width);
 
+        let below_blade = match (self.preview, self.estimate) {
+            (Some(preview), _) => Some(Line::from(Span::styled(
+                format!("    {}", preview.label()),
+                styles::text_dim(),
+            ))),
+            (None, Some(estimate)) => {
+                let until = if estimate.to_full { "to full" } else { "to empty" };
+                Some(Line::from(vec![
+                    Span::styled(format!("    {}", estimate.label()), styles::text()),
+                    Span::styled(format!(" {}", until), styles::text_dim()),
+                ]))
+            }
+            (None, None) => None,
+        };
+        if let (Some(line), true) = (below_blade, chunks[1].height >= 2) {
+            buf.set_line(chunks[1].x, chunks[1].y + 1, &line, chunks[1].width);
+        }
+
         // Scale markers with tick marks
         let scale = "   0%        25%        50%        75%       100%";
         let scale_line = Line::from(Span::styled(scale, styles::text_dim()));
         if chunks[2].width > scale.len() as u16 {
             buf.set_line(chunks[2].x, chunks[2].y, &scale_line, chunks[2].width);
         }
+
+        // Bypass charging toggle (only on supported firmware)
+        if let Some(enabled) = self.bypass {
+            if chunks[3].height >= 1 {
+                let (indicator, state_style) = if enabled {
+                    ("● ON ", Style::default().fg(colors::NEON_CYAN).bold())
+                } else {
+                    ("○ off", styles::text_dim())
+                };
+                let mut spans = vec![
+                    Span::styled("  Bypass: ", styles::text()),
+                    Span::styled(indicator, state_style),
+                ];
+                if self.focused && !self.editing {
+                    spans.push(Span::styled("  [b]", styles::text_highlight()));
+                    spans.push(Span::styled(" toggle", styles::text_dim()));
+                }
+                buf.set_line(chunks[3].x, chunks[3].y, &Line::from(spans), chunks[3].width);
+            }
+        }
     }
 }
 
@@ -262,6 +425,18 @@ pub struct FanCurveGraph<'a> {
     selected_point: Option<usize>,
     focused: bool,
     editing: bool,
+    unit: TemperatureUnit,
+    alert: bool,
+    /// Sensor the x axis and current point follow
+    source: FanTempSource,
+    /// Degrees the target speed lags behind on cooling
+    hysteresis: u8,
+    /// Current temperature and the speed the curve asks for
+    target: Option<(f32, u8)>,
+    /// Cell of the fan/profile switch matrix being chosen
+    selected_switch: Option<(PowerProfile, Fan)>,
+    /// Curve copy being set up, e.g. `CPU → GPU, speeds -10%`
+    copying: Option<String>,
 }
 
 impl<'a> FanCurveGraph<'a> {
@@ -271,9 +446,52 @@ impl<'a> FanCurveGraph<'a> {
             selected_point: None,
             focused: false,
             editing: false,
+            unit: TemperatureUnit::default(),
+            alert: false,
+            source: FanTempSource::default(),
+            hysteresis: 0,
+            target: None,
+            selected_switch: None,
+            copying: None,
         }
     }
 
+    pub fn copying(mut self, copying: Option<String>) -> Self {
+        self.copying = copying;
+        self
+    }
+
+    pub fn selected_switch(mut self, switch: Option<(PowerProfile, Fan)>) -> Self {
+        self.selected_switch = switch;
+        self
+    }
+
+    pub fn hysteresis(mut self, degrees: u8) -> Self {
+        self.hysteresis = degrees;
+        self
+    }
+
+    pub fn source(mut self, source: FanTempSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn target(mut self, target: Option<(f32, u8)>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Highlight the border in red (e.g. fan stall detected)
+    pub fn alert(mut self, alert: bool) -> Self {
+        self.alert = alert;
+        self
+    }
+
+    pub fn unit(mut self, unit: TemperatureUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
     pub fn selected_point(mut self, point: Option<usize>) -> Self {
         self.selected_point = point;
         self
@@ -288,11 +506,67 @@ impl<'a> FanCurveGraph<'a> {
         self.editing = editing;
         self
     }
+
+    /// Which fans follow their custom curve in which profile, as a small
+    /// grid in the top left corner of the graph:
+    ///
+    /// ```text
+    ///     Q B P
+    /// CPU ○ ● ●
+    /// GPU ○ ○ ●
+    /// ```
+    fn render_switches(&self, x: u16, y: u16, buf: &mut Buffer) {
+        let mut header = String::from("   ");
+        for profile in PowerProfile::ALL {
+            header.push(' ');
+            header.extend(profile.as_str().chars().next());
+        }
+        buf.set_string(x, y, header, styles::text_dim());
+
+        for (row, fan) in Fan::ALL.into_iter().enumerate() {
+            let y = y + 1 + row as u16;
+            buf.set_string(x, y, fan.as_str(), styles::text_dim());
+            for (column, profile) in PowerProfile::ALL.into_iter().enumerate() {
+                let on = self.curve.enablement.get(profile, fan);
+                let (symbol, style) = if self.selected_switch == Some((profile, fan)) {
+                    (if on { "◆" } else { "◇" }, styles::graph_point_editing())
+                } else if on {
+                    ("●", Style::default().fg(colors::NEON_CYAN))
+                } else {
+                    ("○", Style::default().fg(colors::STEEL_GRAY))
+                };
+                buf.set_string(x + 4 + 2 * column as u16, y, symbol, style);
+            }
+        }
+    }
+
+    /// Curve point drawn closest to a screen column when the graph is
+    /// rendered into `area`, or `None` when it's too small for a graph
+    pub fn point_at(curve: &FanCurve, area: Rect, column: u16) -> Option<usize> {
+        let inner = area.inner(Margin::new(1, 1));
+        if inner.height < 8 || inner.width < 30 {
+            return None;
+        }
+        let graph_x = inner.x + 6;
+        let graph_width = inner.width.saturating_sub(7) as f32;
+
+        curve
+            .cpu_curve
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, point)| {
+                let x_ratio = (point.temp.saturating_sub(30) as f32) / 70.0;
+                (graph_x + (graph_width * x_ratio) as u16).abs_diff(column)
+            })
+            .map(|(index, _)| index)
+    }
 }
 
 impl Widget for FanCurveGraph<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let border_style = if self.editing {
+        let border_style = if self.alert {
+            styles::border_alert()
+        } else if self.editing {
             styles::border_active()
         } else if self.focused {
             styles::border_focused()
@@ -306,14 +580,22 @@ impl Widget for FanCurveGraph<'_> {
             Span::styled("○ Disabled", Style::default().fg(colors::STEEL_GRAY))
         };
 
-        let block = Block::default()
+        let source = Span::styled(format!(" {} temp ", self.source.as_str()), styles::text_dim());
+        let mut block = Block::default()
             .title("³fan")
             .title_style(styles::title())
+            .title(Line::from(source).right_aligned())
             .title_bottom(Line::from(status).right_aligned())
             .borders(Borders::ALL)
             .border_type(BorderType::Thick)
             .border_style(border_style);
 
+        if self.hysteresis > 0 {
+            let hysteresis = self.unit.format_delta(self.hysteresis as f32);
+            let label = Span::styled(format!(" ±{} hysteresis ", hysteresis), styles::text_dim());
+            block = block.title_bottom(Line::from(label).left_aligned());
+        }
+
         let inner = block.inner(area);
         block.render(area, buf);
 
@@ -348,12 +630,12 @@ impl Widget for FanCurveGraph<'_> {
             buf.set_string(inner.x + 4, y, "╴", styles::text_dim());
         }
 
-        // X-axis labels (temperature °C)
-        let x_labels = ["30°", "50°", "70°", "90°"];
-        for (i, label) in x_labels.iter().enumerate() {
+        // X-axis labels (temperature, in the configured unit)
+        let x_temps = [30.0, 50.0, 70.0, 90.0];
+        for (i, celsius) in x_temps.iter().enumerate() {
             let x = graph_area.x + (graph_width as u16 * i as u16 / 3);
             let y = inner.y + inner.height - 2;
-            buf.set_string(x, y, label, styles::text_dim());
+            buf.set_string(x, y, self.unit.format(*celsius), styles::text_dim());
         }
 
         // Collect points for curve drawing
@@ -370,6 +652,20 @@ impl Widget for FanCurveGraph<'_> {
             draw_smooth_curve(buf, &points, &graph_area, self.focused || self.editing);
         }
 
+        // Per fan and profile switches, where the curve rarely reaches
+        self.render_switches(graph_area.x + 1, graph_area.y, buf);
+
+        // Where the fan is on the curve right now
+        if let Some((celsius, speed)) = self.target {
+            let x_ratio = (celsius.clamp(30.0, 100.0) - 30.0) / 70.0;
+            let x = graph_area.x + (graph_area.width as f32 * x_ratio) as u16;
+            let y_ratio = 1.0 - (speed.min(100) as f32 / 100.0);
+            let y = graph_area.y + (graph_area.height as f32 * y_ratio) as u16;
+            if x < graph_area.right() && y < graph_area.bottom() {
+                buf.set_string(x, y, "✛", styles::text_highlight());
+            }
+        }
+
         // Draw control points on top of the curve (larger, more visible)
         for (i, point) in self.curve.cpu_curve.iter().enumerate() {
             let x_ratio = (point.temp.saturating_sub(30) as f32) / 70.0;
@@ -394,7 +690,7 @@ impl Widget for FanCurveGraph<'_> {
 
                 // Draw point value label for selected point
                 if self.selected_point == Some(i) {
-                    let label = format!("{}°:{}%", point.temp, point.speed);
+                    let label = format!("{}:{}%", self.unit.format(point.temp as f32), point.speed);
                     let label_x = if x + label.len() as u16 + 2 < graph_area.right() {
                         x + 2
                     } else {
@@ -418,6 +714,25 @@ impl Widget for FanCurveGraph<'_> {
                 Span::styled("[Enter]", styles::text_highlight()),
                 Span::styled(" Confirm", styles::text_dim()),
             ])
+        } else if let Some(copying) = self.copying {
+            Line::from(vec![
+                Span::styled(format!("Copy {}  ", copying), styles::text_highlight()),
+                Span::styled("[←→]", styles::text_highlight()),
+                Span::styled(" To  ", styles::text_dim()),
+                Span::styled("[↑↓]", styles::text_highlight()),
+                Span::styled(" Speeds  ", styles::text_dim()),
+                Span::styled("[Enter]", styles::text_highlight()),
+                Span::styled(" Copy", styles::text_dim()),
+            ])
+        } else if self.selected_switch.is_some() {
+            Line::from(vec![
+                Span::styled("[←→]", styles::text_highlight()),
+                Span::styled(" Profile  ", styles::text_dim()),
+                Span::styled("[↑↓]", styles::text_highlight()),
+                Span::styled(" Fan  ", styles::text_dim()),
+                Span::styled("[Space]", styles::text_highlight()),
+                Span::styled(" Switch", styles::text_dim()),
+            ])
         } else if self.focused {
             Line::from(vec![
                 Span::styled("[Enter]", styles::text_highlight()),
@@ -468,23 +783,7 @@ fn draw_smooth_curve(buf: &mut Buffer, points: &[(f32, f32)], area: &Rect, is_ac
     let (start_r, start_g, start_b) = if is_active { (60, 220, 255) } else { (60, 180, 200) };
     let (end_r, end_g, end_b) = if is_active { (255, 60, 120) } else { (200, 60, 100) };
 
-    // Generate interpolated points using Catmull-Rom splines
-    let mut curve_points: Vec<(f32, f32)> = Vec::new();
-
-    for i in 0..points.len() - 1 {
-        let p0 = if i == 0 { points[0] } else { points[i - 1] };
-        let p1 = points[i];
-        let p2 = points[i + 1];
-        let p3 = if i + 2 < points.len() { points[i + 2] } else { points[i + 1] };
-
-        // Generate points along the spline segment
-        let steps = ((p2.0 - p1.0).abs() as usize).max(10);
-        for step in 0..=steps {
-            let t = step as f32 / steps as f32;
-            let point = catmull_rom(p0, p1, p2, p3, t);
-            curve_points.push(point);
-        }
-    }
+    let curve_points = spline_points(points, area);
 
     // Draw the curve with gradient coloring and thick characters
     let total_points = curve_points.len();
@@ -503,8 +802,33 @@ fn draw_smooth_curve(buf: &mut Buffer, points: &[(f32, f32)], area: &Rect, is_ac
     }
 }
 
+/// Points along a Catmull-Rom spline through `points`, kept inside
+/// `area`: the spline overshoots where the curve changes direction, and an
+/// overshoot above the graph would otherwise be drawn along its top edge
+pub fn spline_points(points: &[(f32, f32)], area: &Rect) -> Vec<(f32, f32)> {
+    let right = area.right().saturating_sub(1).max(area.x) as f32;
+    let bottom = area.bottom().saturating_sub(1).max(area.y) as f32;
+    let mut curve_points: Vec<(f32, f32)> = Vec::new();
+
+    for i in 0..points.len().saturating_sub(1) {
+        let p0 = if i == 0 { points[0] } else { points[i - 1] };
+        let p1 = points[i];
+        let p2 = points[i + 1];
+        let p3 = if i + 2 < points.len() { points[i + 2] } else { points[i + 1] };
+
+        // Generate points along the spline segment
+        let steps = ((p2.0 - p1.0).abs() as usize).max(10);
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            let (x, y) = catmull_rom(p0, p1, p2, p3, t);
+            curve_points.push((x.clamp(area.x as f32, right), y.clamp(area.y as f32, bottom)));
+        }
+    }
+    curve_points
+}
+
 /// Catmull-Rom spline interpolation for smooth curves
-fn catmull_rom(p0: (f32, f32), p1: (f32, f32), p2: (f32, f32), p3: (f32, f32), t: f32) -> (f32, f32) {
+pub fn catmull_rom(p0: (f32, f32), p1: (f32, f32), p2: (f32, f32), p3: (f32, f32), t: f32) -> (f32, f32) {
     let t2 = t * t;
     let t3 = t2 * t;
 
@@ -577,157 +901,1596 @@ fn draw_thick_line(buf: &mut Buffer, x0: u16, y0: u16, x1: u16, y1: u16, color:
     }
 }
 
-/// Status bar widget showing connection status and errors
-pub struct StatusBar<'a> {
-    connected: bool,
-    message: Option<&'a str>,
+/// Lighting panel - keyboard brightness and color, backlight idle timeout and display
+/// brightness
+pub struct LightingPanel<'a> {
+    aura: Option<&'a AuraState>,
+    backlight: Option<&'a Backlight>,
+    focused: bool,
+    editing: bool,
+    reactive: bool,
 }
 
-impl<'a> StatusBar<'a> {
-    pub fn new(connected: bool) -> Self {
+impl<'a> LightingPanel<'a> {
+    pub fn new(aura: Option<&'a AuraState>) -> Self {
         Self {
-            connected,
-            message: None,
+            aura,
+            backlight: None,
+            focused: false,
+            editing: false,
+            reactive: false,
         }
     }
 
-    pub fn message(mut self, msg: &'a str) -> Self {
-        self.message = Some(msg);
+    /// Mark the color as following the CPU temperature
+    pub fn reactive(mut self, reactive: bool) -> Self {
+        self.reactive = reactive;
         self
     }
-}
-
-impl Widget for StatusBar<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // Background
-        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
-
-        // Connection status
-        let (status_icon, status_style) = if self.connected {
-            ("● Connected", Style::default().fg(colors::NEON_CYAN))
-        } else {
-            ("○ Disconnected", Style::default().fg(colors::RONIN_RED))
-        };
-
-        buf.set_string(area.x + 1, area.y, status_icon, status_style);
-
-        // Message (if any)
-        if let Some(msg) = self.message {
-            let msg_x = area.x + 20;
-            let style = if msg.contains("Error") {
-                styles::text_error()
-            } else {
-                styles::text_warning()
-            };
-            let available_width = area.width.saturating_sub(21) as usize;
-            let truncated = if msg.len() > available_width {
-                format!("{}...", &msg[..available_width.saturating_sub(3)])
-            } else {
-                msg.to_string()
-            };
-            buf.set_string(msg_x, area.y, &truncated, style);
-        }
 
-        // Keybinds hint on right
-        let hint = " q: quit  s: sakura  tab: cycle  ?: help ";
-        let hint_x = area.right().saturating_sub(hint.len() as u16 + 1);
-        buf.set_string(hint_x, area.y, hint, styles::text_dim());
+    /// Display backlight shown below the keyboard settings
+    pub fn backlight(mut self, backlight: Option<&'a Backlight>) -> Self {
+        self.backlight = backlight;
+        self
     }
-}
-
-/// Header widget with Oni logo and title
-pub struct Header {
-    compact: bool,
-}
 
-impl Header {
-    pub fn new() -> Self {
-        Self { compact: false }
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
     }
 
-    pub fn compact(mut self, compact: bool) -> Self {
-        self.compact = compact;
+    pub fn editing(mut self, editing: bool) -> Self {
+        self.editing = editing;
         self
     }
-}
 
-impl Default for Header {
-    fn default() -> Self {
-        Self::new()
+    fn render_keyboard(&self, aura: &AuraState, inner: Rect, buf: &mut Buffer) {
+        // Brightness as a 3-step meter
+        let level = aura.brightness.unwrap_or_default();
+        let meter: String = (1..=3)
+            .map(|i| if level.to_u32() >= i { '▮' } else { '▯' })
+            .collect();
+        let mut brightness = vec![
+            Span::styled("  Brightness: ", styles::text()),
+            Span::styled(meter, Style::default().fg(colors::SAKURA_PINK).bold()),
+            Span::styled(format!(" {}", level), styles::text_dim()),
+        ];
+        if let Some(color) = aura.color {
+            let swatch = Style::default().fg(Color::Rgb(color.r, color.g, color.b));
+            brightness.push(Span::styled("  ██", swatch));
+            brightness.push(Span::styled(format!(" {}", color), styles::text_dim()));
+        }
+        if self.reactive {
+            brightness.push(Span::styled(" temp", Style::default().fg(colors::EMBER_ORANGE)));
+        }
+        let brightness = Line::from(brightness);
+        buf.set_line(inner.x, inner.y, &brightness, inner.width);
+
+        if inner.height < 2 {
+            return;
+        }
+
+        // Idle timeout
+        let mut timeout = vec![Span::styled("  Idle off:   ", styles::text())];
+        match aura.idle_timeout {
+            Some(seconds) => {
+                let style = if self.editing {
+                    styles::text_highlight()
+                } else {
+                    Style::default().fg(colors::NEON_CYAN)
+                };
+                timeout.push(Span::styled(format_timeout(seconds), style));
+                if self.editing {
+                    timeout.push(Span::styled("  [←/→]", styles::text_highlight()));
+                    timeout.push(Span::styled(" adjust", styles::text_dim()));
+                } else if self.focused {
+                    timeout.push(Span::styled("  [Enter]", styles::text_highlight()));
+                    timeout.push(Span::styled(" edit", styles::text_dim()));
+                }
+            }
+            None => timeout.push(Span::styled("unsupported", styles::text_dim())),
+        }
+        buf.set_line(inner.x, inner.y + 1, &Line::from(timeout), inner.width);
     }
 }
 
-impl Widget for Header {
+impl Widget for LightingPanel<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.height < 2 || area.width < 40 {
-            return;
-        }
+        let border_style = if self.editing {
+            styles::border_active()
+        } else if self.focused {
+            styles::border_focused()
+        } else {
+            styles::border()
+        };
 
-        // Simple header with title on the left
-        let left_padding = 3u16;
-        let text_x = area.x + left_padding;
+        let block = Block::default()
+            .title("⁴lighting")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
 
-        let big_text_height = HACHI_BIG_TEXT.len() as u16;
-        // Center the block text vertically in the header area
-        let title_y = area.y + (area.height.saturating_sub(big_text_height)) / 2;
+        if inner.height < 1 || inner.width < 20 {
+            return;
+        }
 
-        // Gradient start/end colors: Cyan -> Pink
-        let (r1, g1, b1) = (60, 203, 225);  // Neon Cyan
-        let (r2, g2, b2) = (255, 0, 85);    // Sakura Pink
+        // Keyboard rows, or a single placeholder row without an Aura device
+        let display_y = match self.aura {
+            Some(aura) => {
+                self.render_keyboard(aura, inner, buf);
+                inner.y + 2
+            }
+            None => {
+                buf.set_string(inner.x + 2, inner.y, "No Aura keyboard", styles::text_dim());
+                inner.y + 1
+            }
+        };
 
-        // Render Big Text with gradient
-        for (row, line) in HACHI_BIG_TEXT.iter().enumerate() {
-            let y = title_y + row as u16;
-            if y >= area.y + area.height { break; }
-
-            let line_len = line.chars().count();
-            for (col, ch) in line.chars().enumerate() {
-                let x = text_x + col as u16;
-                if x >= area.x + area.width { break; }
-
-                if ch != ' ' {
-                    // Linear interpolation for gradient based on column
-                    let t = col as f32 / line_len as f32;
-                    let r = (r1 as f32 * (1.0 - t) + r2 as f32 * t) as u8;
-                    let g = (g1 as f32 * (1.0 - t) + g2 as f32 * t) as u8;
-                    let b = (b1 as f32 * (1.0 - t) + b2 as f32 * t) as u8;
-
-                    if let Some(cell) = buf.cell_mut((x, y)) {
-                        cell.set_char(ch);
-                        cell.set_fg(Color::Rgb(r, g, b));
-                    }
+        // Display backlight gauge
+        if let Some(backlight) = self.backlight {
+            if display_y < inner.bottom() {
+                let percent = backlight.percent();
+                let width = 10;
+                let filled = (percent as usize * width).div_ceil(100);
+                let mut spans = vec![
+                    Span::styled("  Display:    ", styles::text()),
+                    Span::styled("█".repeat(filled), Style::default().fg(colors::NEON_CYAN)),
+                    Span::styled("░".repeat(width - filled), styles::text_dim()),
+                    Span::styled(format!(" {}%", percent), styles::text()),
+                ];
+                if self.focused {
+                    spans.push(Span::styled("  [+/-]", styles::text_highlight()));
                 }
+                buf.set_line(inner.x, display_y, &Line::from(spans), inner.width);
             }
         }
+    }
+}
 
-        // Render subtitle to the right of the big text, vertically centered
-        let subtitle_x = text_x + 40; // After the HACHI text
-        let subtitle_y = area.y + area.height / 2; // Center vertically
+/// Advanced panel - less common hardware switches grouped by section
+pub struct AdvancedPanel<'a> {
+    /// (name, state) rows of the Peripherals section, `None` = unavailable
+    peripherals: &'a [(&'a str, Option<bool>)],
+    selected: usize,
+    focused: bool,
+}
 
-        if subtitle_x < area.x + area.width && subtitle_y < area.y + area.height {
-            let subtitle = Line::from(vec![
-                Span::styled("蜂 ", Style::default().fg(Color::Rgb(255, 200, 50)).add_modifier(Modifier::BOLD)),
-                Span::styled("ASUS ROG Control Center", styles::text_dim()),
-            ]);
-            buf.set_line(subtitle_x, subtitle_y, &subtitle, area.width.saturating_sub(subtitle_x));
+impl<'a> AdvancedPanel<'a> {
+    pub fn new(peripherals: &'a [(&'a str, Option<bool>)]) -> Self {
+        Self {
+            peripherals,
+            selected: 0,
+            focused: false,
         }
     }
-}
 
-/// Help popup widget
-pub struct HelpPopup;
+    pub fn selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
 
-impl Widget for HelpPopup {
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+impl Widget for AdvancedPanel<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        // Semi-transparent background
-        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+        let border_style = if self.focused {
+            styles::border_focused()
+        } else {
+            styles::border()
+        };
 
         let block = Block::default()
-            .title("⁴help")
+            .title("⁵advanced")
             .title_style(styles::title())
             .borders(Borders::ALL)
             .border_type(BorderType::Thick)
-            .border_style(styles::border_focused());
+            .border_style(border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 1 || inner.width < 20 {
+            return;
+        }
+
+        buf.set_string(inner.x + 1, inner.y, "Peripherals", styles::text_dim());
+
+        let rows = Rect {
+            y: inner.y + 1,
+            height: inner.height - 1,
+            ..inner
+        };
+        let selected = self.focused.then_some(self.selected);
+        render_toggle_rows(self.peripherals, selected, 10, rows, buf);
+    }
+}
+
+/// Presets panel - saved setting bundles, with an input line while one is
+/// being named
+pub struct PresetsPanel<'a> {
+    names: &'a [&'a str],
+    selected: usize,
+    focused: bool,
+    /// Name typed so far when creating or renaming
+    input: Option<&'a str>,
+}
+
+impl<'a> PresetsPanel<'a> {
+    pub fn new(names: &'a [&'a str]) -> Self {
+        Self {
+            names,
+            selected: 0,
+            focused: false,
+            input: None,
+        }
+    }
+
+    pub fn selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn input(mut self, input: Option<&'a str>) -> Self {
+        self.input = input;
+        self
+    }
+}
+
+impl Widget for PresetsPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.input.is_some() {
+            styles::border_active()
+        } else if self.focused {
+            styles::border_focused()
+        } else {
+            styles::border()
+        };
+
+        let mut block = Block::default()
+            .title("⁸presets")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(border_style);
+        if self.focused {
+            let hints = Span::styled(" n new · m rename · d delete ", styles::text_dim());
+            block = block.title_bottom(Line::from(hints).right_aligned());
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 1 || inner.width < 20 {
+            return;
+        }
+
+        // The input line takes the last row
+        let mut rows = inner;
+        if let Some(input) = self.input {
+            rows.height -= 1;
+            let line = Line::from(vec![
+                Span::styled(" Name: ", styles::text_dim()),
+                Span::styled(format!("{}▏", input), styles::text_highlight()),
+            ]);
+            buf.set_line(inner.x, inner.bottom() - 1, &line, inner.width);
+        }
+
+        if self.names.is_empty() {
+            if rows.height > 0 {
+                let hint = " No presets yet: n saves the current settings";
+                buf.set_stringn(rows.x, rows.y, hint, rows.width as usize, styles::text_dim());
+            }
+            return;
+        }
+
+        // Scroll to keep the selection in view
+        let offset = self.selected.saturating_sub(rows.height.saturating_sub(1) as usize);
+        for (row, (i, name)) in self.names.iter().enumerate().skip(offset).enumerate() {
+            if row as u16 >= rows.height {
+                break;
+            }
+            let is_selected = self.focused && self.selected == i;
+            let mut spans = vec![
+                Span::styled(
+                    format!(" {} ", if is_selected { "▶" } else { " " }),
+                    styles::text_highlight(),
+                ),
+                Span::styled(*name, styles::text()),
+            ];
+            if is_selected && self.input.is_none() {
+                spans.push(Span::styled("  [Enter]", styles::text_highlight()));
+                spans.push(Span::styled(" apply", styles::text_dim()));
+            }
+            buf.set_line(rows.x, rows.y + row as u16, &Line::from(spans), rows.width);
+        }
+    }
+}
+
+/// AniMe Matrix panel - whether the display is shown in each system state
+pub struct AnimePanel<'a> {
+    /// (state name, shown) rows, `None` = `AnimeState` is unavailable
+    bindings: Option<&'a [(&'a str, Option<bool>)]>,
+    selected: usize,
+    focused: bool,
+}
+
+impl<'a> AnimePanel<'a> {
+    pub fn new(bindings: Option<&'a [(&'a str, Option<bool>)]>) -> Self {
+        Self {
+            bindings,
+            selected: 0,
+            focused: false,
+        }
+    }
+
+    pub fn selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+impl Widget for AnimePanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            styles::border_focused()
+        } else {
+            styles::border()
+        };
+
+        let block = Block::default()
+            .title("⁶anime")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 1 || inner.width < 20 {
+            return;
+        }
+
+        let Some(bindings) = self.bindings else {
+            buf.set_string(inner.x + 2, inner.y, "No AniMe Matrix", styles::text_dim());
+            return;
+        };
+
+        let selected = self.focused.then_some(self.selected);
+        render_toggle_rows(bindings, selected, 14, inner, buf);
+    }
+}
+
+/// Slash lid lighting panel - enable switch, pattern, brightness and interval
+pub struct SlashPanel<'a> {
+    slash: &'a SlashState,
+    selected: usize,
+    focused: bool,
+}
+
+impl<'a> SlashPanel<'a> {
+    pub fn new(slash: &'a SlashState) -> Self {
+        Self {
+            slash,
+            selected: 0,
+            focused: false,
+        }
+    }
+
+    pub fn selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+impl Widget for SlashPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            styles::border_focused()
+        } else {
+            styles::border()
+        };
+
+        let block = Block::default()
+            .title("⁶slash")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 1 || inner.width < 20 {
+            return;
+        }
+
+        let selected = self.focused.then_some(self.selected);
+        render_toggle_rows(&[("Enabled", self.slash.enabled)], selected, 12, inner, buf);
+
+        let value = Style::default().fg(colors::NEON_CYAN);
+        let rows = [
+            ("Pattern", self.slash.mode.as_ref().map(|mode| format!("◀ {} ▶", mode))),
+            (
+                "Brightness",
+                self.slash.brightness.map(|b| format!("{}%", b as u32 * 100 / 255)),
+            ),
+            ("Interval", self.slash.interval.map(|i| i.to_string())),
+        ];
+        for (i, (name, text)) in rows.into_iter().enumerate() {
+            let row = i + 1;
+            let y = inner.y + row as u16;
+            if y >= inner.bottom() {
+                break;
+            }
+
+            let is_selected = selected == Some(row);
+            let mut spans = vec![
+                Span::styled(
+                    format!(" {} ", if is_selected { "▶" } else { " " }),
+                    styles::text_highlight(),
+                ),
+                Span::styled(format!("{:<12}", name), styles::text()),
+            ];
+            match text {
+                Some(text) => {
+                    spans.push(Span::styled(text, value));
+                    if is_selected {
+                        spans.push(Span::styled("  [←/→]", styles::text_highlight()));
+                        spans.push(Span::styled(" adjust", styles::text_dim()));
+                    }
+                }
+                None => spans.push(Span::styled("unavailable", styles::text_dim())),
+            }
+            buf.set_line(inner.x, y, &Line::from(spans), inner.width);
+        }
+    }
+}
+
+/// TDP quick presets - large buttons for gamepad navigation on handhelds
+pub struct TdpPresets<'a> {
+    presets: &'a [u8],
+    current: Option<u8>,
+    range: Option<ValueRange>,
+    selected: usize,
+    focused: bool,
+}
+
+impl<'a> TdpPresets<'a> {
+    pub fn new(presets: &'a [u8], current: Option<u8>) -> Self {
+        Self {
+            presets,
+            current,
+            range: None,
+            selected: 0,
+            focused: false,
+        }
+    }
+
+    /// Firmware power limit range; presets outside it are shown clamped
+    pub fn range(mut self, range: Option<ValueRange>) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+impl Widget for TdpPresets<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            styles::border_focused()
+        } else {
+            styles::border()
+        };
+
+        let block = Block::default()
+            .title("tdp")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 3 || inner.width < 20 || self.presets.is_empty() {
+            return;
+        }
+
+        if self.current.is_none() {
+            buf.set_string(inner.x + 2, inner.y + 1, "Power limits unavailable", styles::text_dim());
+            return;
+        }
+
+        let buttons = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, self.presets.len() as u32); self.presets.len()])
+            .split(inner);
+
+        for (i, (&watts, button)) in self.presets.iter().zip(buttons.iter()).enumerate() {
+            let watts = self.range.map_or(watts, |range| range.clamp(watts));
+            let is_selected = self.focused && self.selected == i;
+            let is_active = self.current == Some(watts);
+
+            let style = if is_active {
+                Style::default().fg(colors::NEON_CYAN).bold()
+            } else if is_selected {
+                styles::text_highlight()
+            } else {
+                styles::text_dim()
+            };
+            let border = if is_selected {
+                styles::border_active()
+            } else if is_active {
+                styles::border_focused()
+            } else {
+                styles::border()
+            };
+
+            let button_block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(if is_selected { BorderType::Thick } else { BorderType::Rounded })
+                .border_style(border);
+            let label = Paragraph::new(format!("{}W", watts))
+                .style(style)
+                .alignment(Alignment::Center)
+                .block(button_block);
+            label.render(*button, buf);
+        }
+    }
+}
+
+/// Render `(name, state)` switch rows, one per line; `None` states are unavailable
+fn render_toggle_rows(
+    rows: &[(&str, Option<bool>)],
+    selected: Option<usize>,
+    name_width: usize,
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    for (i, (name, state)) in rows.iter().enumerate() {
+        let y = area.y + i as u16;
+        if y >= area.bottom() {
+            break;
+        }
+
+        let is_selected = selected == Some(i);
+        let bracket = if is_selected { "▶" } else { " " };
+        let (indicator, state_style) = match state {
+            Some(true) => ("● ON ", Style::default().fg(colors::NEON_CYAN).bold()),
+            Some(false) => ("○ off", styles::text_dim()),
+            None => ("unavailable", styles::text_dim()),
+        };
+
+        let mut spans = vec![
+            Span::styled(format!(" {} ", bracket), styles::text_highlight()),
+            Span::styled(format!("{:<width$}", name, width = name_width), styles::text()),
+            Span::styled(indicator, state_style),
+        ];
+        if is_selected && state.is_some() {
+            spans.push(Span::styled("  [Enter]", styles::text_highlight()));
+            spans.push(Span::styled(" toggle", styles::text_dim()));
+        }
+        buf.set_line(area.x, y, &Line::from(spans), area.width);
+    }
+}
+
+/// Format an idle timeout in seconds (`never`, `45s`, `2m`, `2m 30s`)
+pub fn format_timeout(seconds: u32) -> String {
+    match (seconds / 60, seconds % 60) {
+        (0, 0) => "never".to_string(),
+        (0, s) => format!("{}s", s),
+        (m, 0) => format!("{}m", m),
+        (m, s) => format!("{}m {}s", m, s),
+    }
+}
+
+/// Telemetry panel - per-core load/frequency bars, memory gauges, temperatures
+/// and a CPU temperature trend with throttling episodes marked
+pub struct TelemetryPanel<'a> {
+    telemetry: &'a Telemetry,
+    hidden_sensors: &'a [String],
+    unit: TemperatureUnit,
+}
+
+impl<'a> TelemetryPanel<'a> {
+    pub fn new(telemetry: &'a Telemetry) -> Self {
+        Self {
+            telemetry,
+            hidden_sensors: &[],
+            unit: TemperatureUnit::default(),
+        }
+    }
+
+    pub fn unit(mut self, unit: TemperatureUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn hidden_sensors(mut self, hidden: &'a [String]) -> Self {
+        self.hidden_sensors = hidden;
+        self
+    }
+}
+
+impl Widget for TelemetryPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("telemetry")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border());
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 1 || inner.width < 20 {
+            return;
+        }
+
+        let cores = &self.telemetry.cores;
+        if cores.is_empty() {
+            buf.set_string(inner.x + 2, inner.y, "No CPU data", styles::text_dim());
+            return;
+        }
+
+        // Label (7) + summary (8) reserved, one cell per core for the rest
+        let max_bars = inner.width.saturating_sub(15) as usize;
+        let shown = &cores[..cores.len().min(max_bars)];
+
+        // Per-core utilization bars
+        let avg_usage = self.telemetry.average_usage();
+        let mut load_spans = vec![Span::styled("  load ", styles::text_dim())];
+        load_spans.extend(shown.iter().map(|core| {
+            Span::styled(bar_glyph(core.usage).to_string(), load_level_style(core.usage))
+        }));
+        load_spans.push(Span::styled(
+            format!(" {:>3.0}%", avg_usage * 100.0),
+            load_level_style(avg_usage).add_modifier(Modifier::BOLD),
+        ));
+        buf.set_line(inner.x, inner.y, &Line::from(load_spans), inner.width);
+
+        // Per-core frequency bars, scaled to each core's max frequency
+        if inner.height >= 2 {
+            let mut freq_spans = vec![Span::styled("  freq ", styles::text_dim())];
+            freq_spans.extend(shown.iter().map(|core| {
+                let ratio = match (core.freq_mhz, core.max_freq_mhz) {
+                    (Some(cur), Some(max)) if max > 0 => cur as f32 / max as f32,
+                    _ => 0.0,
+                };
+                Span::styled(bar_glyph(ratio).to_string(), Style::default().fg(colors::SAKURA_PINK))
+            }));
+            let summary = match self.telemetry.average_freq_mhz() {
+                Some(mhz) => format!(" {:.1}GHz", mhz as f32 / 1000.0),
+                None => " --".to_string(),
+            };
+            freq_spans.push(Span::styled(summary, styles::text()));
+            buf.set_line(inner.x, inner.y + 1, &Line::from(freq_spans), inner.width);
+        }
+
+        // RAM and swap gauges
+        let memory = &self.telemetry.memory;
+        let gauges = [
+            ("  mem  ", memory.used_ratio(), memory.used_kb(), memory.total_kb),
+            ("  swap ", memory.swap_used_ratio(), memory.swap_used_kb(), memory.swap_total_kb),
+        ];
+        for (row, (label, ratio, used, total)) in gauges.into_iter().enumerate() {
+            let y = inner.y + 2 + row as u16;
+            if y >= inner.bottom() || total == 0 {
+                continue;
+            }
+            let summary = format!(" {:.1}/{:.1}G", kib_to_gib(used), kib_to_gib(total));
+            let gauge_width = inner.width.saturating_sub(7 + summary.len() as u16) as usize;
+            let filled = (gauge_width as f32 * ratio).round() as usize;
+            let line = Line::from(vec![
+                Span::styled(label, styles::text_dim()),
+                Span::styled("━".repeat(filled), load_level_style(ratio)),
+                Span::styled("╌".repeat(gauge_width.saturating_sub(filled)), styles::text_dim()),
+                Span::styled(summary, styles::text()),
+            ]);
+            buf.set_line(inner.x, y, &line, inner.width);
+        }
+
+        // Temperature readouts (NVMe, EC and other hwmon sensors)
+        let temp_y = inner.y + 4;
+        if temp_y < inner.bottom() {
+            let mut spans = vec![Span::styled("  temp ", styles::text_dim())];
+            for (i, sensor) in self.telemetry.visible_sensors(self.hidden_sensors).enumerate() {
+                if i > 0 {
+                    spans.push(Span::styled(" · ", styles::text_dim()));
+                }
+                spans.push(Span::styled(sensor.display_name(), styles::text()));
+                spans.push(Span::styled(
+                    format!(" {}", self.unit.format(sensor.celsius)),
+                    temp_style(sensor.celsius).add_modifier(Modifier::BOLD),
+                ));
+            }
+            if spans.len() == 1 {
+                spans.push(Span::styled("No sensors", styles::text_dim()));
+            }
+            buf.set_line(inner.x, temp_y, &Line::from(spans), inner.width);
+        }
+
+        // CPU temperature trend, newest on the right, throttled samples marked
+        let trend_y = inner.y + 5;
+        if trend_y < inner.bottom() {
+            let throttle = &self.telemetry.throttle;
+            let (counter, counter_style) = match throttle.episodes {
+                0 => (" no throttling".to_string(), styles::text_dim()),
+                n if throttle.throttling() => {
+                    (format!(" throttling {}×", n), styles::text_error())
+                }
+                n => (format!(" throttled {}×", n), styles::text_warning()),
+            };
+            let width = inner.width.saturating_sub(7 + counter.chars().count() as u16) as usize;
+            let skip = throttle.trend.len().saturating_sub(width);
+
+            let mut spans = vec![Span::styled("  trend", styles::text_dim())];
+            spans.extend(throttle.trend.iter().skip(skip).map(|sample| {
+                let glyph = match sample.celsius {
+                    Some(celsius) => bar_glyph((celsius - 30.0) / 70.0),
+                    None => ' ',
+                };
+                let style = match (sample.throttled, sample.celsius) {
+                    (true, _) => Style::default().fg(colors::VOID_BLACK).bg(colors::RONIN_RED),
+                    (false, Some(celsius)) => temp_style(celsius),
+                    (false, None) => styles::text_dim(),
+                };
+                Span::styled(glyph.to_string(), style)
+            }));
+            let pad = width.saturating_sub(throttle.trend.len());
+            spans.push(Span::raw(" ".repeat(pad)));
+            spans.push(Span::styled(counter, counter_style));
+            buf.set_line(inner.x, trend_y, &Line::from(spans), inner.width);
+        }
+    }
+}
+
+/// Color a temperature reading (°C) by severity
+fn temp_style(celsius: f32) -> Style {
+    load_level_style((celsius - 30.0) / 70.0)
+}
+
+fn kib_to_gib(kib: u64) -> f32 {
+    kib as f32 / (1024.0 * 1024.0)
+}
+
+/// Pick the eighth-block glyph for a ratio (0.0 - 1.0)
+fn bar_glyph(ratio: f32) -> char {
+    let idx = (ratio.clamp(0.0, 1.0) * (BAR_LEVELS.len() - 1) as f32).round() as usize;
+    BAR_LEVELS[idx]
+}
+
+/// At-a-glance summary of the most important state. The superscript digits
+/// are the keys that open the matching detail panel.
+pub struct Dashboard<'a> {
+    state: &'a HardwareState,
+    telemetry: &'a Telemetry,
+    unit: TemperatureUnit,
+}
+
+impl<'a> Dashboard<'a> {
+    pub fn new(state: &'a HardwareState, telemetry: &'a Telemetry) -> Self {
+        Self {
+            state,
+            telemetry,
+            unit: TemperatureUnit::default(),
+        }
+    }
+
+    pub fn unit(mut self, unit: TemperatureUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+}
+
+impl Widget for Dashboard<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("⁰dashboard")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let missing = || Span::styled("--", styles::text_dim());
+        let temp = |celsius: Option<f32>| match celsius {
+            Some(c) => Span::styled(self.unit.format(c), styles::text()),
+            None => missing(),
+        };
+
+        let profile = if self.state.connected {
+            let style = match self.state.power_profile {
+                PowerProfile::Quiet => profile_styles::quiet(),
+                PowerProfile::Balanced => profile_styles::balanced(),
+                PowerProfile::Performance => profile_styles::performance(),
+            };
+            vec![Span::styled(self.state.power_profile.to_string(), style)]
+        } else {
+            vec![missing()]
+        };
+
+        let mut battery = match &self.telemetry.battery {
+            Some(info) => vec![
+                Span::styled(
+                    format!("{}%", info.capacity),
+                    crate::ui::theme::charge_level_style(info.capacity).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" {}", info.status.to_lowercase()), styles::text_dim()),
+            ],
+            None => vec![missing()],
+        };
+        battery.push(Span::styled(
+            format!("  limit {}%", self.state.charge_limit),
+            styles::text(),
+        ));
+
+        let temps = vec![
+            Span::styled("cpu ", styles::text_dim()),
+            temp(self.telemetry.cpu_temp()),
+            Span::styled("  gpu ", styles::text_dim()),
+            temp(self.telemetry.gpu_temp()),
+        ];
+
+        let fans = if self.telemetry.fans.is_empty() {
+            vec![missing()]
+        } else {
+            self.telemetry
+                .fans
+                .iter()
+                .enumerate()
+                .flat_map(|(i, fan)| {
+                    let separator = if i == 0 { "" } else { "  " };
+                    [
+                        Span::styled(format!("{}{} ", separator, fan.label.to_lowercase()), styles::text_dim()),
+                        Span::styled(format!("{} RPM", fan.rpm), styles::text()),
+                    ]
+                })
+                .collect()
+        };
+
+        let gpu_mode = match self.state.gpu_mode {
+            Some(mode) => vec![Span::styled(mode.as_str(), styles::text())],
+            None => vec![missing()],
+        };
+
+        let rows = [
+            ("¹ profile", profile),
+            ("² battery", battery),
+            ("  temps", temps),
+            ("³ fans", fans),
+            ("  gpu mode", gpu_mode),
+        ];
+
+        for (i, (label, value)) in rows.into_iter().enumerate() {
+            let y = inner.y + 1 + i as u16 * 2;
+            if y >= inner.bottom() {
+                break;
+            }
+            let mut spans = vec![Span::styled(format!("  {:<12}", label), styles::text_highlight())];
+            spans.extend(value);
+            buf.set_line(inner.x, y, &Line::from(spans), inner.width);
+        }
+    }
+}
+
+/// One-row strip of views with their hotkeys
+pub struct TabBar<'a> {
+    tabs: &'a [(&'a str, &'a str)],
+    selected: usize,
+}
+
+impl<'a> TabBar<'a> {
+    /// Tabs as (hotkey, title) pairs
+    pub fn new(tabs: &'a [(&'a str, &'a str)]) -> Self {
+        Self { tabs, selected: 0 }
+    }
+
+    pub fn selected(mut self, index: usize) -> Self {
+        self.selected = index;
+        self
+    }
+}
+
+impl Widget for TabBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![Span::raw(" ")];
+        for (i, (key, title)) in self.tabs.iter().enumerate() {
+            let style = if i == self.selected {
+                styles::selected()
+            } else {
+                styles::text_dim()
+            };
+            spans.push(Span::styled(format!(" {} ", title), style));
+            spans.push(Span::styled(format!("{}  ", key), styles::text_dim()));
+        }
+        buf.set_line(area.x, area.y, &Line::from(spans), area.width);
+    }
+}
+
+/// Local time, uptime and time on the current power source, right-aligned
+/// in the tab bar row. The power source, then the uptime, are left out when
+/// they'd take more than half the row.
+pub struct Clock<'a> {
+    time: &'a str,
+    uptime: Option<Duration>,
+    /// On battery, and for how long if a change was seen
+    power: Option<(bool, Option<Duration>)>,
+}
+
+impl<'a> Clock<'a> {
+    /// `time` as shown, e.g. `14:32`
+    pub fn new(time: &'a str) -> Self {
+        Self {
+            time,
+            uptime: None,
+            power: None,
+        }
+    }
+
+    pub fn uptime(mut self, uptime: Option<Duration>) -> Self {
+        self.uptime = uptime;
+        self
+    }
+
+    pub fn power(mut self, on_battery: Option<bool>, since: Option<Duration>) -> Self {
+        self.power = on_battery.map(|on_battery| (on_battery, since));
+        self
+    }
+}
+
+impl Widget for Clock<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut parts = Vec::new();
+        if let Some((on_battery, since)) = self.power {
+            let source = if on_battery { "on battery" } else { "on AC" };
+            let part = match since {
+                Some(since) => format!("{} {}", source, format_duration(since)),
+                None => source.to_string(),
+            };
+            let style = if on_battery { colors::EMBER_ORANGE } else { colors::STEEL_GRAY };
+            parts.push((part, Style::default().fg(style)));
+        }
+        if let Some(uptime) = self.uptime {
+            parts.push((format!("up {}", format_duration(uptime)), styles::text_dim()));
+        }
+        parts.push((self.time.to_string(), styles::text()));
+
+        let width = |parts: &[(String, Style)]| {
+            parts.iter().map(|(text, _)| text.chars().count() + 3).sum::<usize>()
+        };
+        while parts.len() > 1 && width(&parts) > area.width as usize / 2 {
+            parts.remove(0);
+        }
+
+        let mut spans = Vec::new();
+        for (i, (text, style)) in parts.into_iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" · ", styles::text_dim()));
+            }
+            spans.push(Span::styled(text, style));
+        }
+        spans.push(Span::raw(" "));
+        let line = Line::from(spans);
+        let width = (line.width() as u16).min(area.width);
+        buf.set_line(area.right() - width, area.y, &line, width);
+    }
+}
+
+/// Braille spinner frames for operations waiting on asusd
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Status bar widget showing connection status and errors
+pub struct StatusBar<'a> {
+    connected: bool,
+    backend: Backend,
+    disconnect_reason: Option<DisconnectReason>,
+    message: Option<(&'a str, Severity)>,
+    tooltip: Option<&'a str>,
+    /// Key hints on the right, most important first
+    hints: &'a [(&'a str, &'a str)],
+    recording: bool,
+    macro_register: Option<char>,
+    retrying: bool,
+    read_only: bool,
+    /// Time left on a full blast fan burst
+    full_blast: Option<Duration>,
+    /// Fan speed cap while quiet hours are in effect
+    fan_cap: Option<u8>,
+    /// How long a stress run for tuning has been going
+    tuning: Option<Duration>,
+    /// How long a state refresh has been waiting for asusd
+    refreshing: Option<Duration>,
+}
+
+impl<'a> StatusBar<'a> {
+    pub fn new(connected: bool) -> Self {
+        Self {
+            connected,
+            backend: Backend::Asusd,
+            disconnect_reason: None,
+            message: None,
+            tooltip: None,
+            hints: &[("?", "help"), ("q", "quit")],
+            recording: false,
+            macro_register: None,
+            retrying: false,
+            read_only: false,
+            full_blast: None,
+            fan_cap: None,
+            tuning: None,
+            refreshing: None,
+        }
+    }
+
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub fn hints(mut self, hints: &'a [(&'a str, &'a str)]) -> Self {
+        self.hints = hints;
+        self
+    }
+
+    /// Shown in place of "Disconnected" when known
+    pub fn disconnect_reason(mut self, reason: Option<DisconnectReason>) -> Self {
+        self.disconnect_reason = reason;
+        self
+    }
+
+    pub fn recording(mut self, recording: bool) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// Register a keyboard macro is being recorded into
+    pub fn macro_register(mut self, register: Option<char>) -> Self {
+        self.macro_register = register;
+        self
+    }
+
+    /// Some hardware writes failed and are being retried
+    pub fn retrying(mut self, retrying: bool) -> Self {
+        self.retrying = retrying;
+        self
+    }
+
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    pub fn full_blast(mut self, remaining: Option<Duration>) -> Self {
+        self.full_blast = remaining;
+        self
+    }
+
+    pub fn refreshing(mut self, elapsed: Option<Duration>) -> Self {
+        self.refreshing = elapsed;
+        self
+    }
+
+    pub fn tuning(mut self, elapsed: Option<Duration>) -> Self {
+        self.tuning = elapsed;
+        self
+    }
+
+    pub fn fan_cap(mut self, cap: Option<u8>) -> Self {
+        self.fan_cap = cap;
+        self
+    }
+
+    pub fn message(mut self, msg: &'a str, severity: Severity) -> Self {
+        self.message = Some((msg, severity));
+        self
+    }
+
+    /// What a click under the mouse cursor would do, shown in place of
+    /// anything but an error
+    pub fn tooltip(mut self, tooltip: Option<&'a str>) -> Self {
+        self.tooltip = tooltip;
+        self
+    }
+}
+
+impl Widget for StatusBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Background
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        // Connection status
+        let (status_icon, status_style) = if self.connected && self.backend == Backend::Kernel {
+            ("● Kernel".to_string(), Style::default().fg(colors::NEON_CYAN))
+        } else if self.connected {
+            ("● Connected".to_string(), Style::default().fg(colors::NEON_CYAN))
+        } else {
+            let label = self.disconnect_reason.map_or("Disconnected", DisconnectReason::label);
+            (format!("○ {}", label), Style::default().fg(colors::RONIN_RED))
+        };
+
+        buf.set_string(area.x + 1, area.y, &status_icon, status_style);
+
+        // Tooltip or message (if any)
+        let text = match (self.message, self.tooltip) {
+            (Some((msg, Severity::Error | Severity::Critical)), _) => {
+                Some((msg, styles::text_error()))
+            }
+            (_, Some(tooltip)) => Some((tooltip, styles::text())),
+            (Some((msg, _)), None) => Some((msg, styles::text_warning())),
+            (None, None) => None,
+        };
+        if let Some((msg, style)) = text {
+            let msg_x = area.x + 20;
+            let available_width = area.width.saturating_sub(21) as usize;
+            let truncated = if msg.len() > available_width {
+                format!("{}...", &msg[..available_width.saturating_sub(3)])
+            } else {
+                msg.to_string()
+            };
+            buf.set_string(msg_x, area.y, &truncated, style);
+        }
+
+        // Keybinds hint on right
+        // Keep the hints to half the bar, dropping the least important
+        let mut hint = String::new();
+        for (key, action) in self.hints {
+            let next = format!("{}{}: {}  ", hint, key, action);
+            if next.chars().count() > area.width as usize / 2 {
+                break;
+            }
+            hint = next;
+        }
+        let hint = format!(" {}", hint.trim_end());
+        let hint_x = area.right().saturating_sub(hint.chars().count() as u16 + 1);
+        buf.set_string(hint_x, area.y, &hint, styles::text_dim());
+
+        // Badges right to left, just left of the hints
+        let mut badge_x = hint_x;
+        if self.recording {
+            let badge = "● REC";
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            buf.set_string(badge_x, area.y, badge, styles::text_error());
+        }
+        if let Some(register) = self.macro_register {
+            let badge = format!("● @{}", register);
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            buf.set_string(badge_x, area.y, &badge, styles::text_warning());
+        }
+        if self.retrying {
+            let badge = "⟳ retrying…";
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            buf.set_string(badge_x, area.y, badge, styles::text_warning());
+        }
+        if self.read_only {
+            let badge = "READ-ONLY";
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            buf.set_string(badge_x, area.y, badge, styles::text_dim());
+        }
+        if let Some(remaining) = self.full_blast {
+            let secs = remaining.as_secs();
+            let badge = format!("▲ FULL BLAST {}:{:02}", secs / 60, secs % 60);
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            let style = styles::text_error().add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            buf.set_string(badge_x, area.y, &badge, style);
+        }
+        if let Some(cap) = self.fan_cap {
+            let badge = format!("☾ fans ≤{}%", cap);
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            buf.set_string(badge_x, area.y, &badge, styles::text_highlight());
+        }
+        if let Some(elapsed) = self.refreshing {
+            let frame = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+            let badge = format!("{} refreshing", frame);
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            buf.set_string(badge_x, area.y, &badge, styles::text_highlight());
+        }
+        if let Some(elapsed) = self.tuning {
+            let badge = format!("◉ tune {}s", elapsed.as_secs());
+            badge_x = badge_x.saturating_sub(badge.chars().count() as u16 + 1);
+            buf.set_string(badge_x, area.y, &badge, styles::text_warning());
+        }
+    }
+}
+
+/// Toast popup for critical alerts and notices
+pub struct Toast<'a> {
+    message: &'a str,
+    severity: Severity,
+}
+
+impl<'a> Toast<'a> {
+    pub fn new(message: &'a str) -> Self {
+        Self {
+            message,
+            severity: Severity::Critical,
+        }
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+impl Widget for Toast<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let (title, title_style, border_style) = match self.severity {
+            Severity::Critical | Severity::Error => {
+                ("⚠ alert", styles::text_error(), styles::border_alert())
+            }
+            Severity::Debug | Severity::Info | Severity::Warning => {
+                ("notice", styles::text_highlight(), styles::border_focused())
+            }
+        };
+
+        let block = Block::default()
+            .title(title)
+            .title_style(title_style)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        Paragraph::new(self.message)
+            .style(styles::text())
+            .render(inner, buf);
+    }
+}
+
+/// Header widget with Oni logo and title
+pub struct Header<'a> {
+    compact: bool,
+    art: HeaderArt,
+    model: Option<&'a str>,
+    profile: Option<PowerProfile>,
+}
+
+impl<'a> Header<'a> {
+    pub fn new() -> Self {
+        Self {
+            compact: false,
+            art: HeaderArt::default(),
+            model: None,
+            profile: None,
+        }
+    }
+
+    /// Single row with icon, title, model and profile badge instead of the art
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    pub fn art(mut self, art: HeaderArt) -> Self {
+        self.art = art;
+        self
+    }
+
+    /// Laptop model shown in the compact header
+    pub fn model(mut self, model: Option<&'a str>) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Active profile, shown as a badge in the compact header
+    pub fn profile(mut self, profile: Option<PowerProfile>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Rows the header needs for its art, with a blank row above and below
+    pub fn height(&self) -> u16 {
+        match self.art {
+            HeaderArt::None => 0,
+            _ if self.compact => 1,
+            HeaderArt::BigText => HACHI_BIG_TEXT.len() as u16 + 2,
+            HeaderArt::Bee => art_lines(BEE_LOGO).len() as u16 + 2,
+            HeaderArt::Oni => art_lines(ONI_MASK).len() as u16 + 2,
+        }
+    }
+}
+
+impl Default for Header<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lines of a multi-line art constant, without surrounding blank lines
+fn art_lines(art: &str) -> Vec<&str> {
+    art.trim_matches('\n').lines().collect()
+}
+
+/// Width of the widest line in columns
+fn art_width(lines: &[&str]) -> u16 {
+    lines.iter().map(|line| line.chars().count()).max().unwrap_or(0) as u16
+}
+
+/// Draw lines vertically centered in `area`, starting at column `x`
+fn render_art(lines: &[&str], x: u16, area: Rect, buf: &mut Buffer, color: impl Fn(usize, usize) -> Color) {
+    let top = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+
+    for (row, line) in lines.iter().enumerate() {
+        let y = top + row as u16;
+        if y >= area.y + area.height { break; }
+
+        let line_len = line.chars().count();
+        for (col, ch) in line.chars().enumerate() {
+            let x = x + col as u16;
+            if x >= area.x + area.width { break; }
+
+            if ch != ' ' {
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    cell.set_char(ch);
+                    cell.set_fg(color(col, line_len));
+                }
+            }
+        }
+    }
+}
+
+impl Header<'_> {
+    fn render_compact(self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![Span::styled(
+            " 蜂 ",
+            Style::default().fg(Color::Rgb(255, 200, 50)).add_modifier(Modifier::BOLD),
+        )];
+
+        // "HACHI" in the same cyan-to-pink gradient as the full title
+        let title = "HACHI";
+        let (r1, g1, b1) = (60, 203, 225);  // Neon Cyan
+        let (r2, g2, b2) = (255, 0, 85);    // Sakura Pink
+        let steps = title.len() as f32 - 1.0;
+        for (i, ch) in title.chars().enumerate() {
+            let t = i as f32 / steps;
+            let r = (r1 as f32 * (1.0 - t) + r2 as f32 * t) as u8;
+            let g = (g1 as f32 * (1.0 - t) + g2 as f32 * t) as u8;
+            let b = (b1 as f32 * (1.0 - t) + b2 as f32 * t) as u8;
+            spans.push(Span::styled(
+                ch.to_string(),
+                Style::default().fg(Color::Rgb(r, g, b)).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if let Some(model) = self.model {
+            spans.push(Span::styled(format!("  {}", model), styles::text_dim()));
+        }
+        if let Some(profile) = self.profile {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                format!(" {} ", profile),
+                Style::default()
+                    .fg(colors::VOID_BLACK)
+                    .bg(profile_color(&profile))
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        buf.set_line(area.x, area.y, &Line::from(spans), area.width);
+    }
+}
+
+impl Widget for Header<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.art == HeaderArt::None || area.height == 0 {
+            return;
+        }
+        if self.compact {
+            self.render_compact(area, buf);
+            return;
+        }
+        if area.height < 2 || area.width < 40 {
+            return;
+        }
+
+        // Title on the left, after the logo when the art has one
+        let left_padding = 3u16;
+        let mut text_x = area.x + left_padding;
+
+        let logo = match self.art {
+            HeaderArt::Bee => Some((BEE_LOGO, Color::Rgb(255, 200, 50))),
+            HeaderArt::Oni => Some((ONI_MASK, colors::RONIN_RED)),
+            _ => None,
+        };
+        if let Some((art, logo_color)) = logo {
+            let lines = art_lines(art);
+            render_art(&lines, text_x, area, buf, |_, _| logo_color);
+            text_x += art_width(&lines) + 3;
+        }
+
+        let title = match self.art {
+            HeaderArt::BigText => HACHI_BIG_TEXT.to_vec(),
+            _ => art_lines(HACHI_BANNER),
+        };
+
+        // Gradient start/end colors: Cyan -> Pink
+        let (r1, g1, b1) = (60, 203, 225);  // Neon Cyan
+        let (r2, g2, b2) = (255, 0, 85);    // Sakura Pink
+
+        // Render title with a gradient across its columns
+        render_art(&title, text_x, area, buf, |col, line_len| {
+            // Linear interpolation for gradient based on column
+            let t = col as f32 / line_len as f32;
+            let r = (r1 as f32 * (1.0 - t) + r2 as f32 * t) as u8;
+            let g = (g1 as f32 * (1.0 - t) + g2 as f32 * t) as u8;
+            let b = (b1 as f32 * (1.0 - t) + b2 as f32 * t) as u8;
+            Color::Rgb(r, g, b)
+        });
+
+        // Render subtitle to the right of the title, vertically centered
+        let subtitle_x = text_x + art_width(&title) + 4;
+        let subtitle_y = area.y + area.height / 2; // Center vertically
+
+        if subtitle_x < area.x + area.width && subtitle_y < area.y + area.height {
+            let subtitle = Line::from(vec![
+                Span::styled("蜂 ", Style::default().fg(Color::Rgb(255, 200, 50)).add_modifier(Modifier::BOLD)),
+                Span::styled("ASUS ROG Control Center", styles::text_dim()),
+            ]);
+            buf.set_line(subtitle_x, subtitle_y, &subtitle, area.width.saturating_sub(subtitle_x));
+        }
+    }
+}
+
+/// Keys that can follow a pending prefix (`Q`, `@`, a count), shown in
+/// columns just above the status bar
+pub struct WhichKey<'a> {
+    title: &'a str,
+    keys: &'a [(String, String)],
+}
+
+impl<'a> WhichKey<'a> {
+    pub fn new(title: &'a str, keys: &'a [(String, String)]) -> Self {
+        Self { title, keys }
+    }
+
+    /// Rows needed at `width`, borders included
+    pub fn height(&self, width: u16) -> u16 {
+        let columns = self.columns(width);
+        self.keys.len().div_ceil(columns) as u16 + 2
+    }
+
+    fn key_width(&self) -> usize {
+        self.keys.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0)
+    }
+
+    fn entry_width(&self) -> usize {
+        let action = self.keys.iter().map(|(_, action)| action.chars().count()).max();
+        self.key_width() + action.unwrap_or(0) + 6
+    }
+
+    fn columns(&self, width: u16) -> usize {
+        (width.saturating_sub(2) as usize / self.entry_width()).max(1)
+    }
+}
+
+impl Widget for WhichKey<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .title_style(styles::title())
+            .borders(Borders::TOP | Borders::BOTTOM)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let columns = self.columns(area.width);
+        let (key_width, entry_width) = (self.key_width(), self.entry_width());
+        for (i, (key, action)) in self.keys.iter().enumerate() {
+            let x = inner.x + 1 + ((i % columns) * entry_width) as u16;
+            let y = inner.y + (i / columns) as u16;
+            if y >= inner.bottom() || x >= inner.right() {
+                continue;
+            }
+            let line = Line::from(vec![
+                Span::styled(format!("{:key_width$}", key), styles::text_highlight()),
+                Span::styled(" → ", styles::text_dim()),
+                Span::styled(action.as_str(), styles::text()),
+            ]);
+            buf.set_line(x, y, &line, inner.right() - x);
+        }
+    }
+}
+
+/// Keys of the focused panel (`?` on a panel), from the panel registry
+pub struct PanelHelp<'a> {
+    title: &'a str,
+    keys: &'a [(&'a str, &'a str)],
+}
+
+impl<'a> PanelHelp<'a> {
+    pub fn new(title: &'a str, keys: &'a [(&'a str, &'a str)]) -> Self {
+        Self { title, keys }
+    }
+}
+
+impl Widget for PanelHelp<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let hint = Span::styled(" ? all keys · Esc close ", styles::text_dim());
+        let block = Block::default()
+            .title(format!("help · {}", self.title.to_lowercase()))
+            .title_style(styles::title())
+            .title_bottom(Line::from(hint).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let width = self.keys.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0);
+        let lines: Vec<Line> = self
+            .keys
+            .iter()
+            .map(|(key, action)| {
+                Line::from(vec![
+                    Span::styled(format!("  {:width$}  ", key), styles::text_highlight()),
+                    Span::styled(*action, styles::text()),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(inner.inner(Margin::new(0, 1)), buf);
+    }
+}
+
+/// Help popup widget
+pub struct HelpPopup;
+
+impl Widget for HelpPopup {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Semi-transparent background
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let block = Block::default()
+            .title("help")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -736,8 +2499,11 @@ impl Widget for HelpPopup {
             Line::from(vec![
                 Span::styled("Navigation", styles::text_highlight()),
             ]),
+            Line::from("  0               - Dashboard"),
+            Line::from("  1-8             - Focus panel"),
             Line::from("  H / L (Shift)   - Cycle panels"),
             Line::from("  Tab / Shift+Tab - Cycle panels"),
+            Line::from("  g / G           - First / last panel"),
             Line::from("  k / j           - Select option"),
             Line::from("  Enter           - Confirm / Edit"),
             Line::from("  Esc             - Cancel / Back"),
@@ -745,11 +2511,33 @@ impl Widget for HelpPopup {
             Line::from(vec![Span::styled("Controls", styles::text_highlight())]),
             Line::from("  ← / →           - Adjust values"),
             Line::from("  Space           - Toggle"),
+            Line::from("  + / -           - Display brightness / fan hysteresis"),
+            Line::from("  p               - Keyboard color picker"),
+            Line::from("  t               - Keyboard color follows CPU temperature"),
+            Line::from("  t               - Fan graph temperature source"),
+            Line::from("  n / m / d       - New / rename / delete preset"),
             Line::from(""),
             Line::from(vec![Span::styled("Global", styles::text_highlight())]),
             Line::from("  q               - Quit"),
             Line::from("  r               - Refresh state"),
-            Line::from("  ?               - Toggle help"),
+            Line::from("  u               - Toggle °C / °F"),
+            Line::from("  a               - Cycle header art"),
+            Line::from("  c               - Compact header"),
+            Line::from("  y               - Copy state summary"),
+            Line::from("  e               - Export fan curve (SVG)"),
+            Line::from("  F               - Full blast: fans at 100% for 5 min"),
+            Line::from("  Q<a-z> … Q      - Record macro"),
+            Line::from("  @<a-z> / @@     - Replay macro / last macro"),
+            Line::from("  F1 / F2 / F3    - Monitoring / control / minimal layout"),
+            Line::from("  F5 / F6         - Travel / desk scenario, F7-F9 your own"),
+            Line::from("  Ctrl+R          - Record telemetry"),
+            Line::from("  Ctrl+Z          - Suspend (resume with fg)"),
+            Line::from("  :               - Message history (/ search, d/i/w/e level, y copy)"),
+            Line::from("  T               - Tune: stress run with thermal trace"),
+            Line::from("  D               - Health check"),
+            Line::from("  B               - Save a bug report (JSON)"),
+            Line::from("  F10             - Performance overlay"),
+            Line::from("  ?               - Panel help, again for all keys"),
         ];
 
         let para = Paragraph::new(help_text)
@@ -758,3 +2546,432 @@ impl Widget for HelpPopup {
         para.render(inner, buf);
     }
 }
+
+/// Status message history (`:`), newest first, with an optional search
+pub struct MessagesPopup<'a> {
+    messages: &'a [&'a Message],
+    query: &'a str,
+    searching: bool,
+    least: Severity,
+    scroll: usize,
+}
+
+impl<'a> MessagesPopup<'a> {
+    pub fn new(messages: &'a [&'a Message]) -> Self {
+        Self {
+            messages,
+            query: "",
+            searching: false,
+            least: Severity::Info,
+            scroll: 0,
+        }
+    }
+
+    /// Least severity listed, named in the title
+    pub fn least(mut self, least: Severity) -> Self {
+        self.least = least;
+        self
+    }
+
+    /// Search text, and whether it is still being typed
+    pub fn query(mut self, query: &'a str, searching: bool) -> Self {
+        self.query = query;
+        self.searching = searching;
+        self
+    }
+
+    pub fn scroll(mut self, scroll: usize) -> Self {
+        self.scroll = scroll;
+        self
+    }
+}
+
+impl Widget for MessagesPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let hints = Span::styled(
+            " / search · d/i/w/e level · y copy · Esc close ",
+            styles::text_dim(),
+        );
+        let title = match self.least {
+            Severity::Debug => format!("messages ({})", self.messages.len()),
+            least => format!("messages ({}, {} and up)", self.messages.len(), least.as_str()),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_style(styles::title())
+            .title_bottom(Line::from(hints).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let mut lines = Vec::new();
+        if self.searching || !self.query.is_empty() {
+            let cursor = if self.searching { "▏" } else { "" };
+            lines.push(Line::styled(
+                format!("/{}{}", self.query, cursor),
+                styles::text_highlight(),
+            ));
+        }
+        if self.messages.is_empty() {
+            lines.push(Line::styled("No messages", styles::text_dim()));
+        }
+
+        let rows = (inner.height as usize).saturating_sub(lines.len());
+        let scroll = self.scroll.min(self.messages.len().saturating_sub(rows));
+        for message in self.messages.iter().skip(scroll).take(rows) {
+            let (severity_style, text_style) = match message.severity {
+                Severity::Error | Severity::Critical => (styles::text_error(), styles::text()),
+                Severity::Warning => (styles::text_warning(), styles::text()),
+                Severity::Info => (styles::text(), styles::text()),
+                Severity::Debug => (styles::text_dim(), styles::text_dim()),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", message.timestamp()), styles::text_dim()),
+                Span::styled(format!("{:5} ", message.severity.as_str()), severity_style),
+                Span::styled(message.text.as_str(), text_style),
+            ]));
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+/// Raw D-Bus console (`` ` `` with `--debug-console`): the latest output
+/// above a prompt line
+pub struct ConsolePopup<'a> {
+    console: &'a Console,
+}
+
+impl<'a> ConsolePopup<'a> {
+    pub fn new(console: &'a Console) -> Self {
+        Self { console }
+    }
+}
+
+impl Widget for ConsolePopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let hints = Span::styled(" Enter run · ↑/↓ recall · Esc close ", styles::text_dim());
+        let block = Block::default()
+            .title("asusd console")
+            .title_style(styles::title())
+            .title_bottom(Line::from(hints).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        // Newest output at the bottom, just above the prompt
+        let rows = (inner.height as usize).saturating_sub(1);
+        let output = &self.console.output;
+        let mut lines: Vec<Line> = output[output.len().saturating_sub(rows)..]
+            .iter()
+            .map(|line| {
+                let style = if line.starts_with("> ") {
+                    styles::text_highlight()
+                } else if line.starts_with("error: ") {
+                    styles::text_error()
+                } else {
+                    styles::text()
+                };
+                Line::styled(line.as_str(), style)
+            })
+            .collect();
+        lines.push(Line::styled(
+            format!("> {}▏", self.console.input),
+            styles::text_highlight(),
+        ));
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+/// Stress run temperature traces (`T`): the latest run against the one
+/// before it, with peak temperature, fan speed and cooldown time for each
+pub struct TunePopup<'a> {
+    before: Option<&'a Trace>,
+    after: Option<&'a Trace>,
+    command: &'a str,
+    /// Elapsed time and whether the load is still running, during a run
+    running: Option<(Duration, bool)>,
+    unit: TemperatureUnit,
+}
+
+impl<'a> TunePopup<'a> {
+    pub fn new(before: Option<&'a Trace>, after: Option<&'a Trace>, command: &'a str) -> Self {
+        Self {
+            before,
+            after,
+            command,
+            running: None,
+            unit: TemperatureUnit::default(),
+        }
+    }
+
+    pub fn running(mut self, running: Option<(Duration, bool)>) -> Self {
+        self.running = running;
+        self
+    }
+
+    pub fn unit(mut self, unit: TemperatureUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// `before  peak 91°C · 4800 rpm · cooled in 24s`
+    fn summary(&self, name: &'static str, trace: &Trace, style: Style) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("{:7}", name), style),
+            Span::styled(trace.summary(self.unit).join(" · "), styles::text()),
+        ])
+    }
+}
+
+impl Widget for TunePopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let (status, hint) = match self.running {
+            Some((elapsed, loading)) => {
+                let phase = if loading { "loading" } else { "cooling" };
+                let hint = if loading { " Enter end load " } else { " Enter stop " };
+                (format!("{} {}s", phase, elapsed.as_secs()), hint)
+            }
+            None => ("idle".to_string(), " Enter run · Esc close "),
+        };
+        let block = Block::default()
+            .title(format!("tune · {}", status))
+            .title_style(styles::title())
+            .title_bottom(Line::from(Span::styled(hint, styles::text_dim())).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [header, chart_area, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(4),
+            Constraint::Length(2),
+        ])
+        .areas(inner);
+        Line::from(vec![
+            Span::styled("load: ", styles::text_dim()),
+            Span::styled(self.command, styles::text()),
+        ])
+        .render(header, buf);
+
+        let before_style = Style::default().fg(colors::STEEL_GRAY);
+        let after_style = Style::default().fg(colors::NEON_CYAN);
+        let points = |trace: Option<&Trace>| -> Vec<(f64, f64)> {
+            trace.map_or_else(Vec::new, |trace| {
+                trace
+                    .samples
+                    .iter()
+                    .map(|s| (s.secs as f64, self.unit.convert(s.celsius) as f64))
+                    .collect()
+            })
+        };
+        let (before, after) = (points(self.before), points(self.after));
+        if before.is_empty() && after.is_empty() {
+            Paragraph::new("No runs yet: Enter starts the load command")
+                .style(styles::text_dim())
+                .alignment(Alignment::Center)
+                .render(chart_area, buf);
+        } else {
+            let duration = [self.before, self.after]
+                .into_iter()
+                .flatten()
+                .map(Trace::duration_secs)
+                .fold(10.0, f32::max) as f64;
+            let temps = before.iter().chain(&after).map(|(_, t)| *t);
+            let low = temps.clone().fold(f64::MAX, f64::min).floor() - 5.0;
+            let high = temps.fold(f64::MIN, f64::max).ceil() + 5.0;
+            let symbol = self.unit.symbol();
+            let chart = Chart::new(vec![
+                Dataset::default()
+                    .name("before")
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(before_style)
+                    .data(&before),
+                Dataset::default()
+                    .name("after")
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(after_style)
+                    .data(&after),
+            ])
+            .x_axis(
+                Axis::default()
+                    .style(styles::text_dim())
+                    .bounds([0.0, duration])
+                    .labels(["0s".to_string(), format!("{:.0}s", duration)]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(styles::text_dim())
+                    .bounds([low, high])
+                    .labels([format!("{:.0}{}", low, symbol), format!("{:.0}{}", high, symbol)]),
+            );
+            chart.render(chart_area, buf);
+        }
+
+        let runs = [
+            ("before", self.before, before_style),
+            ("after", self.after, after_style),
+        ];
+        let lines: Vec<Line> = runs
+            .into_iter()
+            .filter_map(|(name, trace, style)| Some(self.summary(name, trace?, style)))
+            .collect();
+        Paragraph::new(lines).render(footer, buf);
+    }
+}
+
+/// Startup health checklist (`D`): one line per check, with a spinner
+/// while the hardware actor is still answering
+pub struct HealthPopup<'a> {
+    checks: &'a [Check],
+    /// Time since the checks started, while they're still running
+    running: Option<Duration>,
+}
+
+impl<'a> HealthPopup<'a> {
+    pub fn new(checks: &'a [Check]) -> Self {
+        Self {
+            checks,
+            running: None,
+        }
+    }
+
+    pub fn running(mut self, elapsed: Option<Duration>) -> Self {
+        self.running = elapsed;
+        self
+    }
+
+    /// Rows needed to show every check
+    pub fn height(&self) -> u16 {
+        self.checks.len().max(1) as u16 + 4
+    }
+}
+
+impl Widget for HealthPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let hint = Span::styled(" D re-run · any key continue ", styles::text_dim());
+        let block = Block::default()
+            .title("health check")
+            .title_style(styles::title())
+            .title_bottom(Line::from(hint).right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(styles::border_focused());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let lines: Vec<Line> = match self.running {
+            Some(elapsed) => {
+                let frame = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+                vec![Line::from(vec![
+                    Span::styled(format!("  {} ", frame), styles::text_highlight()),
+                    Span::styled("checking asusd...", styles::text_dim()),
+                ])]
+            }
+            None => {
+                let width = self.checks.iter().map(|c| c.name.chars().count()).max().unwrap_or(0);
+                self.checks
+                    .iter()
+                    .map(|check| {
+                        let style = match check.status {
+                            CheckStatus::Pass => Style::default().fg(colors::NEON_CYAN),
+                            CheckStatus::Warn => styles::text_warning(),
+                            CheckStatus::Fail => styles::text_error(),
+                        };
+                        Line::from(vec![
+                            Span::styled(format!("  {} ", check.status.symbol()), style),
+                            Span::styled(format!("{:width$}  ", check.name), styles::text()),
+                            Span::styled(check.detail.as_str(), styles::text_dim()),
+                        ])
+                    })
+                    .collect()
+            }
+        };
+        Paragraph::new(lines).render(inner.inner(Margin::new(0, 1)), buf);
+    }
+}
+
+/// Numbers behind the performance overlay
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PerfStats {
+    /// Last frame's draw time, and the slowest of the recent ones
+    pub frame: Duration,
+    pub frame_max: Duration,
+    pub effects: usize,
+    pub particles: usize,
+    /// Last asusd call, reply included
+    pub dbus_latency: Option<Duration>,
+    pub queued_updates: usize,
+    pub queued_intents: usize,
+}
+
+/// Debug overlay (`F10`) for diagnosing sluggishness: frame time, effect
+/// and particle counts, asusd latency and channel depths
+pub struct PerfOverlay<'a> {
+    stats: &'a PerfStats,
+}
+
+impl<'a> PerfOverlay<'a> {
+    /// Outer size, borders included
+    pub const WIDTH: u16 = 34;
+    pub const HEIGHT: u16 = 7;
+
+    pub fn new(stats: &'a PerfStats) -> Self {
+        Self { stats }
+    }
+}
+
+impl Widget for PerfOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, Style::default().bg(colors::SHADOW_GRAY));
+
+        let block = Block::default()
+            .title("perf")
+            .title_style(styles::title())
+            .borders(Borders::ALL)
+            .border_style(styles::border());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let millis = |d: Duration| format!("{:.1} ms", d.as_secs_f64() * 1000.0);
+        let stats = self.stats;
+        let rows = [
+            ("frame", format!("{} (max {})", millis(stats.frame), millis(stats.frame_max))),
+            ("effects", stats.effects.to_string()),
+            ("particles", stats.particles.to_string()),
+            ("asusd", stats.dbus_latency.map_or("-".to_string(), millis)),
+            (
+                "queued",
+                format!("{} updates, {} intents", stats.queued_updates, stats.queued_intents),
+            ),
+        ];
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .map(|(label, value)| {
+                Line::from(vec![
+                    Span::styled(format!(" {:10}", label), styles::text_dim()),
+                    Span::styled(value, styles::text()),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+}